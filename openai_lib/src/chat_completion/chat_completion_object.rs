@@ -13,7 +13,8 @@ pub struct ChatCompletionObject {
     pub choices: Vec<ChatCompletionChoice>,
     pub created: i64,
     pub model: ChatModel,
-    pub system_fingerprint: String,
+    #[serde(default)]
+    pub system_fingerprint: Option<String>,
     pub object: String,
     pub usage: UsageStatistics,
 }
@@ -34,3 +35,39 @@ impl ChatCompletionObject {
         String::new()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn deserializes_missing_system_fingerprint() {
+        let response = json!({
+            "id": "chatcmpl-123",
+            "object": "chat.completion",
+            "created": 1677652288,
+            "model": "gpt-3.5-turbo-1106",
+            "choices": [{
+                "index": 0,
+                "message": {
+                    "role": "assistant",
+                    "content": "Hello there."
+                },
+                "logprobs": null,
+                "finish_reason": "stop"
+            }],
+            "usage": {
+                "prompt_tokens": 9,
+                "completion_tokens": 12,
+                "total_tokens": 21
+            }
+        });
+
+        let object = serde_json::from_value::<ChatCompletionObject>(response).unwrap();
+
+        assert_eq!(object.system_fingerprint, None);
+        assert_eq!(object.get_content(), "Hello there.");
+    }
+}
@@ -299,4 +299,21 @@ impl RunClient for OpenAIClient {
 
         self.handle_response::<RunObject>(response).await
     }
+
+    async fn cancel_run(&self, thread_id: &str, run_id: &str) -> Result<RunObject, Error> {
+        let url = format!(
+            "https://api.openai.com/v1/threads/{}/runs/{}/cancel",
+            thread_id, run_id
+        );
+
+        let response = self
+            .client
+            .post(url)
+            .header("OpenAI-Beta", "assistants=v1")
+            .send()
+            .await
+            .map_err(|e| Error::RequestFailure(e.into()))?;
+
+        self.handle_response::<RunObject>(response).await
+    }
 }
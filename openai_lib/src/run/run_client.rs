@@ -18,4 +18,6 @@ pub trait LocalRunClient {
         thread_id: &str,
         run_is: &str,
     ) -> Result<RunObject, Error>;
+
+    async fn cancel_run(&self, thread_id: &str, run_id: &str) -> Result<RunObject, Error>;
 }
@@ -5,6 +5,7 @@ use log::error;
 use tokio::sync::mpsc;
 use tokio::sync::Mutex;
 
+use crate::config::run_polling_config::RunPollingConfig;
 use crate::file_manager::FileManager;
 use crate::game::game_generation_update::GameGenerationUpdate;
 use openai_lib::OpenAIClient;
@@ -15,6 +16,7 @@ pub struct ApplicationState {
     pub updates_tx: Arc<Mutex<mpsc::Sender<GameGenerationUpdate>>>,
     pub file_manager: Option<FileManager>,
     pub openai_client: Option<OpenAIClient>,
+    pub run_polling_config: RunPollingConfig,
 }
 
 impl ApplicationState {
@@ -23,6 +25,7 @@ impl ApplicationState {
             updates_tx: Arc::new(updates_tx),
             file_manager: None,
             openai_client: None,
+            run_polling_config: RunPollingConfig::default(),
         }
     }
 
@@ -34,6 +37,10 @@ impl ApplicationState {
         self.openai_client = Some(openai_client);
     }
 
+    pub fn set_run_polling_config(&mut self, run_polling_config: RunPollingConfig) {
+        self.run_polling_config = run_polling_config;
+    }
+
     #[allow(dead_code)]
     pub fn verify_setup(&self) -> Result<(), anyhow::Error> {
         ensure!(self.file_manager.is_some(), "File system not set up.");
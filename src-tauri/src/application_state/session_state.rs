@@ -1,18 +1,28 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
-use crate::{game_session::GameSession, game_state::GameState};
+use crate::{
+    game_session::{session_handle::SessionHandle, session_request_update::SessionRequestUpdate},
+    game_state::GameState,
+};
 use tokio::sync::{mpsc::Sender, Mutex};
 
+/// The game sessions being played, keyed by their session id. Sessions keep running until they're
+/// suspended, so the player can switch between games without losing one that's mid-response.
 pub struct SessionState {
-    game_session: Option<GameSession>,
+    sessions: HashMap<String, SessionHandle>,
     state_update_tx: Arc<Mutex<Sender<GameState>>>,
+    request_update_tx: Arc<Mutex<Sender<SessionRequestUpdate>>>,
 }
 
 impl SessionState {
-    pub fn new(state_update_tx: Mutex<Sender<GameState>>) -> Self {
+    pub fn new(
+        state_update_tx: Mutex<Sender<GameState>>,
+        request_update_tx: Mutex<Sender<SessionRequestUpdate>>,
+    ) -> Self {
         Self {
-            game_session: None,
+            sessions: HashMap::new(),
             state_update_tx: Arc::new(state_update_tx),
+            request_update_tx: Arc::new(request_update_tx),
         }
     }
 
@@ -20,11 +30,19 @@ impl SessionState {
         self.state_update_tx.clone()
     }
 
-    pub fn get_game_session(&mut self) -> Option<&mut GameSession> {
-        self.game_session.as_mut()
+    pub fn get_request_update_tx(&self) -> Arc<Mutex<Sender<SessionRequestUpdate>>> {
+        self.request_update_tx.clone()
     }
 
-    pub async fn set_game_session(&mut self, game_session: GameSession) {
-        self.game_session = Some(game_session);
+    pub fn get_session_handle(&mut self, session_id: &str) -> Option<&mut SessionHandle> {
+        self.sessions.get_mut(session_id)
+    }
+
+    pub fn add_session_handle(&mut self, session_id: String, session_handle: SessionHandle) {
+        self.sessions.insert(session_id, session_handle);
+    }
+
+    pub fn remove_session_handle(&mut self, session_id: &str) -> Option<SessionHandle> {
+        self.sessions.remove(session_id)
     }
 }
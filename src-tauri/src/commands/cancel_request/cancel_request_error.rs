@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelRequestError {
+    pub message: String,
+}
+
+impl CancelRequestError {
+    pub fn new(message: &str) -> Self {
+        CancelRequestError {
+            message: message.to_string(),
+        }
+    }
+}
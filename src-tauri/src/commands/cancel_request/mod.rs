@@ -0,0 +1,33 @@
+use crate::application_state::session_state::SessionState;
+
+use self::{
+    cancel_request_error::CancelRequestError, cancel_request_request::CancelRequestRequest,
+};
+
+use log::info;
+use tauri::State;
+use tokio::sync::Mutex;
+
+mod cancel_request_error;
+mod cancel_request_request;
+
+/// Stops waiting on the narrator or character's response, and drops any requests queued behind
+/// it. The cancelled requests are reported through request updates.
+#[tauri::command]
+pub async fn cancel_request(
+    request: CancelRequestRequest,
+    session_state: State<'_, Mutex<SessionState>>,
+) -> Result<(), CancelRequestError> {
+    info!("Received request to cancel session requests");
+
+    let mut session_state = session_state.lock().await;
+
+    let session_handle = session_state.get_session_handle(&request.session_id);
+    let session_handle = session_handle.ok_or(CancelRequestError::new(
+        "Unable to cancel: No active game session.",
+    ))?;
+
+    session_handle.cancel();
+
+    Ok(())
+}
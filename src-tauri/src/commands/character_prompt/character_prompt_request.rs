@@ -2,7 +2,15 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CharacterPromptRequest {
+    pub session_id: String,
     pub message: Option<String>,
     pub trade_accept: Option<bool>,
+    pub trade_counter_offer: Option<CharacterTradeOffer>,
     pub end_conversation: Option<bool>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CharacterTradeOffer {
+    pub to_player: Vec<String>,
+    pub from_player: Vec<String>,
+}
@@ -1,14 +1,12 @@
 use serde::{Deserialize, Serialize};
 
-use crate::game_state::GameState;
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CharacterPromptResponse {
-    game_state: GameState,
+    request_id: u64,
 }
 
 impl CharacterPromptResponse {
-    pub fn new(game_state: GameState) -> Self {
-        Self { game_state }
+    pub fn new(request_id: u64) -> Self {
+        Self { request_id }
     }
 }
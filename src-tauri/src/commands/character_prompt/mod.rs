@@ -2,7 +2,9 @@ use log::{error, info};
 use tauri::State;
 use tokio::sync::Mutex;
 
-use crate::application_state::session_state::SessionState;
+use crate::{
+    application_state::session_state::SessionState, game_session::session_command::SessionCommand,
+};
 
 use self::{
     character_prompt_error::CharacterPromptError, character_prompt_request::CharacterPromptRequest,
@@ -23,64 +25,45 @@ pub async fn character_prompt(
         &request
     );
 
-    let mut session_state = session_state.lock().await;
+    let session_id = request.session_id.clone();
 
-    let game_session = session_state
-        .get_game_session()
-        .ok_or(CharacterPromptError::new("Unable to access game session."))?;
-
-    match request {
+    let command = match request {
         CharacterPromptRequest {
             message: Some(message),
             ..
-        } => {
-            let updated_game_state =
-                game_session
-                    .receive_player_message(message)
-                    .await
-                    .map_err(|e| {
-                        error!("unable to update game state with new prompt:\n{:?}", e);
-                        CharacterPromptError::new("an error occurred processing the request.")
-                    })?;
-
-            let response = CharacterPromptResponse::new(updated_game_state);
-            Ok(response)
-        }
+        } => SessionCommand::PlayerMessage(message),
         CharacterPromptRequest {
             trade_accept: Some(accepted),
             ..
-        } => {
-            let updated_game_state = game_session
-                .receive_trade_response(accepted)
-                .await
-                .map_err(|e| {
-                    error!("unable to update game state with new prompt:\n{:?}", e);
-                    CharacterPromptError::new("an error occurred processing the request.")
-                })?;
-
-            let response = CharacterPromptResponse::new(updated_game_state);
-            Ok(response)
-        }
+        } => SessionCommand::TradeResponse(accepted),
+        CharacterPromptRequest {
+            trade_counter_offer: Some(offer),
+            ..
+        } => SessionCommand::TradeCounterOffer {
+            to_player: offer.to_player,
+            from_player: offer.from_player,
+        },
         CharacterPromptRequest {
             end_conversation: Some(_),
             ..
-        } => {
-            let updated_game_state =
-                game_session
-                    .end_character_interaction()
-                    .await
-                    .map_err(|e| {
-                        error!("unable to update game state with new prompt:\n{:?}", e);
-                        CharacterPromptError::new("an error occurred processing the request.")
-                    })?;
-
-            let response = CharacterPromptResponse::new(updated_game_state);
-            Ok(response)
-        }
+        } => SessionCommand::EndCharacterInteraction,
         _ => {
             return Err(CharacterPromptError::new(
                 "Invalid character prompt request.",
             ))
         }
-    }
+    };
+
+    let mut session_state = session_state.lock().await;
+
+    let session_handle = session_state
+        .get_session_handle(&session_id)
+        .ok_or(CharacterPromptError::new("Unable to access game session."))?;
+
+    let request_id = session_handle.send(command).map_err(|e| {
+        error!("unable to queue character prompt:\n{:?}", e);
+        CharacterPromptError::new("an error occurred processing the request.")
+    })?;
+
+    Ok(CharacterPromptResponse::new(request_id))
 }
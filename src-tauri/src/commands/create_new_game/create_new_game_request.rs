@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::config::content_setting::ContentSetting;
+use crate::{
+    config::content_setting::ContentSetting,
+    utils::{random::Random, stable_hash::StableHash},
+};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CreateNewGameRequest {
@@ -9,6 +12,8 @@ pub struct CreateNewGameRequest {
     pub image_content_setting: Option<ContentSetting>,
     pub temperature_setting: Option<String>,
     pub resume_previous: Option<String>,
+    pub template: Option<String>,
+    pub seed: Option<String>,
 }
 
 impl CreateNewGameRequest {
@@ -19,4 +24,48 @@ impl CreateNewGameRequest {
             .parse()
             .unwrap_or(1.0)
     }
+
+    /// Gets the seed provided by the user, or a random seed if none was provided. Seeds that aren't
+    /// numbers are hashed, so the same text always gives the same game.
+    pub fn get_seed(&self) -> u32 {
+        let seed = match self.seed.as_ref().map(|seed| seed.trim()) {
+            Some(seed) if !seed.is_empty() => seed,
+            _ => return Random::generate_seed(),
+        };
+
+        seed.parse().unwrap_or_else(|_| {
+            let hash = StableHash::fnv1a(seed.as_bytes());
+            (hash ^ (hash >> 32)) as u32
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn request(seed: Option<&str>) -> CreateNewGameRequest {
+        CreateNewGameRequest {
+            prompt: String::new(),
+            text_content_setting: None,
+            image_content_setting: None,
+            temperature_setting: None,
+            resume_previous: None,
+            template: None,
+            seed: seed.map(String::from),
+        }
+    }
+
+    #[test]
+    fn uses_numeric_seeds_directly() {
+        assert_eq!(request(Some(" 42 ")).get_seed(), 42);
+    }
+
+    #[test]
+    fn hashes_text_seeds() {
+        let seed = request(Some("haunted lighthouse")).get_seed();
+
+        assert_eq!(request(Some("haunted lighthouse")).get_seed(), seed);
+        assert_ne!(request(Some("sunken city")).get_seed(), seed);
+    }
 }
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportSessionGraphError {
+    pub message: String,
+}
+
+impl ExportSessionGraphError {
+    pub fn new(message: &str) -> Self {
+        ExportSessionGraphError {
+            message: message.to_string(),
+        }
+    }
+}
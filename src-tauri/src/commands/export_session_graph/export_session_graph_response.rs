@@ -0,0 +1,16 @@
+use serde::Serialize;
+
+use crate::session_context::state_history::StateTransition;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportSessionGraphResponse {
+    /// The state machine in Graphviz DOT format.
+    pub dot: String,
+    pub history: Vec<StateTransition>,
+}
+
+impl ExportSessionGraphResponse {
+    pub fn new(dot: String, history: Vec<StateTransition>) -> Self {
+        ExportSessionGraphResponse { dot, history }
+    }
+}
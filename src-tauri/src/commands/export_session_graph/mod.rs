@@ -0,0 +1,47 @@
+use crate::application_state::session_state::SessionState;
+
+use self::{
+    export_session_graph_error::ExportSessionGraphError,
+    export_session_graph_request::ExportSessionGraphRequest,
+    export_session_graph_response::ExportSessionGraphResponse,
+};
+
+use log::{error, info};
+use tauri::State;
+use tokio::sync::Mutex;
+
+mod export_session_graph_error;
+mod export_session_graph_request;
+mod export_session_graph_response;
+
+/// Exports the session's state machine and recent transitions, for debugging a session
+/// that got stuck or went wrong.
+#[tauri::command]
+pub async fn export_session_graph(
+    request: ExportSessionGraphRequest,
+    session_state: State<'_, Mutex<SessionState>>,
+) -> Result<ExportSessionGraphResponse, ExportSessionGraphError> {
+    info!("Received request to export session graph");
+
+    let graph = {
+        let mut session_state = session_state.lock().await;
+
+        let session_handle = session_state.get_session_handle(&request.session_id);
+        let session_handle = session_handle.ok_or(ExportSessionGraphError::new(
+            "Unable to export session graph: No active game session.",
+        ))?;
+
+        session_handle.export_graph().map_err(|e| {
+            error!("Unable to queue session graph export:\n{:?}", e);
+            ExportSessionGraphError::new("Unable to export session graph.")
+        })?
+    };
+
+    // The session state is unlocked first, so other commands aren't held up while this waits
+    // its turn
+    let (dot, history) = graph
+        .await
+        .map_err(|_| ExportSessionGraphError::new("Session graph export was cancelled."))?;
+
+    Ok(ExportSessionGraphResponse::new(dot, history))
+}
@@ -2,5 +2,6 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GamePromptRequest {
+    pub session_id: String,
     pub prompt: String,
 }
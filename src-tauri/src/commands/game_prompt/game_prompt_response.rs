@@ -1,14 +1,12 @@
 use serde::{Deserialize, Serialize};
 
-use crate::game_state::GameState;
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GamePromptResponse {
-    pub game_state: GameState,
+    pub request_id: u64,
 }
 
 impl GamePromptResponse {
-    pub fn new(game_state: GameState) -> Self {
-        GamePromptResponse { game_state }
+    pub fn new(request_id: u64) -> Self {
+        GamePromptResponse { request_id }
     }
 }
@@ -1,4 +1,6 @@
-use crate::application_state::session_state::SessionState;
+use crate::{
+    application_state::session_state::SessionState, game_session::session_command::SessionCommand,
+};
 
 use self::{
     game_prompt_error::GamePromptError, game_prompt_request::GamePromptRequest,
@@ -27,21 +29,19 @@ pub async fn game_prompt(
 
     let mut session_state = session_state.lock().await;
 
-    let game_session = session_state.get_game_session();
-    let game_session = game_session.ok_or(GamePromptError::new(
+    let session_handle = session_state.get_session_handle(&request.session_id);
+    let session_handle = session_handle.ok_or(GamePromptError::new(
         "Unable to submit prompt: No active game session.",
     ))?;
 
-    info!("Loaded game session, processing new prompt.");
-    let updated_game_state = game_session
-        .receive_player_message(request.prompt)
-        .await
+    let request_id = session_handle
+        .send(SessionCommand::PlayerMessage(request.prompt))
         .map_err(|e| {
-            error!("Unable to update game state with new prompt:\n{:?}", e);
+            error!("Unable to queue prompt:\n{:?}", e);
             GamePromptError::new("An error occurred processing the request.")
         })?;
 
-    info!("Received updated game state, returning to UI for rendering.");
+    info!("Queued prompt as request {}.", request_id);
 
-    Ok(GamePromptResponse::new(updated_game_state))
+    Ok(GamePromptResponse::new(request_id))
 }
@@ -0,0 +1,17 @@
+pub enum ListGenerationTemplatesError {
+    SetupError(String),
+}
+
+impl ListGenerationTemplatesError {
+    pub fn get_type(&self) -> String {
+        match self {
+            ListGenerationTemplatesError::SetupError(_) => String::from("setup_error"),
+        }
+    }
+
+    pub fn get_message(&self) -> String {
+        match self {
+            ListGenerationTemplatesError::SetupError(message) => message.clone(),
+        }
+    }
+}
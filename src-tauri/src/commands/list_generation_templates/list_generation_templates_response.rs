@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::GenerationTemplate;
+
+use super::list_generation_templates_error::ListGenerationTemplatesError;
+
+#[derive(Serialize, Deserialize)]
+pub struct ListGenerationTemplatesSuccessResponse {
+    success: bool,
+    templates: Vec<GenerationTemplate>,
+}
+
+impl ListGenerationTemplatesSuccessResponse {
+    pub fn new(templates: Vec<GenerationTemplate>) -> Self {
+        ListGenerationTemplatesSuccessResponse {
+            success: true,
+            templates,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ListGenerationTemplatesFailureResponse {
+    success: bool,
+    error: String,
+    message: String,
+}
+
+impl ListGenerationTemplatesFailureResponse {
+    pub fn new(error: ListGenerationTemplatesError) -> Self {
+        ListGenerationTemplatesFailureResponse {
+            success: false,
+            error: error.get_type(),
+            message: error.get_message(),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use log::info;
+use tauri::State;
+use tokio::sync::Mutex;
+
+use crate::{application_state::ApplicationState, game::GenerationTemplate};
+
+use self::{
+    list_generation_templates_error::ListGenerationTemplatesError,
+    list_generation_templates_response::{
+        ListGenerationTemplatesFailureResponse, ListGenerationTemplatesSuccessResponse,
+    },
+};
+
+mod list_generation_templates_error;
+mod list_generation_templates_response;
+
+#[tauri::command]
+pub async fn list_generation_templates(
+    application_state: State<'_, Mutex<ApplicationState>>,
+) -> Result<ListGenerationTemplatesSuccessResponse, ListGenerationTemplatesFailureResponse> {
+    let application_state = application_state.lock().await;
+
+    let file_manager = application_state.file_manager.as_ref().ok_or(
+        ListGenerationTemplatesFailureResponse::new(ListGenerationTemplatesError::SetupError(
+            String::from("Unable to access file manager."),
+        )),
+    )?;
+
+    let templates = GenerationTemplate::list(file_manager);
+    info!("Found {} generation templates.", templates.len());
+
+    Ok(ListGenerationTemplatesSuccessResponse::new(templates))
+}
@@ -1,5 +1,12 @@
+pub mod cancel_request;
 pub mod create_new_game;
+pub mod export_session_graph;
 pub mod game_prompt;
+pub mod list_generation_templates;
+pub mod regenerate;
+pub mod rewind_turn;
+pub mod save_game;
 pub mod setup;
 pub mod start_game;
+pub mod suspend_game;
 pub mod character_prompt;
\ No newline at end of file
@@ -0,0 +1,68 @@
+use log::{error, info};
+use tauri::State;
+use tokio::sync::Mutex;
+
+use crate::{application_state::ApplicationState, game::GameFactory};
+
+use self::{
+    regenerate_error::RegenerateError,
+    regenerate_request::RegenerateRequest,
+    regenerate_response::{RegenerateFailureResponse, RegenerateSuccessResponse},
+};
+
+mod regenerate_error;
+mod regenerate_request;
+mod regenerate_response;
+
+#[tauri::command]
+pub async fn regenerate(
+    request: RegenerateRequest,
+    application_state: State<'_, Mutex<ApplicationState>>,
+) -> Result<RegenerateSuccessResponse, RegenerateFailureResponse> {
+    let game_factory = {
+        let application_state = application_state.lock().await;
+
+        let file_manager =
+            application_state
+                .file_manager
+                .as_ref()
+                .ok_or(RegenerateFailureResponse::new(RegenerateError::SetupError(
+                    String::from("Unable to access file manager."),
+                )))?;
+
+        let openai_client =
+            application_state
+                .openai_client
+                .as_ref()
+                .ok_or(RegenerateFailureResponse::new(RegenerateError::SetupError(
+                    String::from("Unable to access OpenAI client."),
+                )))?;
+
+        GameFactory::resume(
+            &request.game_id,
+            openai_client,
+            file_manager,
+            &application_state.updates_tx,
+        )
+        .map_err(|e| {
+            error!("Unable to establish game factory:\n{:?}", e);
+            RegenerateFailureResponse::new(RegenerateError::SetupError(String::from(
+                "Unable to create factory for game regeneration.",
+            )))
+        })?
+    };
+
+    let game = game_factory
+        .regenerate(request.target.clone(), request.instruction)
+        .await
+        .map_err(|e| {
+            error!("Unable to regenerate {}:\n{:?}", &request.target, e);
+            RegenerateFailureResponse::new(RegenerateError::GenerationError(format!(
+                "Unable to regenerate {}.",
+                &request.target
+            )))
+        })?;
+
+    info!("Regenerated {} for game '{}'.", &request.target, game.id);
+    Ok(RegenerateSuccessResponse::new(game))
+}
@@ -0,0 +1,20 @@
+pub enum RegenerateError {
+    SetupError(String),
+    GenerationError(String),
+}
+
+impl RegenerateError {
+    pub fn get_type(&self) -> String {
+        match self {
+            RegenerateError::SetupError(_) => String::from("setup_error"),
+            RegenerateError::GenerationError(_) => String::from("generation_error"),
+        }
+    }
+
+    pub fn get_message(&self) -> String {
+        match self {
+            RegenerateError::SetupError(message) => message.clone(),
+            RegenerateError::GenerationError(message) => message.clone(),
+        }
+    }
+}
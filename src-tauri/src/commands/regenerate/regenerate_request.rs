@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::RegenerationTarget;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RegenerateRequest {
+    pub game_id: String,
+    pub target: RegenerationTarget,
+    pub instruction: Option<String>,
+}
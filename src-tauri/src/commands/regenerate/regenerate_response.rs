@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::Game;
+
+use super::regenerate_error::RegenerateError;
+
+#[derive(Serialize, Deserialize)]
+pub struct RegenerateSuccessResponse {
+    success: bool,
+    game: Game,
+}
+
+impl RegenerateSuccessResponse {
+    pub fn new(game: Game) -> Self {
+        RegenerateSuccessResponse {
+            success: true,
+            game,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RegenerateFailureResponse {
+    success: bool,
+    error: String,
+    message: String,
+}
+
+impl RegenerateFailureResponse {
+    pub fn new(error: RegenerateError) -> Self {
+        RegenerateFailureResponse {
+            success: false,
+            error: error.get_type(),
+            message: error.get_message(),
+        }
+    }
+}
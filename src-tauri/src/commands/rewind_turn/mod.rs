@@ -0,0 +1,41 @@
+use crate::{
+    application_state::session_state::SessionState, game_session::session_command::SessionCommand,
+};
+
+use self::{
+    rewind_turn_error::RewindTurnError, rewind_turn_request::RewindTurnRequest,
+    rewind_turn_response::RewindTurnResponse,
+};
+
+use log::{error, info};
+use tauri::State;
+use tokio::sync::Mutex;
+
+mod rewind_turn_error;
+mod rewind_turn_request;
+mod rewind_turn_response;
+
+#[tauri::command]
+pub async fn rewind_turn(
+    request: RewindTurnRequest,
+    session_state: State<'_, Mutex<SessionState>>,
+) -> Result<RewindTurnResponse, RewindTurnError> {
+    info!("Received request to rewind to turn {}", request.turn);
+
+    let mut session_state = session_state.lock().await;
+
+    let session_handle = session_state.get_session_handle(&request.session_id);
+    let session_handle = session_handle.ok_or(RewindTurnError::new(
+        "Unable to rewind: No active game session.",
+    ))?;
+
+    // Rewinding stops the narrator, rather than waiting for it to finish responding
+    let request_id = session_handle
+        .send(SessionCommand::Rewind(request.turn))
+        .map_err(|e| {
+            error!("Unable to queue rewind:\n{:?}", e);
+            RewindTurnError::new("Unable to rewind to that turn.")
+        })?;
+
+    Ok(RewindTurnResponse::new(request_id))
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RewindTurnError {
+    pub message: String,
+}
+
+impl RewindTurnError {
+    pub fn new(message: &str) -> Self {
+        RewindTurnError {
+            message: message.to_string(),
+        }
+    }
+}
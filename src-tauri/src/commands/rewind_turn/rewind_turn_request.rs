@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RewindTurnRequest {
+    pub session_id: String,
+    pub turn: u32,
+}
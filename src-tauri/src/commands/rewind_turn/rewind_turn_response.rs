@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RewindTurnResponse {
+    pub request_id: u64,
+}
+
+impl RewindTurnResponse {
+    pub fn new(request_id: u64) -> Self {
+        RewindTurnResponse { request_id }
+    }
+}
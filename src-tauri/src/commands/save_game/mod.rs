@@ -0,0 +1,42 @@
+use crate::{
+    application_state::session_state::SessionState, game_session::session_command::SessionCommand,
+};
+
+use self::{
+    save_game_error::SaveGameError, save_game_request::SaveGameRequest,
+    save_game_response::SaveGameResponse,
+};
+
+use log::{error, info};
+use tauri::State;
+use tokio::sync::Mutex;
+
+mod save_game_error;
+mod save_game_request;
+mod save_game_response;
+
+/// Saves the game to its save slot once the session is waiting on the player.
+#[tauri::command]
+pub async fn save_game(
+    request: SaveGameRequest,
+    session_state: State<'_, Mutex<SessionState>>,
+) -> Result<SaveGameResponse, SaveGameError> {
+    info!(
+        "Received request to save game session {}",
+        &request.session_id
+    );
+
+    let mut session_state = session_state.lock().await;
+
+    let session_handle = session_state.get_session_handle(&request.session_id);
+    let session_handle = session_handle.ok_or(SaveGameError::new(
+        "Unable to save: No active game session.",
+    ))?;
+
+    let request_id = session_handle.send(SessionCommand::Save).map_err(|e| {
+        error!("Unable to queue save:\n{:?}", e);
+        SaveGameError::new("Unable to save the game.")
+    })?;
+
+    Ok(SaveGameResponse::new(request_id))
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveGameError {
+    pub message: String,
+}
+
+impl SaveGameError {
+    pub fn new(message: &str) -> Self {
+        SaveGameError {
+            message: message.to_string(),
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveGameResponse {
+    pub request_id: u64,
+}
+
+impl SaveGameResponse {
+    pub fn new(request_id: u64) -> Self {
+        SaveGameResponse { request_id }
+    }
+}
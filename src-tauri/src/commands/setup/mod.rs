@@ -104,6 +104,7 @@ pub async fn setup(
         let mut state = state.lock().await;
         state.set_file_manager(file_manager);
         state.set_openai_client(openai_client);
+        state.set_run_polling_config(config.run_polling);
     }
 
     info!("Initialization complete.");
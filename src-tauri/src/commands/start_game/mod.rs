@@ -1,11 +1,17 @@
 use crate::{
     application_state::{session_state::SessionState, ApplicationState},
-    game_session::{game_session_error::GameSessionError, GameSession},
+    game_session::{
+        game_session_error::GameSessionError, session_command::SessionCommand,
+        session_handle::SessionHandle, GameSession,
+    },
+    game_state::{GameState, DEFAULT_SAVE_SLOT},
+    session_context::cancellation::Cancellation,
 };
 
 use self::{start_game_request::StartGameRequest, start_game_response::StartGameResponse};
 
 use log::error;
+use openai_lib::{assistant::AssistantClient, thread::ThreadClient, OpenAIClient};
 use tauri::State;
 use tokio::sync::Mutex;
 
@@ -18,40 +24,143 @@ pub async fn start_game(
     application_state: State<'_, Mutex<ApplicationState>>,
     session_state: State<'_, Mutex<SessionState>>,
 ) -> Result<StartGameResponse, GameSessionError> {
-    let application_state = application_state.lock().await;
-    let file_manager = &application_state.file_manager.as_ref();
-    let file_manager = file_manager.ok_or(GameSessionError::ConfigError(String::from(
-        "Unable to access file manager.",
-    )))?;
+    // Setting up a new game makes several requests to OpenAI, so neither state is kept locked
+    // while the session is built.
+    let (file_manager, openai_client, run_polling_config) = {
+        let application_state = application_state.lock().await;
+        let file_manager = application_state.file_manager.clone();
+        let file_manager = file_manager.ok_or(GameSessionError::ConfigError(String::from(
+            "Unable to access file manager.",
+        )))?;
 
-    let openai_client = &application_state.openai_client.as_ref();
-    let openai_client = openai_client.ok_or(GameSessionError::ConfigError(String::from(
-        "Unable to access OpenAI client.",
-    )))?;
+        let openai_client = application_state.openai_client.clone();
+        let openai_client = openai_client.ok_or(GameSessionError::ConfigError(String::from(
+            "Unable to access OpenAI client.",
+        )))?;
+
+        (
+            file_manager,
+            openai_client,
+            application_state.run_polling_config.clone(),
+        )
+    };
+
+    let save_slot = request
+        .save_slot
+        .unwrap_or_else(|| String::from(DEFAULT_SAVE_SLOT));
+    let session_id = GameState::session_id_for(&request.game_id, &save_slot);
+
+    let (game_state_update_tx, request_update_tx) = {
+        let mut session_state = session_state.lock().await;
+
+        if let Some(session_handle) = session_state.get_session_handle(&session_id) {
+            let game_state = session_handle.game_state();
+            return Ok(StartGameResponse::new(session_id, game_state, None));
+        }
+
+        (
+            session_state.get_state_update_tx(),
+            session_state.get_request_update_tx(),
+        )
+    };
+
+    let cancellation = Cancellation::default();
+
+    let saved_game_state = GameSession::load_save(&request.game_id, &save_slot, &file_manager)
+        .map_err(|e| {
+            error!("Unable to load saved game:\n{:?}", e);
+            GameSessionError::SetupFailure(format!("Error occurred while loading game:\n{:?}", e))
+        })?;
+
+    let resuming = saved_game_state.is_some();
+
+    let game_session = match saved_game_state {
+        Some(game_state) => GameSession::resume(
+            game_state,
+            &openai_client,
+            &run_polling_config,
+            cancellation.clone(),
+            &file_manager,
+            game_state_update_tx.clone(),
+        )
+        .map_err(|e| {
+            error!("Unable to resume game session:\n{:?}", e);
+            GameSessionError::SetupFailure(format!("Error occurred while resuming game:\n{:?}", e))
+        })?,
+        None => GameSession::start_new(
+            request.game_id,
+            save_slot,
+            &openai_client,
+            &run_polling_config,
+            cancellation.clone(),
+            &file_manager,
+            game_state_update_tx.clone(),
+        )
+        .await
+        .map_err(|e| {
+            error!("Unable to establish game session:\n{:?}", e);
+            GameSessionError::SetupFailure(format!(
+                "Error occurred while setting up game:\n{:?}",
+                e
+            ))
+        })?,
+    };
 
     let mut session_state = session_state.lock().await;
 
-    if let Some(game_session) = session_state.get_game_session() {
-        let game_state = game_session.game_state.clone();
-        return Ok(StartGameResponse::new(game_state));
-    }
+    // The same game may have been started again while this one was being set up
+    if let Some(session_handle) = session_state.get_session_handle(&session_id) {
+        let game_state = session_handle.game_state();
+        drop(session_state);
 
-    let game_state_update_tx = session_state.get_state_update_tx();
+        // A resumed session shares its narrator with the save, so only a new one is cleaned up
+        if !resuming {
+            delete_narrator(&openai_client, &game_session.game_state).await;
+        }
 
-    let game_session = GameSession::start_new(
-        request.game_id,
-        openai_client,
-        file_manager,
-        game_state_update_tx,
-    )
-    .await
-    .map_err(|e| {
-        error!("Unable to establish game session:\n{:?}", e);
-        GameSessionError::SetupFailure(format!("Error occurred while setting up game:\n{:?}", e))
-    })?;
+        return Ok(StartGameResponse::new(session_id, game_state, None));
+    }
 
     let game_state = game_session.game_state.clone();
-    session_state.set_game_session(game_session).await;
+    let mut session_handle = SessionHandle::spawn(
+        game_session,
+        cancellation,
+        game_state_update_tx,
+        request_update_tx,
+    );
+
+    // The narrator's introduction arrives as a state update once it's ready
+    let request_id = match resuming {
+        true => None,
+        false => Some(
+            session_handle
+                .send(SessionCommand::Begin)
+                .map_err(|e| GameSessionError::SetupFailure(format!("{:?}", e)))?,
+        ),
+    };
+
+    session_state.add_session_handle(session_id.clone(), session_handle);
 
-    Ok(StartGameResponse::new(game_state))
+    Ok(StartGameResponse::new(session_id, game_state, request_id))
+}
+
+/// Deletes the narrator assistant and thread of a new session that won't be used, so they aren't
+/// left behind. Failures are only logged, since the other session has already been returned.
+async fn delete_narrator(openai_client: &OpenAIClient, game_state: &GameState) {
+    if let Err(e) = openai_client
+        .delete_assistant(&game_state.assistant_id)
+        .await
+    {
+        error!(
+            "Failed to delete narrator assistant {}:\n{:?}",
+            &game_state.assistant_id, e
+        );
+    }
+
+    if let Err(e) = openai_client.delete_thread(&game_state.thread_id).await {
+        error!(
+            "Failed to delete narrator thread {}:\n{:?}",
+            &game_state.thread_id, e
+        );
+    }
 }
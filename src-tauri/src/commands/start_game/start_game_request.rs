@@ -3,4 +3,6 @@ use serde::{Serialize, Deserialize};
 #[derive(Serialize, Deserialize)]
 pub struct StartGameRequest {
   pub game_id: String,
+  /// Defaults to the game's default slot.
+  pub save_slot: Option<String>,
 }
\ No newline at end of file
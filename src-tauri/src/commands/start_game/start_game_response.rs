@@ -4,11 +4,18 @@ use crate::game_state::GameState;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StartGameResponse {
+    pub session_id: String,
     pub game_state: GameState,
+    /// The request running the narrator's introduction, if the game wasn't started before.
+    pub request_id: Option<u64>,
 }
 
 impl StartGameResponse {
-    pub fn new(game_state: GameState) -> Self {
-        StartGameResponse { game_state }
+    pub fn new(session_id: String, game_state: GameState, request_id: Option<u64>) -> Self {
+        StartGameResponse {
+            session_id,
+            game_state,
+            request_id,
+        }
     }
 }
@@ -0,0 +1,50 @@
+use crate::application_state::session_state::SessionState;
+
+use self::{suspend_game_error::SuspendGameError, suspend_game_request::SuspendGameRequest};
+
+use log::{error, info};
+use tauri::State;
+use tokio::sync::Mutex;
+
+mod suspend_game_error;
+mod suspend_game_request;
+
+/// Saves the game and stops its session, so another can be played. Anything the session was doing
+/// is cancelled first. The game is picked up again by starting it with the same save slot.
+#[tauri::command]
+pub async fn suspend_game(
+    request: SuspendGameRequest,
+    session_state: State<'_, Mutex<SessionState>>,
+) -> Result<(), SuspendGameError> {
+    info!(
+        "Received request to suspend game session {}",
+        &request.session_id
+    );
+
+    let suspended = {
+        let mut session_state = session_state.lock().await;
+
+        let session_handle = session_state.get_session_handle(&request.session_id);
+        let session_handle = session_handle.ok_or(SuspendGameError::new(
+            "Unable to suspend: No active game session.",
+        ))?;
+
+        session_handle.suspend().map_err(|e| {
+            error!("Unable to queue suspend:\n{:?}", e);
+            SuspendGameError::new("Unable to suspend the game.")
+        })?
+    };
+
+    // The session state is unlocked while the session saves, so other sessions aren't held up
+    suspended
+        .await
+        .map_err(|_| SuspendGameError::new("The game session stopped unexpectedly."))?
+        .map_err(|e| SuspendGameError::new(&e))?;
+
+    session_state
+        .lock()
+        .await
+        .remove_session_handle(&request.session_id);
+
+    Ok(())
+}
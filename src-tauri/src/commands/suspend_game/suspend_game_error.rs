@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuspendGameError {
+    pub message: String,
+}
+
+impl SuspendGameError {
+    pub fn new(message: &str) -> Self {
+        SuspendGameError {
+            message: message.to_string(),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub mod content_setting;
+pub mod run_polling_config;
 
 use anyhow::anyhow;
 use log::{error, info};
@@ -6,15 +7,20 @@ use serde::{Deserialize, Serialize};
 
 use crate::file_manager::FileManager;
 
+use self::run_polling_config::RunPollingConfig;
+
 #[derive(Serialize, Deserialize)]
 pub struct Config {
     pub openai_api_key: Option<String>,
+    #[serde(default)]
+    pub run_polling: RunPollingConfig,
 }
 
 impl Config {
     fn default() -> Self {
         Config {
             openai_api_key: None,
+            run_polling: RunPollingConfig::default(),
         }
     }
 
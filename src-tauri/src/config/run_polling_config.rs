@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// How long to wait on an assistant's run, and how often to check on it. Polling starts quickly
+/// and backs off, since most runs finish within a second or two but some take much longer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct RunPollingConfig {
+    /// The longest a run can take before it is abandoned.
+    pub deadline_ms: u64,
+    pub initial_interval_ms: u64,
+    pub max_interval_ms: u64,
+    /// The number of failed status checks in a row before the run is abandoned.
+    pub max_consecutive_errors: u32,
+}
+
+impl Default for RunPollingConfig {
+    fn default() -> Self {
+        RunPollingConfig {
+            deadline_ms: 120_000,
+            initial_interval_ms: 250,
+            max_interval_ms: 4_000,
+            max_consecutive_errors: 5,
+        }
+    }
+}
+
+impl RunPollingConfig {
+    pub fn deadline(&self) -> Duration {
+        Duration::from_millis(self.deadline_ms)
+    }
+
+    /// The time to wait before the next status check, doubling with each attempt up to the
+    /// maximum interval. `jitter` scales the interval, so that checks on several runs spread out.
+    pub fn backoff(&self, attempt: u32, jitter: f64) -> Duration {
+        let interval = self
+            .initial_interval_ms
+            .saturating_mul(2u64.saturating_pow(attempt))
+            .min(self.max_interval_ms);
+
+        Duration::from_millis((interval as f64 * jitter) as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_maximum() {
+        let config = RunPollingConfig::default();
+
+        let intervals = (0..6)
+            .map(|attempt| config.backoff(attempt, 1.0).as_millis())
+            .collect::<Vec<_>>();
+
+        assert_eq!(intervals, vec![250, 500, 1000, 2000, 4000, 4000]);
+        assert_eq!(config.backoff(100, 0.5).as_millis(), 2000);
+    }
+}
@@ -210,6 +210,54 @@ impl FileManager {
             .context("File existence could not be verfied.")
     }
 
+    /// Moves a file within the data directory, replacing any file already at the destination.
+    pub fn rename_file(&self, from: &str, to: &str) -> Result<(), anyhow::Error> {
+        let from_path: PathBuf = self.data_dir.join(from);
+        let to_path: PathBuf = self.data_dir.join(to);
+
+        debug!("Renaming file {:?} to {:?}", from_path, to_path);
+
+        std::fs::rename(from_path, to_path)
+            .with_context(|| format!("Unable to rename file {} to {}.", from, to))
+    }
+
+    /// Deletes a file within the data directory. Files that don't exist are ignored.
+    pub fn remove_file(&self, file_name: &str) -> Result<(), anyhow::Error> {
+        let file_path: PathBuf = self.data_dir.join(file_name);
+
+        if !file_path.exists() {
+            return Ok(());
+        }
+
+        debug!("Removing file: {:?}", file_path);
+
+        std::fs::remove_file(file_path)
+            .with_context(|| format!("Unable to remove file {}.", file_name))
+    }
+
+    /// Returns the full path to a file or directory within the data directory.
+    pub fn resolve_path(&self, file_name: &str) -> PathBuf {
+        self.data_dir.join(file_name)
+    }
+
+    /// Recursively copies the contents of a directory anywhere on disk into a directory within
+    /// the data directory.
+    pub fn copy_directory(
+        &self,
+        source: impl AsRef<Path>,
+        destination: &str,
+    ) -> Result<(), anyhow::Error> {
+        let destination_path: PathBuf = self.data_dir.join(destination);
+
+        debug!(
+            "Copying directory {:?} to {:?}",
+            source.as_ref(),
+            &destination_path
+        );
+
+        copy_directory_recursive(source.as_ref(), &destination_path)
+    }
+
     pub fn read_from_file(&self, file_name: &str) -> Result<String, anyhow::Error> {
         let file_path: PathBuf = self.data_dir.join(file_name);
 
@@ -221,6 +269,28 @@ impl FileManager {
     }
 }
 
+fn copy_directory_recursive(source: &Path, destination: &Path) -> Result<(), anyhow::Error> {
+    std::fs::create_dir_all(destination)
+        .with_context(|| format!("Unable to create directory {}.", destination.display()))?;
+
+    for entry in std::fs::read_dir(source)
+        .with_context(|| format!("Unable to read directory {}.", source.display()))?
+    {
+        let entry = entry?;
+        let entry_destination = destination.join(entry.file_name());
+
+        if entry.file_type()?.is_dir() {
+            copy_directory_recursive(&entry.path(), &entry_destination)?;
+        } else {
+            std::fs::copy(entry.path(), &entry_destination).with_context(|| {
+                format!("Unable to copy file to {}.", entry_destination.display())
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
 fn modify_json_file<T, F>(file: &mut File, transaction: F) -> Result<(), anyhow::Error>
 where
     F: FnOnce(T) -> T,
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+/// A stat that the player has throughout the game, such as their health, or a skill used in
+/// skill checks.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PlayerAttribute {
+    pub name: String,
+    pub description: String,
+    pub starting_value: i32,
+    pub max_value: i32,
+    /// Whether the player is defeated when this attribute reaches zero.
+    #[serde(default)]
+    pub fatal_at_zero: bool,
+}
+
+impl PlayerAttribute {
+    pub fn health() -> Self {
+        Self {
+            name: String::from("Health"),
+            description: String::from(
+                "The player's physical wellbeing. The player is defeated if it reaches zero.",
+            ),
+            starting_value: 20,
+            max_value: 20,
+            fatal_at_zero: true,
+        }
+    }
+
+    pub fn stamina() -> Self {
+        Self {
+            name: String::from("Stamina"),
+            description: String::from(
+                "The player's energy for running, climbing, fighting and other strenuous actions.",
+            ),
+            starting_value: 10,
+            max_value: 10,
+            fatal_at_zero: false,
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        chat_completion_factory::{ChatCompletionFactory, ChatCompletionFactoryArgs},
+        summary::Summary,
+    },
+    prompt_builder::PromptInput,
+};
+
+use super::{attributes_input::AttributesInput, PlayerAttribute};
+
+/// The attributes the player has in a game. Games generated before attributes existed only have
+/// health and stamina.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PlayerAttributes {
+    pub attributes: Vec<PlayerAttribute>,
+}
+
+impl Default for PlayerAttributes {
+    fn default() -> Self {
+        Self {
+            attributes: vec![PlayerAttribute::health(), PlayerAttribute::stamina()],
+        }
+    }
+}
+
+impl PlayerAttributes {
+    pub async fn create(
+        summary: &Summary,
+        factory: &ChatCompletionFactory<'_>,
+    ) -> Result<Self, anyhow::Error> {
+        let input = AttributesInput::new(&summary.summary, &summary.win_condition);
+
+        let system_message = factory
+            .prompt_builder()
+            .add_prompt("attributes/main.txt")
+            .add_example_input("attributes/example1_input.json")
+            .add_example_output("attributes/example1_output.json")
+            .build()?;
+
+        let user_message = input.to_prompt()?;
+
+        info!("Prepared system and user message for player attributes.");
+
+        factory
+            .try_create(
+                ChatCompletionFactoryArgs::builder()
+                    .name("Player Attributes")
+                    .system_message(system_message)
+                    .user_message(user_message)
+                    .file_name("tmp/attributes.json")
+                    .template_guidelines()
+                    .before_save(Box::new(|mut attributes: PlayerAttributes| {
+                        attributes.normalize();
+                        attributes
+                    }))
+                    .build(),
+            )
+            .await
+    }
+
+    /// Lists the attributes in a form the narrator can refer to when changing or checking them.
+    pub fn to_narrator_list(&self) -> String {
+        self.attributes
+            .iter()
+            .map(|a| {
+                let mut line = format!("- {} (max {}): {}", a.name, a.max_value, a.description);
+                if a.fatal_at_zero {
+                    line += " The player is defeated if this reaches zero.";
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Fixes up anything in the generated attributes that the game can't use:
+    ///
+    /// - Attributes with duplicate names are removed.
+    /// - Max values are at least 1, and starting values are between 1 and the max value.
+    /// - Health and stamina are added if they are missing, and health is always fatal at zero.
+    fn normalize(&mut self) {
+        let mut seen_names: Vec<String> = Vec::new();
+
+        self.attributes.retain(|attribute| {
+            let name = attribute.name.to_lowercase();
+            if seen_names.contains(&name) {
+                warn!(
+                    "Removing attribute with duplicate name '{}'.",
+                    &attribute.name
+                );
+                return false;
+            }
+            seen_names.push(name);
+            true
+        });
+
+        for attribute in &mut self.attributes {
+            attribute.max_value = attribute.max_value.max(1);
+            attribute.starting_value = attribute.starting_value.clamp(1, attribute.max_value);
+        }
+
+        for (index, required) in [PlayerAttribute::health(), PlayerAttribute::stamina()]
+            .into_iter()
+            .enumerate()
+        {
+            if !self
+                .attributes
+                .iter()
+                .any(|a| a.name.eq_ignore_ascii_case(&required.name))
+            {
+                warn!("Adding missing attribute '{}'.", &required.name);
+                self.attributes.insert(index, required);
+            }
+        }
+
+        if let Some(health) = self
+            .attributes
+            .iter_mut()
+            .find(|a| a.name.eq_ignore_ascii_case("Health"))
+        {
+            health.fatal_at_zero = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::prompt_builder::PromptBuilder;
+
+    use super::*;
+
+    #[test]
+    fn attributes_example1_is_valid() {
+        let example1 = PromptBuilder::new()
+            .add_prompt("attributes/example1_output.json")
+            .build()
+            .unwrap();
+        let mut attributes = serde_json::from_str::<PlayerAttributes>(&example1).unwrap();
+        let before = attributes.clone();
+
+        attributes.normalize();
+
+        assert_eq!(attributes, before);
+    }
+
+    #[test]
+    fn normalize_adds_required_attributes() {
+        let mut attributes = serde_json::from_value::<PlayerAttributes>(json!({
+            "attributes": [
+                {
+                    "name": "Sanity",
+                    "description": "",
+                    "starting_value": 30,
+                    "max_value": 10,
+                    "fatal_at_zero": true
+                },
+                {
+                    "name": "sanity",
+                    "description": "",
+                    "starting_value": 5,
+                    "max_value": 5
+                },
+                {
+                    "name": "health",
+                    "description": "",
+                    "starting_value": 0,
+                    "max_value": 0
+                }
+            ]
+        }))
+        .unwrap();
+
+        attributes.normalize();
+
+        let names = attributes
+            .attributes
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, vec!["Sanity", "Stamina", "health"]);
+        assert_eq!(attributes.attributes[0].starting_value, 10);
+        assert_eq!(attributes.attributes[2].starting_value, 1);
+        assert!(attributes.attributes[2].fatal_at_zero);
+    }
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+use crate::prompt_builder::PromptInput;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AttributesInput {
+    game_summary: String,
+    win_condition: String,
+}
+
+impl AttributesInput {
+    pub fn new(game_summary: &str, win_condition: &str) -> Self {
+        Self {
+            game_summary: game_summary.to_string(),
+            win_condition: win_condition.to_string(),
+        }
+    }
+}
+
+impl PromptInput for AttributesInput {}
+
+#[cfg(test)]
+mod test {
+    use crate::prompt_builder::PromptBuilder;
+
+    use super::*;
+
+    #[test]
+    fn matches_example1_input() {
+        let example1 = PromptBuilder::new()
+            .add_prompt("attributes/example1_input.json")
+            .build()
+            .unwrap();
+        serde_json::from_str::<AttributesInput>(&example1).unwrap();
+    }
+}
@@ -0,0 +1,6 @@
+mod attribute;
+pub mod attributes;
+mod attributes_input;
+
+pub use attribute::PlayerAttribute;
+pub use attributes::PlayerAttributes;
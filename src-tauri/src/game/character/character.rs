@@ -7,9 +7,10 @@ use crate::{
     config::content_setting::ContentSetting,
     file_manager::FileManager,
     game::{
-        character::character_input::CharacterInput,
+        character::{character_input::CharacterInput, CharacterMovement},
         chat_completion_factory::{ChatCompletionFactory, ChatCompletionFactoryArgs},
         game_metadata::GameMetadata,
+        generation_record::GenerationRecord,
         image::{
             image_factory::{ImageFactory, ImageFactoryArgs},
             image_multiprocessor::ImageMultiprocessor,
@@ -18,7 +19,7 @@ use crate::{
         scene::Scene,
         summary::Summary,
     },
-    prompt_builder::PromptBuilder,
+    prompt_builder::PromptInput,
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -32,6 +33,8 @@ pub struct Character {
     pub thoughts: String,
     pub inventory: Vec<String>,
     pub image: Image,
+    #[serde(default)]
+    pub movement: CharacterMovement,
 }
 
 impl Character {
@@ -43,25 +46,18 @@ impl Character {
     ) -> Result<Self, anyhow::Error> {
         let character_input =
             CharacterInput::new(&summary.summary, &scene_description, &character_summary);
-        let system_prompt = PromptBuilder::new()
-            .add_prompt("./prompts/character_detail/main.txt")
-            .add_example_input("./prompts/character_detail/example1_input.json")
-            .add_example_output("./prompts/character_detail/example1_output.json")
-            .add_example_input("./prompts/character_detail/example2_input.json")
-            .add_example_output("./prompts/character_detail/example2_output.json")
-            .build();
+        let system_prompt = factory
+            .prompt_builder()
+            .add_prompt("character_detail/main.txt")
+            .add_example_input("character_detail/example1_input.json")
+            .add_example_output("character_detail/example1_output.json")
+            .add_example_input("character_detail/example2_input.json")
+            .add_example_output("character_detail/example2_output.json")
+            .build()?;
 
-        let user_prompt = character_input.to_string();
+        let user_prompt = character_input.to_prompt()?;
 
-        let character_name: String = character_summary
-            .split(":")
-            .next()
-            .unwrap_or(character_summary.as_str())
-            .split("(")
-            .next()
-            .unwrap_or(character_summary.as_str())
-            .trim()
-            .to_string();
+        let character_name = Character::name_from_summary(&character_summary);
 
         info!("Creating character detail for {}", &character_name);
 
@@ -88,6 +84,34 @@ impl Character {
         Ok(character)
     }
 
+    /// Removes the files a character was generated into, once they are no longer in the game.
+    pub fn remove(
+        character_name: &str,
+        game_metadata: &GameMetadata,
+        file_manager: &FileManager,
+    ) -> Result<(), anyhow::Error> {
+        let file_name = format!("tmp/characters/{}.json", character_name);
+
+        for file_name in [GenerationRecord::file_name(&file_name), file_name] {
+            file_manager.remove_file(&format!("{}/{}", game_metadata.game_id, file_name))?;
+        }
+
+        Ok(())
+    }
+
+    /// Extracts the character's name from a scene's character entry, e.g. "Arin: Keeper of Lore".
+    pub fn name_from_summary(character_summary: &str) -> String {
+        character_summary
+            .split(":")
+            .next()
+            .unwrap_or(character_summary)
+            .split("(")
+            .next()
+            .unwrap_or(character_summary)
+            .trim()
+            .to_string()
+    }
+
     pub async fn create_from_scenes(
         summary: &Summary,
         scenes: &Vec<Scene>,
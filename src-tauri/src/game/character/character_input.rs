@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::prompt_builder::PromptInput;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CharacterInput {
     pub game_summary: String,
@@ -15,12 +17,10 @@ impl CharacterInput {
             character_description: character_description.to_string(),
         }
     }
-
-    pub fn to_string(&self) -> String {
-        serde_json::to_string(&self).unwrap()
-    }
 }
 
+impl PromptInput for CharacterInput {}
+
 #[cfg(test)]
 mod test {
     use crate::prompt_builder::PromptBuilder;
@@ -30,8 +30,9 @@ mod test {
     #[test]
     fn character_input_example1_matches() {
         let example1 = PromptBuilder::new()
-            .add_prompt("./prompts/character_detail/example1_input.json")
-            .build();
+            .add_prompt("character_detail/example1_input.json")
+            .build()
+            .unwrap();
 
         serde_json::from_str::<CharacterInput>(&example1).unwrap();
     }
@@ -39,8 +40,9 @@ mod test {
     #[test]
     fn character_input_example2_matches() {
         let example2 = PromptBuilder::new()
-            .add_prompt("./prompts/character_detail/example2_input.json")
-            .build();
+            .add_prompt("character_detail/example2_input.json")
+            .build()
+            .unwrap();
 
         serde_json::from_str::<CharacterInput>(&example2).unwrap();
     }
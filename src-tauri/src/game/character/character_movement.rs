@@ -0,0 +1,57 @@
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::fuzzy_match::FuzzyMatch;
+
+/// How a character moves between scenes during the game. Characters generated before movement
+/// existed stay in the scene that introduced them.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CharacterMovement {
+    #[default]
+    Stationary,
+    /// The character travels with the player once they are in the same scene.
+    FollowsPlayer,
+    /// The character moves to each stop's scene once its objective has been completed.
+    Schedule { stops: Vec<ScheduleStop> },
+}
+
+impl CharacterMovement {
+    /// Removes schedule stops that refer to unknown scenes or objectives. A character left without
+    /// any stops stays where they are.
+    pub fn remove_invalid_stops(
+        &mut self,
+        character: &str,
+        scenes: &[String],
+        objective_ids: &[String],
+    ) {
+        if let CharacterMovement::Schedule { stops } = self {
+            stops.retain_mut(|stop| {
+                let scene = FuzzyMatch::find(&stop.scene, scenes);
+                match (scene, objective_ids.contains(&stop.after_objective)) {
+                    (Some(scene), true) => {
+                        stop.scene = scene.clone();
+                        true
+                    }
+                    _ => {
+                        warn!(
+                            "Removing invalid schedule stop for '{}': {} after {}.",
+                            character, &stop.scene, &stop.after_objective
+                        );
+                        false
+                    }
+                }
+            });
+
+            if stops.is_empty() {
+                *self = CharacterMovement::Stationary;
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ScheduleStop {
+    pub after_objective: String,
+    pub scene: String,
+}
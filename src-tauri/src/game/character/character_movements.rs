@@ -0,0 +1,221 @@
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        chat_completion_factory::{ChatCompletionFactory, ChatCompletionFactoryArgs},
+        summary::Summary,
+        Quest, Scene,
+    },
+    prompt_builder::PromptInput,
+    utils::fuzzy_match::FuzzyMatch,
+};
+
+use super::{movements_input::MovementsInput, Character, CharacterMovement};
+
+/// How each character moves between scenes, generated once the quest is known so that schedules
+/// can follow its objectives. Characters that aren't listed stay where they are.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CharacterMovements {
+    pub movements: Vec<CharacterMovementEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CharacterMovementEntry {
+    pub character: String,
+    #[serde(flatten)]
+    pub movement: CharacterMovement,
+}
+
+impl CharacterMovements {
+    pub async fn create(
+        summary: &Summary,
+        scenes: &[Scene],
+        characters: &[Character],
+        quest: &Quest,
+        factory: &ChatCompletionFactory<'_>,
+    ) -> Result<Self, anyhow::Error> {
+        let scene_names = scenes.iter().map(|s| s.name.clone()).collect::<Vec<_>>();
+        let character_names = characters
+            .iter()
+            .map(|c| c.name.clone())
+            .collect::<Vec<_>>();
+        let objective_ids = quest
+            .objectives
+            .iter()
+            .map(|o| o.id.clone())
+            .collect::<Vec<_>>();
+
+        let character_list = characters
+            .iter()
+            .map(|character| {
+                let starting_scene = scenes
+                    .iter()
+                    .find(|s| {
+                        s.characters
+                            .iter()
+                            .any(|c| Character::name_from_summary(c) == character.name)
+                    })
+                    .map(|s| s.name.as_str())
+                    .unwrap_or("unknown");
+
+                format!(
+                    "{} (starts in {}): {}",
+                    character.name, starting_scene, character.short_description
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let objective_list = quest
+            .objectives
+            .iter()
+            .map(|o| format!("{}: {}", o.id, o.description))
+            .collect::<Vec<_>>();
+
+        let input = MovementsInput::new(
+            &summary.summary,
+            scene_names.clone(),
+            character_list,
+            objective_list,
+        );
+
+        let system_message = factory
+            .prompt_builder()
+            .add_prompt("character_movement/main.txt")
+            .add_example_input("character_movement/example1_input.json")
+            .add_example_output("character_movement/example1_output.json")
+            .build()?;
+
+        let user_message = input.to_prompt()?;
+
+        info!("Prepared system and user message for character movements.");
+
+        factory
+            .try_create(
+                ChatCompletionFactoryArgs::builder()
+                    .name("Character Movements")
+                    .system_message(system_message)
+                    .user_message(user_message)
+                    .file_name("tmp/movements.json")
+                    .before_save(Box::new(move |mut movements: CharacterMovements| {
+                        movements.remove_invalid_references(
+                            &character_names,
+                            &scene_names,
+                            &objective_ids,
+                        );
+                        movements
+                    }))
+                    .build(),
+            )
+            .await
+    }
+
+    /// Sets the movement of each listed character.
+    pub fn apply(&self, characters: &mut [Character]) {
+        for entry in &self.movements {
+            if let Some(character) = characters.iter_mut().find(|c| c.name == entry.character) {
+                character.movement = entry.movement.clone();
+            }
+        }
+    }
+
+    /// Fixes up anything in the generated movements that doesn't match the rest of the game:
+    ///
+    /// - Entries for unknown characters, or characters that are already listed, are removed.
+    /// - Schedule stops that refer to unknown scenes or objectives are removed, and characters
+    ///   left without any stops stay where they are.
+    fn remove_invalid_references(
+        &mut self,
+        characters: &[String],
+        scenes: &[String],
+        objective_ids: &[String],
+    ) {
+        let mut seen_characters: Vec<String> = Vec::new();
+
+        self.movements.retain_mut(|entry| {
+            let character = match FuzzyMatch::find(&entry.character, characters) {
+                Some(character) if !seen_characters.contains(character) => character,
+                _ => {
+                    warn!(
+                        "Removing movement for unknown or duplicate character '{}'.",
+                        &entry.character
+                    );
+                    return false;
+                }
+            };
+            entry.character = character.clone();
+            seen_characters.push(character.clone());
+            true
+        });
+
+        for entry in &mut self.movements {
+            entry
+                .movement
+                .remove_invalid_stops(&entry.character, scenes, objective_ids);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::prompt_builder::PromptBuilder;
+
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn movements_example1_is_valid() {
+        let example1 = PromptBuilder::new()
+            .add_prompt("character_movement/example1_output.json")
+            .build()
+            .unwrap();
+        let mut movements = serde_json::from_str::<CharacterMovements>(&example1).unwrap();
+        let before = movements.clone();
+
+        movements.remove_invalid_references(
+            &names(&["Arin", "Elder Maela", "Pip"]),
+            &names(&["Shadow Valley", "Hall of Echoes", "Altar of Ancients"]),
+            &names(&["earn_the_map", "find_the_crystal"]),
+        );
+
+        assert_eq!(movements, before);
+    }
+
+    #[test]
+    fn removes_invalid_references() {
+        let mut movements = serde_json::from_value::<CharacterMovements>(json!({
+            "movements": [
+                { "character": "pip", "type": "follows_player" },
+                { "character": "Pip", "type": "stationary" },
+                { "character": "Stranger", "type": "follows_player" },
+                {
+                    "character": "Arin",
+                    "type": "schedule",
+                    "stops": [
+                        { "after_objective": "earn_the_map", "scene": "the temple" },
+                        { "after_objective": "missing", "scene": "Temple" }
+                    ]
+                }
+            ]
+        }))
+        .unwrap();
+
+        movements.remove_invalid_references(
+            &names(&["Arin", "Pip"]),
+            &names(&["Village"]),
+            &names(&["earn_the_map"]),
+        );
+
+        assert_eq!(movements.movements.len(), 2);
+        assert_eq!(movements.movements[0].character, "Pip");
+        assert_eq!(
+            movements.movements[1].movement,
+            CharacterMovement::Stationary
+        );
+    }
+}
@@ -1,4 +1,9 @@
 pub mod character;
 mod character_input;
+mod character_movement;
+pub mod character_movements;
+mod movements_input;
 
 pub use character::Character;
+pub use character_movement::CharacterMovement;
+pub use character_movements::CharacterMovements;
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+use crate::prompt_builder::PromptInput;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MovementsInput {
+    game_summary: String,
+    scenes: Vec<String>,
+    characters: Vec<String>,
+    objectives: Vec<String>,
+}
+
+impl MovementsInput {
+    pub fn new(
+        game_summary: &str,
+        scenes: Vec<String>,
+        characters: Vec<String>,
+        objectives: Vec<String>,
+    ) -> Self {
+        Self {
+            game_summary: game_summary.to_string(),
+            scenes,
+            characters,
+            objectives,
+        }
+    }
+}
+
+impl PromptInput for MovementsInput {}
+
+#[cfg(test)]
+mod test {
+    use crate::prompt_builder::PromptBuilder;
+
+    use super::*;
+
+    #[test]
+    fn matches_example1_input() {
+        let example1 = PromptBuilder::new()
+            .add_prompt("character_movement/example1_input.json")
+            .build()
+            .unwrap();
+        serde_json::from_str::<MovementsInput>(&example1).unwrap();
+    }
+}
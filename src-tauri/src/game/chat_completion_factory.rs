@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use anyhow::{anyhow, Context};
 use log::{info, trace, warn};
 use openai_lib::{
@@ -7,9 +9,17 @@ use openai_lib::{
 };
 use serde::{de::DeserializeOwned, Serialize};
 
-use crate::{config::content_setting::ContentSetting, file_manager::FileManager};
+use crate::{
+    config::content_setting::ContentSetting,
+    file_manager::FileManager,
+    prompt_builder::PromptBuilder,
+    utils::{random::Random, stable_hash::StableHash},
+};
 
-use super::game_metadata::GameMetadata;
+use super::{
+    game_metadata::GameMetadata, generation_record::GenerationRecord,
+    generation_template::GenerationTemplate,
+};
 
 /// A factory that can produce Chat Completions based on the game settings and configuration.
 /// It can be used to pass any prompt to OpenAI and get an object of any shape back from the
@@ -18,6 +28,7 @@ pub struct ChatCompletionFactory<'a> {
     openai_client: &'a OpenAIClient,
     file_manager: &'a FileManager,
     game_metadata: &'a GameMetadata,
+    regenerations: HashMap<String, Option<String>>,
 }
 
 impl<'a> ChatCompletionFactory<'a> {
@@ -30,7 +41,27 @@ impl<'a> ChatCompletionFactory<'a> {
             openai_client,
             file_manager,
             game_metadata,
+            regenerations: HashMap::new(),
+        }
+    }
+
+    /// Marks a file as needing to be regenerated, so that any existing saved result is ignored
+    /// and replaced. An optional instruction can be provided to steer the new result.
+    pub fn regenerate(&mut self, file_name: impl Into<String>, instruction: Option<String>) {
+        self.regenerations.insert(file_name.into(), instruction);
+    }
+
+    /// Creates a PromptBuilder that loads any prompt overrides from the game's generation template.
+    pub fn prompt_builder(&self) -> PromptBuilder {
+        let mut prompt_builder = PromptBuilder::new();
+
+        if self.game_metadata.template.is_some() {
+            prompt_builder.overrides_dir(self.file_manager.resolve_path(
+                &GenerationTemplate::game_directory(&self.game_metadata.game_id),
+            ));
         }
+
+        prompt_builder
     }
 
     /// Process a chat completion request with the provided ChatCompletionFactoryArgs and attempts
@@ -48,8 +79,8 @@ impl<'a> ChatCompletionFactory<'a> {
 
         let mut errors = Vec::new();
 
-        for _ in 0..factory_args.max_attempts {
-            match self.create(&factory_args).await {
+        for attempt in 0..factory_args.max_attempts {
+            match self.create(&factory_args, attempt).await {
                 Ok(result) => return Ok(result),
                 Err(e) => {
                     warn!(
@@ -71,6 +102,7 @@ impl<'a> ChatCompletionFactory<'a> {
     async fn create<T>(
         &self,
         factory_args: &ChatCompletionFactoryArgs<T>,
+        attempt: u8,
     ) -> Result<T, anyhow::Error>
     where
         T: DeserializeOwned + Serialize,
@@ -82,8 +114,10 @@ impl<'a> ChatCompletionFactory<'a> {
             &factory_args.name, &file_path
         );
 
+        let regeneration = self.regenerations.get(&factory_args.file_name);
+
         match self.file_manager.file_exists(&file_path) {
-            Ok(true) => {
+            Ok(true) if regeneration.is_none() => {
                 info!(
                     "Found existing {} JSON file. Loading...",
                     &factory_args.name
@@ -101,7 +135,9 @@ impl<'a> ChatCompletionFactory<'a> {
             }
         }
 
-        let result = self.generate::<T>(factory_args).await?;
+        let seed = self.seed(factory_args, regeneration.is_some(), attempt);
+        let instruction = regeneration.cloned().flatten();
+        let (result, record) = self.generate::<T>(factory_args, seed, instruction).await?;
 
         let result = (factory_args.before_save)(result);
 
@@ -109,6 +145,17 @@ impl<'a> ChatCompletionFactory<'a> {
             .write_json::<T>(&file_path, &result)
             .context("Unable to write to JSON file.")?;
 
+        self.file_manager
+            .write_json(
+                format!(
+                    "{}/{}",
+                    self.game_metadata.game_id,
+                    GenerationRecord::file_name(&factory_args.file_name)
+                ),
+                &record,
+            )
+            .context("Unable to write generation record to file.")?;
+
         info!(
             "Generated {} and saved to '{}'.",
             &factory_args.name, &file_path
@@ -117,10 +164,46 @@ impl<'a> ChatCompletionFactory<'a> {
         Ok(result)
     }
 
+    /// Derives the seed for a request from the game seed, so that generating the same game again
+    /// sends the same seed for each artifact. Regenerating an artifact derives a new seed from the
+    /// one it was last generated with.
+    fn seed<T>(
+        &self,
+        factory_args: &ChatCompletionFactoryArgs<T>,
+        regenerate: bool,
+        attempt: u8,
+    ) -> u32 {
+        let mut seed = Random::derive_seed(self.game_metadata.seed, &factory_args.file_name);
+
+        if regenerate {
+            let record_path = format!(
+                "{}/{}",
+                self.game_metadata.game_id,
+                GenerationRecord::file_name(&factory_args.file_name)
+            );
+
+            if let Ok(record) = self
+                .file_manager
+                .read_json::<GenerationRecord>(&record_path)
+            {
+                seed = record.seed;
+            }
+
+            seed = Random::derive_seed(seed, "regenerate");
+        }
+
+        match attempt {
+            0 => seed,
+            _ => Random::derive_seed(seed, &format!("attempt {}", attempt)),
+        }
+    }
+
     async fn generate<T>(
         &self,
         factory_args: &ChatCompletionFactoryArgs<T>,
-    ) -> Result<T, anyhow::Error>
+        seed: u32,
+        instruction: Option<String>,
+    ) -> Result<(T, GenerationRecord), anyhow::Error>
     where
         T: DeserializeOwned,
     {
@@ -129,20 +212,55 @@ impl<'a> ChatCompletionFactory<'a> {
             _ => ChatModel::Gpt_4_1106_Preview,
         };
 
-        let response_text = self
+        let mut system_message = match &self.game_metadata.template {
+            Some(template) if factory_args.template_guidelines => format!(
+                "{}\n{}",
+                &factory_args.system_message,
+                template.guidelines()
+            ),
+            _ => factory_args.system_message.clone(),
+        };
+
+        if let Some(instruction) = &instruction {
+            info!(
+                "Steering {} with instruction: {}",
+                factory_args.name, &instruction
+            );
+            system_message += &format!(
+                "\nThe user has asked for this to be regenerated with the following instruction: {}",
+                instruction
+            );
+        }
+
+        let prompt_hash = StableHash::hex(&format!(
+            "{}\n{}",
+            &system_message, &factory_args.user_message
+        ));
+
+        let response = self
             .openai_client
             .create_chat_completion(
                 ChatCompletionRequest::builder()
-                    .add_system_message(&factory_args.system_message)
+                    .add_system_message(&system_message)
                     .add_user_message(&factory_args.user_message)
                     .model(model)
                     .json()
                     .temperature(self.game_metadata.temperature_setting)
+                    .seed(seed)
                     .build(),
             )
             .await
-            .map_err(|e| anyhow!("Failed to create chat completion request: {}", e))?
-            .get_content();
+            .map_err(|e| anyhow!("Failed to create chat completion request: {}", e))?;
+
+        let record = GenerationRecord {
+            seed,
+            model: response.model.to_string(),
+            system_fingerprint: response.system_fingerprint.clone(),
+            prompt_hash,
+            instruction,
+        };
+
+        let response_text = response.get_content();
 
         trace!("{} response text: {}", factory_args.name, &response_text);
 
@@ -151,7 +269,7 @@ impl<'a> ChatCompletionFactory<'a> {
 
         info!("Generated and parsed new {}", factory_args.name);
 
-        Ok(result)
+        Ok((result, record))
     }
 }
 
@@ -172,6 +290,7 @@ pub struct ChatCompletionFactoryArgs<T> {
     user_message: String,
     max_attempts: u8,
     file_name: String,
+    template_guidelines: bool,
     before_save: Box<dyn Fn(T) -> T + Send + Sync + 'static>,
 }
 
@@ -187,6 +306,7 @@ pub struct ChatCompletionFactoryArgsBuilder<T> {
     user_message: Option<String>,
     max_attempts: u8,
     file_name: Option<String>,
+    template_guidelines: bool,
     before_save: Option<Box<dyn Fn(T) -> T + Send + Sync + 'static>>,
 }
 
@@ -198,6 +318,7 @@ impl<T> ChatCompletionFactoryArgsBuilder<T> {
             user_message: None,
             max_attempts: 3,
             file_name: None,
+            template_guidelines: false,
             before_save: None,
         }
     }
@@ -237,6 +358,14 @@ impl<T> ChatCompletionFactoryArgsBuilder<T> {
         self
     }
 
+    /// Appends the guidelines from the game's generation template, if it was created with one, to
+    /// the system message. Only requests that decide what the template covers, like the genre or
+    /// the number of scenes, need them.
+    pub fn template_guidelines(mut self) -> Self {
+        self.template_guidelines = true;
+        self
+    }
+
     /// A function that will be called before the result is saved to file. This can be used to
     /// modify the JSON data before it is saved.
     pub fn before_save(mut self, before_save: Box<dyn Fn(T) -> T + Send + Sync + 'static>) -> Self {
@@ -257,6 +386,7 @@ impl<T> ChatCompletionFactoryArgsBuilder<T> {
             user_message: self.user_message.unwrap(),
             max_attempts: self.max_attempts,
             file_name: self.file_name.unwrap(),
+            template_guidelines: self.template_guidelines,
             before_save: self.before_save.unwrap(),
         }
     }
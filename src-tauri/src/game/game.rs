@@ -1,8 +1,12 @@
+use anyhow::bail;
 use serde::{Deserialize, Serialize};
 
 use crate::file_manager::FileManager;
 
-use super::{summary::Summary, Character, Image, Item, Narrative, Scene};
+use super::{
+    quest::ObjectiveTrigger, summary::Summary, Character, Image, Item, Narrative, PlayerAttributes,
+    Quest, Scene,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Game {
@@ -14,6 +18,12 @@ pub struct Game {
     pub scenes: Vec<Scene>,
     pub characters: Vec<Character>,
     pub items: Vec<Item>,
+    #[serde(default)]
+    pub quest: Option<Quest>,
+    #[serde(default)]
+    pub attributes: PlayerAttributes,
+    #[serde(default)]
+    pub seed: u32,
 }
 
 impl Game {
@@ -23,4 +33,285 @@ impl Game {
     ) -> Result<Self, anyhow::Error> {
         file_manager.read_json::<Game>(format!("{}/game.json", game_id.into()))
     }
+
+    /// Renames an item everywhere it appears in scenes, character inventories and quest
+    /// objectives, returning the names of the scenes and characters that were changed. Renaming an
+    /// item to the name of another item is an error, since their details would collide.
+    pub fn rename_item(
+        &mut self,
+        old_name: &str,
+        new_name: &str,
+    ) -> Result<(Vec<String>, Vec<String>), anyhow::Error> {
+        if self.items.iter().any(|i| i.name == new_name) {
+            bail!(
+                "Unable to rename item {} to {}, which is already an item in the game.",
+                old_name,
+                new_name
+            );
+        }
+
+        let mut changed_scenes = Vec::new();
+        let mut changed_characters = Vec::new();
+
+        for scene in &mut self.scenes {
+            for item in scene.items.iter_mut().filter(|i| *i == old_name) {
+                *item = new_name.to_string();
+                changed_scenes.push(scene.name.clone());
+            }
+        }
+
+        for character in &mut self.characters {
+            for item in character.inventory.iter_mut().filter(|i| *i == old_name) {
+                *item = new_name.to_string();
+                changed_characters.push(character.name.clone());
+            }
+        }
+
+        for item in self.items.iter_mut().filter(|i| i.name == old_name) {
+            item.name = new_name.to_string();
+        }
+
+        for objective in self.quest.iter_mut().flat_map(|q| q.objectives.iter_mut()) {
+            if let ObjectiveTrigger::ObtainItem { item } = &mut objective.trigger {
+                if item == old_name {
+                    *item = new_name.to_string();
+                }
+            }
+        }
+
+        changed_scenes.dedup();
+        changed_characters.dedup();
+
+        Ok((changed_scenes, changed_characters))
+    }
+
+    /// Fixes up the quest and character movement after scenes or characters have been replaced,
+    /// so they don't refer to anything that is no longer in the game.
+    pub fn remove_invalid_references(&mut self) {
+        let scene_names = self
+            .scenes
+            .iter()
+            .map(|s| s.name.clone())
+            .collect::<Vec<String>>();
+        let character_names = self
+            .characters
+            .iter()
+            .map(|c| c.name.clone())
+            .collect::<Vec<String>>();
+        let item_names = self
+            .held_items()
+            .into_iter()
+            .chain(self.items.iter().map(|i| i.name.clone()))
+            .collect::<Vec<String>>();
+
+        if let Some(quest) = &mut self.quest {
+            quest.remove_invalid_references(&scene_names, &character_names, &item_names);
+        }
+
+        let objective_ids = self
+            .quest
+            .iter()
+            .flat_map(|q| q.objectives.iter())
+            .map(|o| o.id.clone())
+            .collect::<Vec<String>>();
+
+        for character in &mut self.characters {
+            character
+                .movement
+                .remove_invalid_stops(&character.name, &scene_names, &objective_ids);
+        }
+    }
+
+    /// Lists the items held in scenes or by characters that don't have any details generated.
+    pub fn missing_items(&self) -> Vec<String> {
+        let mut missing_items = self
+            .held_items()
+            .into_iter()
+            .filter(|name| !self.items.iter().any(|i| &i.name == name))
+            .collect::<Vec<String>>();
+
+        missing_items.sort();
+        missing_items.dedup();
+
+        missing_items
+    }
+
+    /// Lists the items held in scenes or by characters.
+    pub fn held_items(&self) -> Vec<String> {
+        self.scenes
+            .iter()
+            .flat_map(|s| s.items.iter())
+            .chain(self.characters.iter().flat_map(|c| c.inventory.iter()))
+            .cloned()
+            .collect()
+    }
+
+    /// Removes the details of any of the given items that are no longer held in a scene or by a
+    /// character, and returns the names of the items that were removed.
+    pub fn remove_unheld_items(&mut self, item_names: &[String]) -> Vec<String> {
+        let held_items = self.held_items();
+
+        let mut removed = item_names
+            .iter()
+            .filter(|name| !held_items.contains(name))
+            .filter(|name| self.items.iter().any(|i| &i.name == *name))
+            .cloned()
+            .collect::<Vec<String>>();
+        removed.sort();
+        removed.dedup();
+
+        self.items.retain(|i| !removed.contains(&i.name));
+
+        removed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    fn test_game() -> Game {
+        serde_json::from_value(json!({
+            "id": "test",
+            "name": "Test",
+            "summary": {
+                "name": "Test",
+                "description": "",
+                "art_style": "",
+                "art_theme": "",
+                "cover_art": "",
+                "summary": "",
+                "win_condition": ""
+            },
+            "cover_art": "",
+            "narrative": { "pages": [] },
+            "scenes": [
+                {
+                    "name": "Village",
+                    "narrative": "",
+                    "metadata": "",
+                    "image": "",
+                    "characters": ["Arin: Keeper of Lore"],
+                    "items": ["Old Map", "Lantern"]
+                }
+            ],
+            "characters": [
+                {
+                    "name": "Arin",
+                    "short_description": "",
+                    "physical_description": "",
+                    "speech": "",
+                    "personality": "",
+                    "backstory": "",
+                    "thoughts": "",
+                    "inventory": ["Old Map", "Rune Stone"],
+                    "image": ""
+                }
+            ],
+            "items": [
+                { "name": "Old Map", "description": "", "image": "" },
+                { "name": "Lantern", "description": "", "image": "" }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn rename_item_updates_scenes_and_characters() {
+        let mut game = test_game();
+
+        let (scenes, characters) = game.rename_item("Old Map", "Temple Map").unwrap();
+
+        assert_eq!(scenes, vec![String::from("Village")]);
+        assert_eq!(characters, vec![String::from("Arin")]);
+        assert_eq!(game.scenes[0].items, vec!["Temple Map", "Lantern"]);
+        assert_eq!(
+            game.characters[0].inventory,
+            vec!["Temple Map", "Rune Stone"]
+        );
+        assert_eq!(game.items[0].name, "Temple Map");
+    }
+
+    #[test]
+    fn rename_item_rejects_existing_item() {
+        let mut game = test_game();
+
+        assert!(game.rename_item("Old Map", "Lantern").is_err());
+        assert_eq!(game.scenes[0].items, vec!["Old Map", "Lantern"]);
+    }
+
+    #[test]
+    fn remove_invalid_references_updates_quest_and_movement() {
+        let mut game = test_game();
+        game.quest = Some(
+            serde_json::from_value(json!({
+                "objectives": [
+                    {
+                        "id": "meet_arin",
+                        "title": "Meet Arin",
+                        "description": "",
+                        "trigger": { "type": "talk_to", "character": "Arin" }
+                    },
+                    {
+                        "id": "meet_maela",
+                        "title": "Meet Maela",
+                        "description": "",
+                        "trigger": { "type": "talk_to", "character": "Maela" }
+                    }
+                ]
+            }))
+            .unwrap(),
+        );
+        game.characters[0].movement = serde_json::from_value(json!({
+            "type": "schedule",
+            "stops": [
+                { "after_objective": "meet_arin", "scene": "Village" },
+                { "after_objective": "meet_arin", "scene": "Temple" }
+            ]
+        }))
+        .unwrap();
+
+        game.remove_invalid_references();
+
+        let objectives = &game.quest.as_ref().unwrap().objectives;
+        assert_eq!(
+            objectives[0].trigger,
+            ObjectiveTrigger::TalkTo {
+                character: String::from("Arin")
+            }
+        );
+        assert_eq!(objectives[1].trigger, ObjectiveTrigger::Narrator);
+        assert_eq!(
+            game.characters[0].movement,
+            serde_json::from_value(json!({
+                "type": "schedule",
+                "stops": [{ "after_objective": "meet_arin", "scene": "Village" }]
+            }))
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn missing_items_lists_items_without_details() {
+        let game = test_game();
+
+        assert_eq!(game.missing_items(), vec![String::from("Rune Stone")]);
+    }
+
+    #[test]
+    fn remove_unheld_items_keeps_items_still_held() {
+        let mut game = test_game();
+        let held_items = game.held_items();
+
+        game.scenes[0].items.clear();
+
+        assert_eq!(
+            game.remove_unheld_items(&held_items),
+            vec![String::from("Lantern")]
+        );
+        assert_eq!(game.items.len(), 1);
+        assert_eq!(game.items[0].name, "Old Map");
+    }
 }
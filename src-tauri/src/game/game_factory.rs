@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use anyhow::Context;
+use anyhow::{anyhow, bail, Context};
 use log::{error, info};
 use openai_lib::OpenAIClient;
 use tokio::sync::{mpsc::Sender, Mutex};
@@ -9,16 +9,24 @@ use crate::{
     commands::create_new_game::create_new_game_request::CreateNewGameRequest,
     file_manager::FileManager,
     game::{
-        character::Character, chat_completion_factory::ChatCompletionFactory,
-        image::image_factory::ImageFactory, item::Item, narrative::Narrative, scene::Scene,
-        scene_summary::SceneSummary, summary::Summary,
+        attributes::PlayerAttributes,
+        character::{Character, CharacterMovements},
+        chat_completion_factory::ChatCompletionFactory,
+        image::image_factory::ImageFactory,
+        item::Item,
+        narrative::Narrative,
+        quest::Quest,
+        scene::Scene,
+        scene_summary::SceneSummary,
+        summary::Summary,
     },
     utils::random::Random,
 };
 
 use super::{
     game_generation_update::GameGenerationUpdate, game_metadata::GameMetadata,
-    image::image_multiprocessor::ImageMultiprocessor, Game,
+    generation_record::GenerationRecord, generation_template::GenerationTemplate,
+    image::image_multiprocessor::ImageMultiprocessor, Game, RegenerationTarget,
 };
 
 pub struct GameFactory {
@@ -27,7 +35,7 @@ pub struct GameFactory {
     openai_client: OpenAIClient,
     file_manager: FileManager,
     start_time: std::time::Instant,
-    updates_tx: Arc<Mutex<Sender<GameGenerationUpdate>>>,
+    updates_tx: Option<Arc<Mutex<Sender<GameGenerationUpdate>>>>,
 }
 
 impl GameFactory {
@@ -40,7 +48,17 @@ impl GameFactory {
         let game_id = Random::generate_id();
         info!("Creating new game with id: {}", &game_id);
 
-        let game_metadata = GameMetadata::from_request(&game_id, request);
+        let template = match &request.template {
+            Some(template_id) => {
+                let (template, template_dir) = GenerationTemplate::find(template_id, file_manager)?;
+                GenerationTemplate::install(template_dir, &game_id, file_manager)?;
+                info!("Installed generation template '{}'.", &template.name);
+                Some(template)
+            }
+            None => None,
+        };
+
+        let game_metadata = GameMetadata::from_request(&game_id, request, template);
         info!("Extracted metadata from request: {:?}", &game_metadata);
 
         file_manager
@@ -54,7 +72,7 @@ impl GameFactory {
             openai_client: openai_client.clone(),
             file_manager: file_manager.clone(),
             start_time: std::time::Instant::now(),
-            updates_tx: updates_tx.clone(),
+            updates_tx: Some(updates_tx.clone()),
         })
     }
 
@@ -67,6 +85,21 @@ impl GameFactory {
         let game_id = game_id.into();
         info!("Resuming building game with id: {}", &game_id);
 
+        let mut game_factory = GameFactory::load(game_id, openai_client, file_manager)?;
+        game_factory.updates_tx = Some(updates_tx.clone());
+
+        Ok(game_factory)
+    }
+
+    /// Loads the factory for a game that has already been generated, without sending updates to
+    /// the UI. This is used to add to the game while it is being played.
+    pub fn load(
+        game_id: impl Into<String>,
+        openai_client: &OpenAIClient,
+        file_manager: &FileManager,
+    ) -> Result<Self, anyhow::Error> {
+        let game_id = game_id.into();
+
         let game_metadata = file_manager
             .read_json::<GameMetadata>(format!("{}/tmp/metadata.json", &game_id))
             .context("Error occurred attempting to read game metadata json file.")?;
@@ -78,13 +111,17 @@ impl GameFactory {
             openai_client: openai_client.clone(),
             file_manager: file_manager.clone(),
             start_time: std::time::Instant::now(),
-            updates_tx: updates_tx.clone(),
+            updates_tx: None,
         })
     }
 
     pub async fn create(&self) -> Result<Game, anyhow::Error> {
         info!("Starting game creation process for game {}.", &self.game_id);
-        self.send_update("Starting game creation process").await;
+        self.send_update(format!(
+            "Starting game creation process with seed {}.",
+            self.game_metadata.seed
+        ))
+        .await;
 
         let chat_completion_factory = ChatCompletionFactory::new(
             &self.openai_client,
@@ -98,10 +135,7 @@ impl GameFactory {
         self.send_update("Generated game name, style, and summary.")
             .await;
 
-        let image_style = format!(
-            "In the style of {}\nWith themes of {}",
-            &summary.art_style, &summary.art_theme
-        );
+        let image_style = self.image_style(&summary);
         info!(
             "Extracted image style phrasing from summary: '{}'",
             &image_style
@@ -180,6 +214,32 @@ impl GameFactory {
         info!("Finished generating images for each item.");
         self.send_update("Generated images for every item.").await;
 
+        let quest = Quest::create(
+            &summary,
+            &scenes,
+            &characters,
+            &items,
+            &chat_completion_factory,
+        )
+        .await?;
+        self.send_update("Generated objectives for the quest.")
+            .await;
+
+        CharacterMovements::create(
+            &summary,
+            &scenes,
+            &characters,
+            &quest,
+            &chat_completion_factory,
+        )
+        .await?
+        .apply(&mut characters);
+        self.send_update("Generated how characters move between scenes.")
+            .await;
+
+        let attributes = PlayerAttributes::create(&summary, &chat_completion_factory).await?;
+        self.send_update("Generated the player's attributes.").await;
+
         let id = self.game_metadata.game_id.clone();
         let name = summary.name.clone();
         let cover_art = summary.cover_art.clone();
@@ -193,6 +253,9 @@ impl GameFactory {
             scenes,
             characters,
             items,
+            quest: Some(quest),
+            attributes,
+            seed: self.game_metadata.seed,
         };
 
         info!("Game creation process complete.");
@@ -214,10 +277,343 @@ impl GameFactory {
         Ok(game)
     }
 
+    /// Regenerates a single artifact of a game that has already been generated, along with any
+    /// artifacts that depend on it, and rebuilds the game file.
+    pub async fn regenerate(
+        &self,
+        target: RegenerationTarget,
+        instruction: Option<String>,
+    ) -> Result<Game, anyhow::Error> {
+        self.send_update(format!("Regenerating {}.", &target)).await;
+
+        let mut game = Game::load(&self.game_id, &self.file_manager)
+            .context("Unable to load existing game for regeneration.")?;
+
+        let mut chat_completion_factory = ChatCompletionFactory::new(
+            &self.openai_client,
+            &self.file_manager,
+            &self.game_metadata,
+        );
+
+        let image_factory = ImageFactory::new(
+            &self.openai_client,
+            &self.file_manager,
+            &self.game_metadata,
+            self.image_style(&game.summary),
+        );
+
+        let held_items = game.held_items();
+
+        match &target {
+            RegenerationTarget::Cover => {
+                game.summary.cover_art = game.summary.cover_art.to_prompt(instruction.as_deref());
+                game.summary
+                    .generate_images(&image_factory, &self.game_metadata, &self.file_manager)
+                    .await?;
+                game.cover_art = game.summary.cover_art.clone();
+            }
+            RegenerationTarget::NarrativePage(index) => {
+                game.narrative
+                    .reset_page_image(*index, instruction.as_deref())?;
+                game.narrative
+                    .generate_images(&image_factory, &self.game_metadata, &self.file_manager)
+                    .await?;
+            }
+            RegenerationTarget::Scene(scene_name) => {
+                let scene_summary =
+                    SceneSummary::create(&game.summary, &chat_completion_factory).await?;
+                let summarized_scene = scene_summary
+                    .scenes
+                    .iter()
+                    .find(|s| &s.name == scene_name)
+                    .ok_or(anyhow!("Scene {} does not exist.", scene_name))?;
+
+                chat_completion_factory
+                    .regenerate(format!("tmp/scenes/{}.json", scene_name), instruction);
+
+                let mut scene =
+                    Scene::create(&game.summary, summarized_scene, &chat_completion_factory)
+                        .await?;
+                scene
+                    .generate_image(&image_factory, &self.game_metadata, &self.file_manager)
+                    .await?;
+                self.send_update(format!("Regenerated scene {}.", scene_name))
+                    .await;
+
+                // Characters are generated from the scene's narrative, so they're regenerated too
+                for character_summary in &scene.characters {
+                    chat_completion_factory.regenerate(
+                        format!(
+                            "tmp/characters/{}.json",
+                            Character::name_from_summary(character_summary)
+                        ),
+                        None,
+                    );
+                }
+
+                let mut characters = Character::create_from_scenes(
+                    &game.summary,
+                    &vec![scene.clone()],
+                    &chat_completion_factory,
+                )
+                .await?;
+                characters
+                    .generate_images(&image_factory, &self.game_metadata, &self.file_manager)
+                    .await?;
+                self.send_update(format!("Regenerated characters in scene {}.", scene_name))
+                    .await;
+
+                if let Some(existing) = game.scenes.iter_mut().find(|s| &s.name == scene_name) {
+                    *existing = scene;
+                }
+
+                for character in characters {
+                    game.characters.retain(|c| c.name != character.name);
+                    game.characters.push(character);
+                }
+
+                let character_names = game
+                    .scenes
+                    .iter()
+                    .flat_map(|s| s.characters.iter())
+                    .map(|c| Character::name_from_summary(c))
+                    .collect::<Vec<String>>();
+
+                for character in game
+                    .characters
+                    .iter()
+                    .filter(|c| !character_names.contains(&c.name))
+                {
+                    info!(
+                        "Removing character {}, who is no longer in any scene.",
+                        &character.name
+                    );
+                    Character::remove(&character.name, &self.game_metadata, &self.file_manager)?;
+                }
+
+                game.characters
+                    .retain(|c| character_names.contains(&c.name));
+            }
+            RegenerationTarget::Character(character_name) => {
+                let (scene_narrative, character_summary) = game
+                    .scenes
+                    .iter()
+                    .find_map(|s| {
+                        s.characters
+                            .iter()
+                            .find(|c| &Character::name_from_summary(c) == character_name)
+                            .map(|c| (s.narrative.clone(), c.clone()))
+                    })
+                    .ok_or(anyhow!("Character {} does not exist.", character_name))?;
+
+                chat_completion_factory.regenerate(
+                    format!("tmp/characters/{}.json", character_name),
+                    instruction,
+                );
+
+                let mut character = Character::create(
+                    &game.summary,
+                    scene_narrative,
+                    character_summary,
+                    &chat_completion_factory,
+                )
+                .await?;
+                character
+                    .generate_image(&image_factory, &self.game_metadata, &self.file_manager)
+                    .await?;
+
+                match game
+                    .characters
+                    .iter_mut()
+                    .find(|c| &c.name == character_name)
+                {
+                    Some(existing) => {
+                        // Movement is generated with the quest, so it's kept from the original
+                        character.movement = existing.movement.clone();
+                        *existing = character;
+                    }
+                    None => game.characters.push(character),
+                }
+            }
+            RegenerationTarget::Item(item_name) => {
+                if !game.items.iter().any(|i| &i.name == item_name) {
+                    bail!("Item {} does not exist.", item_name);
+                }
+
+                chat_completion_factory.regenerate(Item::file_name(item_name), instruction);
+
+                let mut item =
+                    Item::create_one(&game.summary, item_name, &chat_completion_factory).await?;
+
+                // Renamed before saving, so an item with the new name isn't replaced
+                if &item.name != item_name {
+                    info!("Item {} was renamed to {}.", item_name, &item.name);
+                    self.rename_item(&mut game, item_name, &item.name)?;
+                }
+
+                item.save(item_name, &self.game_metadata, &self.file_manager)
+                    .await?;
+                item.generate_image(&image_factory, &self.game_metadata, &self.file_manager)
+                    .await?;
+
+                if let Some(existing) = game.items.iter_mut().find(|i| i.name == item.name) {
+                    *existing = item;
+                }
+            }
+        }
+
+        game.remove_invalid_references();
+
+        // Only items that were in the game before are removed, so keepsakes created while playing
+        // are kept
+        for item_name in game.remove_unheld_items(&held_items) {
+            info!(
+                "Removing item {}, which is no longer in any scene.",
+                &item_name
+            );
+            Item::remove(&item_name, &self.game_metadata, &self.file_manager).await?;
+        }
+
+        for item_name in game.missing_items() {
+            info!("Creating details for new item {}.", &item_name);
+
+            let mut item =
+                Item::create_one(&game.summary, &item_name, &chat_completion_factory).await?;
+            item.name = item_name.clone();
+            item.save(&item_name, &self.game_metadata, &self.file_manager)
+                .await?;
+            item.generate_image(&image_factory, &self.game_metadata, &self.file_manager)
+                .await?;
+
+            game.items.push(item);
+        }
+
+        let file_path = format!("{}/game.json", &self.game_metadata.game_id);
+
+        self.file_manager
+            .write_json(&file_path, &game)
+            .context("Error occurred attempting to write game json file.")?;
+
+        self.send_update(format!("Finished regenerating {}.", &target))
+            .await;
+
+        Ok(game)
+    }
+
+    /// Creates the details and image for an item the player came across while playing, and adds it
+    /// to the saved game.
+    pub async fn create_item(
+        &self,
+        game: &mut Game,
+        item_name: &str,
+    ) -> Result<Item, anyhow::Error> {
+        info!("Creating details for new item {}.", item_name);
+
+        let chat_completion_factory = ChatCompletionFactory::new(
+            &self.openai_client,
+            &self.file_manager,
+            &self.game_metadata,
+        );
+
+        let image_factory = ImageFactory::new(
+            &self.openai_client,
+            &self.file_manager,
+            &self.game_metadata,
+            self.image_style(&game.summary),
+        );
+
+        let mut item = Item::create_one(&game.summary, item_name, &chat_completion_factory).await?;
+        item.name = item_name.to_string();
+        item.save(item_name, &self.game_metadata, &self.file_manager)
+            .await?;
+        item.generate_image(&image_factory, &self.game_metadata, &self.file_manager)
+            .await?;
+
+        game.items.push(item.clone());
+
+        self.file_manager
+            .write_json(format!("{}/game.json", &self.game_id), game)
+            .context("Error occurred attempting to write game json file.")?;
+
+        Ok(item)
+    }
+
+    /// Renames an item throughout the game, and saves the affected scene and character files so
+    /// that they stay consistent with the rebuilt game. The file the item was generated into is
+    /// moved to its new name, so it's found again if the item is regenerated.
+    fn rename_item(
+        &self,
+        game: &mut Game,
+        old_name: &str,
+        new_name: &str,
+    ) -> Result<(), anyhow::Error> {
+        let (scene_names, character_names) = game.rename_item(old_name, new_name)?;
+
+        for scene in game.scenes.iter().filter(|s| scene_names.contains(&s.name)) {
+            self.file_manager
+                .write_json(
+                    format!("{}/tmp/scenes/{}.json", &self.game_id, &scene.name),
+                    scene,
+                )
+                .context("Unable to save scene with renamed item.")?;
+        }
+
+        for character in game
+            .characters
+            .iter()
+            .filter(|c| character_names.contains(&c.name))
+        {
+            self.file_manager
+                .write_json(
+                    format!("{}/tmp/characters/{}.json", &self.game_id, &character.name),
+                    character,
+                )
+                .context("Unable to save character with renamed item.")?;
+        }
+
+        for (old_file, new_file) in [
+            (Item::file_name(old_name), Item::file_name(new_name)),
+            (
+                GenerationRecord::file_name(&Item::file_name(old_name)),
+                GenerationRecord::file_name(&Item::file_name(new_name)),
+            ),
+        ] {
+            let old_file = format!("{}/{}", &self.game_id, old_file);
+            if self.file_manager.file_exists(&old_file)? {
+                self.file_manager
+                    .rename_file(&old_file, &format!("{}/{}", &self.game_id, new_file))
+                    .context("Unable to move generated file for renamed item.")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn image_style(&self, summary: &Summary) -> String {
+        let mut image_style = format!(
+            "In the style of {}\nWith themes of {}",
+            &summary.art_style, &summary.art_theme
+        );
+
+        if let Some(art_direction) = self
+            .game_metadata
+            .template
+            .as_ref()
+            .and_then(|t| t.art_direction.as_ref())
+        {
+            image_style += &format!("\nWith an art direction of {}", art_direction);
+        }
+
+        image_style
+    }
+
     pub async fn send_update(&self, update: impl Into<String>) {
         let update = update.into();
         info!("{}", &update);
-        let updates_tx = self.updates_tx.lock().await;
+        let updates_tx = match &self.updates_tx {
+            Some(updates_tx) => updates_tx.lock().await,
+            None => return,
+        };
         if let Err(_) = updates_tx
             .send(GameGenerationUpdate::new(&self.game_id, update))
             .await
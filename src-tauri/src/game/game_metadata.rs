@@ -5,6 +5,8 @@ use crate::{
     config::content_setting::ContentSetting,
 };
 
+use super::generation_template::GenerationTemplate;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct GameMetadata {
     pub game_id: String,
@@ -12,10 +14,18 @@ pub struct GameMetadata {
     pub text_content_setting: ContentSetting,
     pub image_content_setting: ContentSetting,
     pub temperature_setting: f32,
+    #[serde(default)]
+    pub template: Option<GenerationTemplate>,
+    #[serde(default)]
+    pub seed: u32,
 }
 
 impl GameMetadata {
-    pub fn from_request(game_id: impl Into<String>, request: CreateNewGameRequest) -> Self {
+    pub fn from_request(
+        game_id: impl Into<String>,
+        request: CreateNewGameRequest,
+        template: Option<GenerationTemplate>,
+    ) -> Self {
         let prompt = request.prompt.clone();
 
         let text_content_setting = request
@@ -30,12 +40,16 @@ impl GameMetadata {
 
         let temperature_setting = request.get_temperature();
 
+        let seed = request.get_seed();
+
         GameMetadata {
             game_id: game_id.into(),
             prompt,
             text_content_setting,
             image_content_setting,
             temperature_setting,
+            template,
+            seed,
         }
     }
 }
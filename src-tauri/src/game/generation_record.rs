@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+/// Details about how a generated artifact was created. This is saved alongside the artifact, so
+/// that the same request can be replayed later.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GenerationRecord {
+    pub seed: u32,
+    pub model: String,
+    pub system_fingerprint: Option<String>,
+    pub prompt_hash: String,
+    pub instruction: Option<String>,
+}
+
+impl GenerationRecord {
+    /// The file that the record for an artifact is saved to, e.g. `tmp/summary.provenance.json`
+    /// for `tmp/summary.json`.
+    pub fn file_name(artifact_file_name: &str) -> String {
+        let stem = artifact_file_name
+            .strip_suffix(".json")
+            .unwrap_or(artifact_file_name);
+
+        format!("{}.provenance.json", stem)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn record_is_saved_next_to_artifact() {
+        assert_eq!(
+            GenerationRecord::file_name("tmp/scenes/Village.json"),
+            "tmp/scenes/Village.provenance.json"
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::{file_manager::FileManager, prompt_builder::PromptLibrary};
+
+const BUILT_IN_TEMPLATES_DIR: &str = "templates";
+const USER_TEMPLATES_DIR: &str = "templates";
+const TEMPLATE_FILE_NAME: &str = "template.json";
+
+/// A set of user-selectable options that steer game generation, such as the genre, tone, and how
+/// many scenes, characters and items the game should have.
+///
+/// Each template is a directory containing a `template.json` file. Built-in templates live in
+/// `prompts/templates/<id>/`, and users can add their own in `templates/<id>/` inside the app data
+/// directory. A template directory can also override any prompt or example file by mirroring its
+/// path within the `prompts` directory, e.g. `<id>/scene_summary/main.txt`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GenerationTemplate {
+    /// The name of the template's directory. This is set when the template is loaded.
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub genre: Option<String>,
+    #[serde(default)]
+    pub tone: Option<String>,
+    #[serde(default)]
+    pub target_play_length: Option<String>,
+    #[serde(default)]
+    pub age_rating: Option<String>,
+    #[serde(default)]
+    pub art_direction: Option<String>,
+    #[serde(default)]
+    pub scene_count: Option<CountRange>,
+    #[serde(default)]
+    pub character_count: Option<CountRange>,
+    #[serde(default)]
+    pub item_count: Option<CountRange>,
+    /// Names of genre-specific attributes the player should have, in addition to health and
+    /// stamina.
+    #[serde(default)]
+    pub attributes: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CountRange {
+    pub min: u32,
+    pub max: u32,
+}
+
+impl std::fmt::Display for CountRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.min == self.max {
+            true => write!(f, "{}", self.min),
+            false => write!(f, "{}-{}", self.min, self.max),
+        }
+    }
+}
+
+impl GenerationTemplate {
+    /// Lists all of the available templates. User templates replace built-in templates with the
+    /// same id.
+    pub fn list(file_manager: &FileManager) -> Vec<GenerationTemplate> {
+        let mut templates =
+            GenerationTemplate::read_all(&PromptLibrary::resolve(BUILT_IN_TEMPLATES_DIR));
+
+        for (template, dir) in
+            GenerationTemplate::read_all(&file_manager.resolve_path(USER_TEMPLATES_DIR))
+        {
+            templates.retain(|(t, _)| t.id != template.id);
+            templates.push((template, dir));
+        }
+
+        templates.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+        templates
+            .into_iter()
+            .map(|(template, _)| template)
+            .collect()
+    }
+
+    /// Finds the template with the provided id, returning it along with the directory it was
+    /// loaded from.
+    pub fn find(
+        id: &str,
+        file_manager: &FileManager,
+    ) -> Result<(GenerationTemplate, PathBuf), anyhow::Error> {
+        // The id is used as a directory name, so it mustn't be able to point anywhere else
+        if !GenerationTemplate::is_valid_id(id) {
+            return Err(anyhow!("Invalid generation template id '{}'.", id));
+        }
+
+        let user_dir = file_manager.resolve_path(USER_TEMPLATES_DIR).join(id);
+        let built_in_dir = PromptLibrary::resolve(BUILT_IN_TEMPLATES_DIR).join(id);
+
+        for dir in [user_dir, built_in_dir] {
+            if dir.join(TEMPLATE_FILE_NAME).exists() {
+                let template = GenerationTemplate::read(&dir)?;
+                return Ok((template, dir));
+            }
+        }
+
+        Err(anyhow!("No generation template found with id '{}'.", id))
+    }
+
+    /// Copies the template and any prompt overrides into the game's directory, so that resuming
+    /// generation later uses the same prompts even if the original template changes.
+    pub fn install(
+        template_dir: impl AsRef<Path>,
+        game_id: &str,
+        file_manager: &FileManager,
+    ) -> Result<(), anyhow::Error> {
+        file_manager
+            .copy_directory(template_dir, &GenerationTemplate::game_directory(game_id))
+            .context("Unable to copy generation template into game directory.")
+    }
+
+    /// The directory, relative to the data directory, that a game's template is installed to.
+    pub fn game_directory(game_id: &str) -> String {
+        format!("{}/tmp/template", game_id)
+    }
+
+    /// Formats the template options as guidelines that can be appended to generation prompts.
+    pub fn guidelines(&self) -> String {
+        let mut guidelines = vec![String::from(
+            "Follow these guidelines for the game being created. They take priority over any conflicting instructions above:",
+        )];
+
+        let options = [
+            ("Genre", self.genre.clone()),
+            ("Tone", self.tone.clone()),
+            ("Target play length", self.target_play_length.clone()),
+            ("Age rating", self.age_rating.clone()),
+            ("Art direction", self.art_direction.clone()),
+            (
+                "Number of scenes",
+                self.scene_count.as_ref().map(|c| c.to_string()),
+            ),
+            (
+                "Total number of characters",
+                self.character_count.as_ref().map(|c| c.to_string()),
+            ),
+            (
+                "Total number of key items",
+                self.item_count.as_ref().map(|c| c.to_string()),
+            ),
+            (
+                "Player attributes",
+                self.attributes.as_ref().map(|a| a.join(", ")),
+            ),
+        ];
+
+        for (label, value) in options {
+            if let Some(value) = value {
+                guidelines.push(format!("- {}: {}", label, value));
+            }
+        }
+
+        guidelines.join("\n")
+    }
+
+    fn is_valid_id(id: &str) -> bool {
+        !id.is_empty()
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+
+    fn read(dir: &Path) -> Result<GenerationTemplate, anyhow::Error> {
+        let contents = std::fs::read_to_string(dir.join(TEMPLATE_FILE_NAME))
+            .with_context(|| format!("Unable to read template file in {}.", dir.display()))?;
+
+        let mut template = serde_json::from_str::<GenerationTemplate>(&contents)
+            .with_context(|| format!("Unable to parse template file in {}.", dir.display()))?;
+
+        template.id = dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or(anyhow!("Invalid template directory name."))?
+            .to_string();
+
+        Ok(template)
+    }
+
+    fn read_all(templates_dir: &Path) -> Vec<(GenerationTemplate, PathBuf)> {
+        let entries = match std::fs::read_dir(templates_dir) {
+            Ok(entries) => entries,
+            Err(_) => {
+                info!("No templates found in {}.", templates_dir.display());
+                return vec![];
+            }
+        };
+
+        entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.join(TEMPLATE_FILE_NAME).exists())
+            .filter_map(|dir| match GenerationTemplate::read(&dir) {
+                Ok(template) => Some((template, dir)),
+                Err(e) => {
+                    warn!("Skipping invalid template: {:?}", e);
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn built_in_templates_are_valid() {
+        let templates =
+            GenerationTemplate::read_all(&PromptLibrary::resolve(BUILT_IN_TEMPLATES_DIR));
+
+        assert!(!templates.is_empty());
+
+        for (template, dir) in templates {
+            assert_eq!(
+                Some(template.id.as_str()),
+                dir.file_name().and_then(|n| n.to_str())
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_template_ids_outside_templates_directory() {
+        assert!(GenerationTemplate::is_valid_id("short_adventure"));
+        assert!(!GenerationTemplate::is_valid_id(""));
+        assert!(!GenerationTemplate::is_valid_id(".."));
+        assert!(!GenerationTemplate::is_valid_id("../short_adventure"));
+        assert!(!GenerationTemplate::is_valid_id("/tmp"));
+    }
+
+    #[test]
+    fn guidelines_include_set_options() {
+        let template = GenerationTemplate {
+            id: String::from("test"),
+            name: String::from("Test"),
+            description: String::new(),
+            genre: Some(String::from("Mystery")),
+            tone: None,
+            target_play_length: None,
+            age_rating: None,
+            art_direction: None,
+            scene_count: Some(CountRange { min: 4, max: 6 }),
+            character_count: Some(CountRange { min: 3, max: 3 }),
+            item_count: None,
+            attributes: Some(vec![String::from("Perception"), String::from("Charm")]),
+        };
+
+        let guidelines = template.guidelines();
+
+        assert!(guidelines.contains("- Genre: Mystery"));
+        assert!(guidelines.contains("- Number of scenes: 4-6"));
+        assert!(guidelines.contains("- Total number of characters: 3"));
+        assert!(guidelines.contains("- Player attributes: Perception, Charm"));
+        assert!(!guidelines.contains("Tone"));
+    }
+}
@@ -10,6 +10,23 @@ pub enum Image {
     Created { src: String, alt: String },
 }
 
+impl Image {
+    /// Converts this image back into a prompt so that it can be generated again. Created images
+    /// use their alt text, which holds the prompt revised by the image model. An optional
+    /// instruction is appended to steer the new image.
+    pub fn to_prompt(&self, instruction: Option<&str>) -> Image {
+        let prompt = match self {
+            Image::Prompt(prompt) => prompt.clone(),
+            Image::Created { alt, .. } => alt.clone(),
+        };
+
+        match instruction {
+            Some(instruction) => Image::Prompt(format!("{}\n{}", prompt, instruction)),
+            None => Image::Prompt(prompt),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use serde_json::json;
@@ -48,4 +65,17 @@ mod test {
             }
         );
     }
+
+    #[test]
+    fn created_image_converts_to_prompt() {
+        let image = Image::Created {
+            src: String::from("cover_art.png"),
+            alt: String::from("A misty forest"),
+        };
+
+        assert_eq!(
+            image.to_prompt(Some("At night")),
+            Image::Prompt(String::from("A misty forest\nAt night"))
+        );
+    }
 }
@@ -1,3 +1,4 @@
+use anyhow::anyhow;
 use futures::{StreamExt, TryStreamExt};
 use log::info;
 use openai_lib::{
@@ -14,6 +15,7 @@ use crate::{
         character::Character,
         chat_completion_factory::{ChatCompletionFactory, ChatCompletionFactoryArgs},
         game_metadata::GameMetadata,
+        generation_record::GenerationRecord,
         image::{
             image_factory::{ImageFactory, ImageFactoryArgs},
             image_multiprocessor::ImageMultiprocessor,
@@ -22,16 +24,18 @@ use crate::{
         scene::Scene,
         summary::Summary,
     },
-    prompt_builder::PromptBuilder,
+    prompt_builder::PromptInput,
 };
 
-use super::item_input::ItemInput;
+use super::{item_input::ItemInput, ItemProperties};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Item {
     pub name: String,
     pub description: String,
     pub image: Image,
+    #[serde(default)]
+    pub properties: ItemProperties,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -43,17 +47,19 @@ impl Item {
     pub async fn create(
         summary: &Summary,
         item_list: Vec<String>,
+        scene_list: Vec<String>,
         factory: &ChatCompletionFactory<'_>,
     ) -> Result<Vec<Item>, anyhow::Error> {
-        let items_input = ItemInput::new(&summary.summary, item_list);
-        let system_prompt = PromptBuilder::new()
-            .add_prompt("./prompts/item_detail/main.txt")
-            .add_example_input("./prompts/item_detail/example1_input.json")
-            .add_example_output("./prompts/item_detail/example1_output.json")
-            .add_example_input("./prompts/item_detail/example2_input.json")
-            .add_example_output("./prompts/item_detail/example2_output.json")
-            .build();
-        let user_prompt = items_input.to_string();
+        let items_input = ItemInput::new(&summary.summary, item_list.clone(), scene_list.clone());
+        let system_prompt = factory
+            .prompt_builder()
+            .add_prompt("item_detail/main.txt")
+            .add_example_input("item_detail/example1_input.json")
+            .add_example_output("item_detail/example1_output.json")
+            .add_example_input("item_detail/example2_input.json")
+            .add_example_output("item_detail/example2_output.json")
+            .build()?;
+        let user_prompt = items_input.to_prompt()?;
 
         info!("Prepared system and user messages for generating item details.");
 
@@ -64,6 +70,13 @@ impl Item {
                     .system_message(system_prompt)
                     .user_message(user_prompt)
                     .file_name("tmp/items.json")
+                    .before_save(Box::new(move |mut result: ItemsResult| {
+                        for item in &mut result.items {
+                            item.properties
+                                .remove_invalid_references(&item_list, &scene_list);
+                        }
+                        result
+                    }))
                     .build(),
             )
             .await?;
@@ -73,6 +86,104 @@ impl Item {
         Ok(items)
     }
 
+    /// Creates the details for a single item, saved separately from the items generated with the
+    /// rest of the game. The returned item may not have the requested name if the model changed it.
+    pub async fn create_one(
+        summary: &Summary,
+        item_name: &str,
+        factory: &ChatCompletionFactory<'_>,
+    ) -> Result<Item, anyhow::Error> {
+        let items_input = ItemInput::new(&summary.summary, vec![item_name.to_string()], vec![]);
+        let system_prompt = factory
+            .prompt_builder()
+            .add_prompt("item_detail/main.txt")
+            .add_example_input("item_detail/example1_input.json")
+            .add_example_output("item_detail/example1_output.json")
+            .add_example_input("item_detail/example2_input.json")
+            .add_example_output("item_detail/example2_output.json")
+            .build()?;
+        let user_prompt = items_input.to_prompt()?;
+
+        info!(
+            "Prepared system and user messages for generating item {}.",
+            item_name
+        );
+
+        let result = factory
+            .try_create::<ItemsResult>(
+                ChatCompletionFactoryArgs::builder()
+                    .name(item_name)
+                    .system_message(system_prompt)
+                    .user_message(user_prompt)
+                    .file_name(Item::file_name(item_name))
+                    .build(),
+            )
+            .await?;
+
+        result
+            .items
+            .into_iter()
+            .next()
+            .ok_or(anyhow!("No item details were generated for {}.", item_name))
+    }
+
+    /// The file that a single item created with `create_one` is saved to.
+    pub fn file_name(item_name: &str) -> String {
+        format!("tmp/items/{}.json", item_name)
+    }
+
+    /// Adds or replaces an item in the saved items file, removing any item with the name in
+    /// `replaces`.
+    pub async fn save(
+        &self,
+        replaces: &str,
+        game_metadata: &GameMetadata,
+        file_manager: &FileManager,
+    ) -> Result<(), anyhow::Error> {
+        let item = self.clone();
+        let replaces = replaces.to_string();
+
+        file_manager
+            .json_transaction::<ItemsResult, _>(
+                format!("{}/tmp/items.json", game_metadata.game_id),
+                move |mut items_result| {
+                    items_result
+                        .items
+                        .retain(|i| i.name != replaces && i.name != item.name);
+                    items_result.items.push(item);
+                    items_result
+                },
+            )
+            .await
+    }
+
+    /// Removes an item from the saved items file, along with the files it was generated into.
+    pub async fn remove(
+        item_name: &str,
+        game_metadata: &GameMetadata,
+        file_manager: &FileManager,
+    ) -> Result<(), anyhow::Error> {
+        let removed = item_name.to_string();
+
+        file_manager
+            .json_transaction::<ItemsResult, _>(
+                format!("{}/tmp/items.json", game_metadata.game_id),
+                move |mut items_result| {
+                    items_result.items.retain(|i| i.name != removed);
+                    items_result
+                },
+            )
+            .await?;
+
+        let file_name = Item::file_name(item_name);
+
+        for file_name in [GenerationRecord::file_name(&file_name), file_name] {
+            file_manager.remove_file(&format!("{}/{}", game_metadata.game_id, file_name))?;
+        }
+
+        Ok(())
+    }
+
     pub async fn create_from_scenes_and_chars(
         summary: &Summary,
         scenes: &Vec<Scene>,
@@ -102,7 +213,9 @@ impl Item {
 
         info!("Removed duplicates and sorted list. Generating details...");
 
-        let items = Item::create(summary, item_list, factory).await?;
+        let scene_list = scenes.iter().map(|scene| scene.name.clone()).collect();
+
+        let items = Item::create(summary, item_list, scene_list, factory).await?;
 
         Ok(items)
     }
@@ -1,24 +1,27 @@
 use serde::{Deserialize, Serialize};
 
+use crate::prompt_builder::PromptInput;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ItemInput {
     game_summary: String,
     items: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    scenes: Vec<String>,
 }
 
 impl ItemInput {
-    pub fn new(game_summary: &str, items: Vec<String>) -> Self {
+    pub fn new(game_summary: &str, items: Vec<String>, scenes: Vec<String>) -> Self {
         ItemInput {
             game_summary: game_summary.to_string(),
             items: items.clone(),
+            scenes,
         }
     }
-
-    pub fn to_string(self) -> String {
-        serde_json::to_string(&self).unwrap()
-    }
 }
 
+impl PromptInput for ItemInput {}
+
 #[cfg(test)]
 mod test {
     use crate::prompt_builder::PromptBuilder;
@@ -28,8 +31,9 @@ mod test {
     #[test]
     fn item_input_example1_matches() {
         let example1 = PromptBuilder::new()
-            .add_prompt("./prompts/item_detail/example1_input.json")
-            .build();
+            .add_prompt("item_detail/example1_input.json")
+            .build()
+            .unwrap();
 
         serde_json::from_str::<ItemInput>(&example1).unwrap();
     }
@@ -37,8 +41,9 @@ mod test {
     #[test]
     fn item_input_example2_matches() {
         let example2 = PromptBuilder::new()
-            .add_prompt("./prompts/item_detail/example2_input.json")
-            .build();
+            .add_prompt("item_detail/example2_input.json")
+            .build()
+            .unwrap();
 
         serde_json::from_str::<ItemInput>(&example2).unwrap();
     }
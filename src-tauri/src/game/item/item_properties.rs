@@ -0,0 +1,143 @@
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::fuzzy_match::FuzzyMatch;
+
+/// What the player can do with an item. Items generated before properties existed can be used,
+/// but aren't consumed and can't be combined.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct ItemProperties {
+    pub usable: bool,
+    /// What happens when the item is used, for the narrator to describe.
+    pub effect: String,
+    /// Whether the item is used up when it is used.
+    pub consumable: bool,
+    pub combinations: Vec<ItemCombination>,
+    /// The scenes whose locked exits this item opens.
+    pub unlocks: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ItemCombination {
+    pub with: String,
+    pub result: String,
+}
+
+impl Default for ItemProperties {
+    fn default() -> Self {
+        Self {
+            usable: true,
+            effect: String::new(),
+            consumable: false,
+            combinations: Vec::new(),
+            unlocks: Vec::new(),
+        }
+    }
+}
+
+impl ItemProperties {
+    /// Finds the item this makes when combined with the other item.
+    pub fn combination_with(&self, other_item: &str) -> Option<&ItemCombination> {
+        let with = self.combinations.iter().map(|c| &c.with);
+        let with = FuzzyMatch::find(other_item, with)?;
+
+        self.combinations.iter().find(|c| &c.with == with)
+    }
+
+    /// Removes combinations with items that aren't in the game, and unlocks for scenes that aren't
+    /// in the game. Names that are close enough are corrected.
+    pub fn remove_invalid_references(&mut self, item_names: &[String], scene_names: &[String]) {
+        self.combinations.retain_mut(|combination| {
+            match FuzzyMatch::find(&combination.with, item_names) {
+                Some(name) => {
+                    combination.with = name.clone();
+                    true
+                }
+                None => {
+                    warn!(
+                        "Removing combination with unknown item '{}'.",
+                        &combination.with
+                    );
+                    false
+                }
+            }
+        });
+
+        self.unlocks = self
+            .unlocks
+            .iter()
+            .filter_map(|scene| {
+                let name = FuzzyMatch::find(scene, scene_names);
+                if name.is_none() {
+                    warn!("Removing unlock for unknown scene '{}'.", scene);
+                }
+                name.cloned()
+            })
+            .collect();
+    }
+
+    /// Whether using the item opens the exit to a scene.
+    pub fn unlocks_scene(&self, scene_name: &str) -> bool {
+        self.unlocks
+            .iter()
+            .any(|s| s.eq_ignore_ascii_case(scene_name))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn missing_properties_use_defaults() {
+        let properties =
+            serde_json::from_value::<ItemProperties>(json!({ "consumable": true })).unwrap();
+
+        assert!(properties.usable);
+        assert!(properties.consumable);
+        assert!(properties.combinations.is_empty());
+    }
+
+    #[test]
+    fn finds_combinations() {
+        let properties = serde_json::from_value::<ItemProperties>(json!({
+            "combinations": [{ "with": "Grappling Hook", "result": "Climbing Rope" }],
+            "unlocks": ["Cliff Top"]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            properties
+                .combination_with("the grappling hook")
+                .unwrap()
+                .result,
+            "Climbing Rope"
+        );
+        assert!(properties.combination_with("Lantern").is_none());
+        assert!(properties.unlocks_scene("cliff top"));
+    }
+
+    #[test]
+    fn removes_invalid_references() {
+        let mut properties = serde_json::from_value::<ItemProperties>(json!({
+            "combinations": [
+                { "with": "grappling hook", "result": "Climbing Rope" },
+                { "with": "Lantern", "result": "Torch" }
+            ],
+            "unlocks": ["Cliff Top", "Moon"]
+        }))
+        .unwrap();
+
+        properties.remove_invalid_references(
+            &[String::from("Grappling Hook")],
+            &[String::from("Cliff Top"), String::from("Beach")],
+        );
+
+        assert_eq!(properties.combinations.len(), 1);
+        assert_eq!(properties.combinations[0].with, "Grappling Hook");
+        assert_eq!(properties.unlocks, vec!["Cliff Top"]);
+    }
+}
@@ -1,4 +1,6 @@
 pub mod item;
 mod item_input;
+mod item_properties;
 
 pub use item::Item;
+pub use item_properties::ItemProperties;
@@ -1,21 +1,30 @@
+mod attributes;
 mod character;
 mod chat_completion_factory;
 mod game;
 mod game_factory;
 pub mod game_generation_update;
 mod game_metadata;
+mod generation_record;
+mod generation_template;
 mod image;
 mod item;
 mod narrative;
+mod quest;
+mod regeneration_target;
 mod scene;
 mod scene_summary;
 mod summary;
 
 pub use game::Game;
 pub use game_factory::GameFactory;
+pub use generation_template::GenerationTemplate;
 
-pub use character::Character;
+pub use attributes::PlayerAttributes;
+pub use character::{Character, CharacterMovement};
 pub use image::Image;
-pub use item::Item;
+pub use item::{Item, ItemProperties};
 pub use narrative::Narrative;
-pub use scene::Scene;
+pub use quest::{Objective, ObjectiveTrigger, Quest};
+pub use regeneration_target::RegenerationTarget;
+pub use scene::{Scene, SceneExit};
@@ -1,3 +1,4 @@
+use anyhow::anyhow;
 use futures::{StreamExt, TryStreamExt};
 use log::info;
 use openai_lib::{
@@ -18,7 +19,6 @@ use crate::{
         },
         summary::Summary,
     },
-    prompt_builder::PromptBuilder,
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -37,13 +37,14 @@ impl Narrative {
         summary: &Summary,
         factory: &ChatCompletionFactory<'_>,
     ) -> Result<Self, anyhow::Error> {
-        let system_prompt = PromptBuilder::new()
-            .add_prompt("./prompts/narrative/main.txt")
-            .add_example_input("./prompts/narrative/example1_input.json")
-            .add_example_output("./prompts/narrative/example1_output.json")
-            .add_example_input("./prompts/narrative/example2_input.json")
-            .add_example_output("./prompts/narrative/example2_output.json")
-            .build();
+        let system_prompt = factory
+            .prompt_builder()
+            .add_prompt("narrative/main.txt")
+            .add_example_input("narrative/example1_input.json")
+            .add_example_output("narrative/example1_output.json")
+            .add_example_input("narrative/example2_input.json")
+            .add_example_output("narrative/example2_output.json")
+            .build()?;
 
         let user_prompt = summary.summary.clone();
 
@@ -56,6 +57,7 @@ impl Narrative {
                     .system_message(system_prompt)
                     .user_message(user_prompt)
                     .file_name("tmp/narrative.json")
+                    .template_guidelines()
                     .build(),
             )
             .await?;
@@ -63,6 +65,23 @@ impl Narrative {
         Ok(narrative)
     }
 
+    /// Resets the image for a single page back to a prompt, so that it will be generated again the
+    /// next time images are generated.
+    pub fn reset_page_image(
+        &mut self,
+        index: usize,
+        instruction: Option<&str>,
+    ) -> Result<(), anyhow::Error> {
+        let page = self
+            .pages
+            .get_mut(index)
+            .ok_or(anyhow!("Narrative page {} does not exist.", index))?;
+
+        page.image = page.image.to_prompt(instruction);
+
+        Ok(())
+    }
+
     pub async fn generate_images(
         &mut self,
         factory: &ImageFactory<'_>,
@@ -0,0 +1,6 @@
+mod objective;
+pub mod quest;
+mod quest_input;
+
+pub use objective::{Objective, ObjectiveTrigger};
+pub use quest::Quest;
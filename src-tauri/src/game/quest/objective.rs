@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// A single step of the game's quest, which becomes available once all of its prerequisites have
+/// been completed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Objective {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    #[serde(default)]
+    pub prerequisites: Vec<String>,
+    pub trigger: ObjectiveTrigger,
+    #[serde(default)]
+    pub is_final: bool,
+}
+
+/// What completes an objective. Every trigger except `Narrator` is checked automatically as the
+/// player plays.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ObjectiveTrigger {
+    ObtainItem { item: String },
+    ReachScene { scene: String },
+    TalkTo { character: String },
+    Narrator,
+}
+
+impl std::fmt::Display for ObjectiveTrigger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObjectiveTrigger::ObtainItem { item } => write!(f, "the player obtains the {}", item),
+            ObjectiveTrigger::ReachScene { scene } => write!(f, "the player reaches {}", scene),
+            ObjectiveTrigger::TalkTo { character } => {
+                write!(f, "the player talks to {}", character)
+            }
+            ObjectiveTrigger::Narrator => write!(f, "you call complete_objective"),
+        }
+    }
+}
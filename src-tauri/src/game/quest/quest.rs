@@ -0,0 +1,235 @@
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        chat_completion_factory::{ChatCompletionFactory, ChatCompletionFactoryArgs},
+        summary::Summary,
+        Character, Item, Scene,
+    },
+    prompt_builder::PromptInput,
+};
+
+use super::{quest_input::QuestInput, Objective, ObjectiveTrigger};
+
+/// The structured goal of the game, made up of objectives that unlock one another.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Quest {
+    pub objectives: Vec<Objective>,
+}
+
+impl Quest {
+    pub async fn create(
+        summary: &Summary,
+        scenes: &[Scene],
+        characters: &[Character],
+        items: &[Item],
+        factory: &ChatCompletionFactory<'_>,
+    ) -> Result<Self, anyhow::Error> {
+        let scene_names = scenes.iter().map(|s| s.name.clone()).collect::<Vec<_>>();
+        let character_names = characters
+            .iter()
+            .map(|c| c.name.clone())
+            .collect::<Vec<_>>();
+        let item_names = items.iter().map(|i| i.name.clone()).collect::<Vec<_>>();
+
+        let input = QuestInput::new(
+            &summary.summary,
+            &summary.win_condition,
+            scene_names.clone(),
+            character_names.clone(),
+            item_names.clone(),
+        );
+
+        let system_message = factory
+            .prompt_builder()
+            .add_prompt("quest/main.txt")
+            .add_example_input("quest/example1_input.json")
+            .add_example_output("quest/example1_output.json")
+            .build()?;
+
+        let user_message = input.to_prompt()?;
+
+        info!("Prepared system and user message for quest.");
+
+        factory
+            .try_create(
+                ChatCompletionFactoryArgs::builder()
+                    .name("Quest")
+                    .system_message(system_message)
+                    .user_message(user_message)
+                    .file_name("tmp/quest.json")
+                    .before_save(Box::new(move |mut quest: Quest| {
+                        quest.remove_invalid_references(
+                            &scene_names,
+                            &character_names,
+                            &item_names,
+                        );
+                        quest
+                    }))
+                    .build(),
+            )
+            .await
+    }
+
+    /// Lists the objectives in a form the narrator can refer to when completing them.
+    pub fn to_narrator_list(&self) -> String {
+        self.objectives
+            .iter()
+            .map(|o| {
+                let mut line = format!(
+                    "- {} ({}): {} Completed when {}.",
+                    o.id, o.title, o.description, o.trigger
+                );
+                if !o.prerequisites.is_empty() {
+                    line += &format!(" Requires: {}.", o.prerequisites.join(", "));
+                }
+                if o.is_final {
+                    line += " Completing this wins the game.";
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Fixes up anything in the generated quest that doesn't match the rest of the game:
+    ///
+    /// - Objectives with duplicate ids are removed.
+    /// - Triggers that refer to unknown scenes, characters or items are left to the narrator.
+    /// - Prerequisites may only refer to objectives listed before them, so the graph can't
+    ///   contain cycles.
+    /// - If no objective is final, the last objective is.
+    pub fn remove_invalid_references(
+        &mut self,
+        scenes: &[String],
+        characters: &[String],
+        items: &[String],
+    ) {
+        let mut seen_ids: Vec<String> = Vec::new();
+
+        self.objectives.retain(|objective| {
+            if seen_ids.contains(&objective.id) {
+                warn!("Removing objective with duplicate id '{}'.", &objective.id);
+                return false;
+            }
+            seen_ids.push(objective.id.clone());
+            true
+        });
+
+        for (index, objective) in self.objectives.iter_mut().enumerate() {
+            let earlier_ids = &seen_ids[..index];
+
+            objective.prerequisites.retain(|prerequisite| {
+                let valid = earlier_ids.contains(prerequisite);
+                if !valid {
+                    warn!(
+                        "Removing invalid prerequisite '{}' from objective '{}'.",
+                        prerequisite, &objective.id
+                    );
+                }
+                valid
+            });
+
+            let canonical_name = match &mut objective.trigger {
+                ObjectiveTrigger::ObtainItem { item } => Some((item, items)),
+                ObjectiveTrigger::ReachScene { scene } => Some((scene, scenes)),
+                ObjectiveTrigger::TalkTo { character } => Some((character, characters)),
+                ObjectiveTrigger::Narrator => None,
+            };
+
+            if let Some((name, known_names)) = canonical_name {
+                match known_names.iter().find(|n| n.eq_ignore_ascii_case(name)) {
+                    Some(known_name) => *name = known_name.clone(),
+                    None => {
+                        warn!(
+                            "Objective '{}' refers to unknown name '{}'. Leaving it to the narrator.",
+                            &objective.id, name
+                        );
+                        objective.trigger = ObjectiveTrigger::Narrator;
+                    }
+                }
+            }
+        }
+
+        if !self.objectives.iter().any(|o| o.is_final) {
+            if let Some(last) = self.objectives.last_mut() {
+                last.is_final = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::prompt_builder::PromptBuilder;
+
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn quest_example1_is_valid() {
+        let example1 = PromptBuilder::new()
+            .add_prompt("quest/example1_output.json")
+            .build()
+            .unwrap();
+        let mut quest = serde_json::from_str::<Quest>(&example1).unwrap();
+        let before = quest.clone();
+
+        quest.remove_invalid_references(
+            &names(&["Shadow Valley"]),
+            &names(&["Elder Maela", "Arin"]),
+            &names(&["Temple Map", "Echo Crystal"]),
+        );
+
+        assert_eq!(quest, before);
+    }
+
+    #[test]
+    fn removes_invalid_references() {
+        let mut quest = serde_json::from_value::<Quest>(json!({
+            "objectives": [
+                {
+                    "id": "find_map",
+                    "title": "",
+                    "description": "",
+                    "prerequisites": ["open_gate"],
+                    "trigger": { "type": "obtain_item", "item": "temple map" }
+                },
+                {
+                    "id": "open_gate",
+                    "title": "",
+                    "description": "",
+                    "prerequisites": ["find_map", "missing"],
+                    "trigger": { "type": "reach_scene", "scene": "Castle" }
+                },
+                {
+                    "id": "find_map",
+                    "title": "",
+                    "description": "",
+                    "trigger": { "type": "narrator" }
+                }
+            ]
+        }))
+        .unwrap();
+
+        quest.remove_invalid_references(&names(&["Village"]), &[], &names(&["Temple Map"]));
+
+        assert_eq!(quest.objectives.len(), 2);
+        assert!(quest.objectives[0].prerequisites.is_empty());
+        assert_eq!(
+            quest.objectives[0].trigger,
+            ObjectiveTrigger::ObtainItem {
+                item: String::from("Temple Map")
+            }
+        );
+        assert_eq!(quest.objectives[1].prerequisites, vec!["find_map"]);
+        assert_eq!(quest.objectives[1].trigger, ObjectiveTrigger::Narrator);
+        assert!(quest.objectives[1].is_final);
+    }
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+use crate::prompt_builder::PromptInput;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QuestInput {
+    game_summary: String,
+    win_condition: String,
+    scenes: Vec<String>,
+    characters: Vec<String>,
+    items: Vec<String>,
+}
+
+impl QuestInput {
+    pub fn new(
+        game_summary: &str,
+        win_condition: &str,
+        scenes: Vec<String>,
+        characters: Vec<String>,
+        items: Vec<String>,
+    ) -> Self {
+        Self {
+            game_summary: game_summary.to_string(),
+            win_condition: win_condition.to_string(),
+            scenes,
+            characters,
+            items,
+        }
+    }
+}
+
+impl PromptInput for QuestInput {}
+
+#[cfg(test)]
+mod test {
+    use crate::prompt_builder::PromptBuilder;
+
+    use super::*;
+
+    #[test]
+    fn matches_example1_input() {
+        let example1 = PromptBuilder::new()
+            .add_prompt("quest/example1_input.json")
+            .build()
+            .unwrap();
+        serde_json::from_str::<QuestInput>(&example1).unwrap();
+    }
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// An individual artifact of a generated game that can be regenerated.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", content = "target", rename_all = "snake_case")]
+pub enum RegenerationTarget {
+    Cover,
+    NarrativePage(usize),
+    Scene(String),
+    Character(String),
+    Item(String),
+}
+
+impl std::fmt::Display for RegenerationTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegenerationTarget::Cover => write!(f, "cover art"),
+            RegenerationTarget::NarrativePage(index) => write!(f, "narrative page {}", index + 1),
+            RegenerationTarget::Scene(name) => write!(f, "scene {}", name),
+            RegenerationTarget::Character(name) => write!(f, "character {}", name),
+            RegenerationTarget::Item(name) => write!(f, "item {}", name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn deserializes_from_tagged_json() {
+        let cover: RegenerationTarget = serde_json::from_value(json!({ "type": "cover" })).unwrap();
+        let page: RegenerationTarget =
+            serde_json::from_value(json!({ "type": "narrative_page", "target": 2 })).unwrap();
+        let scene: RegenerationTarget =
+            serde_json::from_value(json!({ "type": "scene", "target": "Village" })).unwrap();
+
+        assert_eq!(cover, RegenerationTarget::Cover);
+        assert_eq!(page, RegenerationTarget::NarrativePage(2));
+        assert_eq!(scene, RegenerationTarget::Scene(String::from("Village")));
+    }
+}
@@ -1,4 +1,6 @@
 pub mod scene;
+mod scene_exit;
 mod scene_input;
 
 pub use scene::Scene;
+pub use scene_exit::SceneExit;
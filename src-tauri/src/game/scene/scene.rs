@@ -17,10 +17,10 @@ use crate::{
         scene_summary::{SceneSummary, SummarizedScene},
         summary::Summary,
     },
-    prompt_builder::PromptBuilder,
+    prompt_builder::PromptInput,
 };
 
-use super::scene_input::SceneInput;
+use super::{scene_exit::SceneExit, scene_input::SceneInput};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Scene {
@@ -30,6 +30,8 @@ pub struct Scene {
     pub image: Image,
     pub characters: Vec<String>,
     pub items: Vec<String>,
+    #[serde(default)]
+    pub exits: Vec<SceneExit>,
 }
 
 impl Scene {
@@ -58,15 +60,16 @@ impl Scene {
     ) -> Result<Self, anyhow::Error> {
         let scene_detail_input = SceneInput::new(&summary.summary, summarized_scene);
 
-        let system_message = PromptBuilder::new()
-            .add_prompt("./prompts/scene_detail/main.txt")
-            .add_example_input("./prompts/scene_detail/example1_input.json")
-            .add_example_output("./prompts/scene_detail/example1_output.json")
-            .add_example_input("./prompts/scene_detail/example2_input.json")
-            .add_example_output("./prompts/scene_detail/example2_output.json")
-            .build();
+        let system_message = factory
+            .prompt_builder()
+            .add_prompt("scene_detail/main.txt")
+            .add_example_input("scene_detail/example1_input.json")
+            .add_example_output("scene_detail/example1_output.json")
+            .add_example_input("scene_detail/example2_input.json")
+            .add_example_output("scene_detail/example2_output.json")
+            .build()?;
 
-        let user_message = serde_json::to_string(&scene_detail_input).unwrap();
+        let user_message = scene_detail_input.to_prompt()?;
 
         info!(
             "Prepared system and user message for generating scene '{}'.",
@@ -74,6 +77,7 @@ impl Scene {
         );
 
         let scene_name = summarized_scene.name.clone();
+        let exits = summarized_scene.exits.clone();
         let name = format!("{} Scene Detail", &scene_name);
         let file_name = format!("tmp/scenes/{}.json", &scene_name);
 
@@ -88,6 +92,9 @@ impl Scene {
                         // Ensure that the LLM doesn't try to change the name, leading to filename
                         // mismatch
                         scene.name = scene_name.clone();
+                        // Exits are decided when the scene summary is generated, so the scene
+                        // graph stays consistent between scenes
+                        scene.exits = exits.clone();
                         scene
                     }))
                     .build(),
@@ -95,7 +102,12 @@ impl Scene {
             .await
     }
 
-    async fn generate_image(
+    /// Finds the exit from this scene that leads to the provided scene, if any.
+    pub fn find_exit(&self, scene_name: &str) -> Option<&SceneExit> {
+        self.exits.iter().find(|e| e.scene == scene_name)
+    }
+
+    pub async fn generate_image(
         &mut self,
         factory: &ImageFactory<'_>,
         game_metadata: &GameMetadata,
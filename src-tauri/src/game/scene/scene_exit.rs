@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+
+/// A named, one-way connection from one scene to another. An exit can optionally be locked behind
+/// an item the player must be carrying, or the permission of a character in the scene.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SceneExit {
+    pub name: String,
+    pub scene: String,
+    #[serde(default)]
+    pub required_item: Option<String>,
+    #[serde(default)]
+    pub permission_from: Option<String>,
+}
+
+impl SceneExit {
+    /// Checks whether the player can pass through this exit, returning the reason as an error if
+    /// not. Each lock is checked on its own terms: `item_unlocked` should be true if the player has
+    /// unlocked the exit by using an item, and `passage_granted` should be true if the character in
+    /// `permission_from` has already allowed the player through.
+    pub fn check_access(
+        &self,
+        inventory: &[String],
+        item_unlocked: bool,
+        passage_granted: bool,
+    ) -> Result<(), String> {
+        if let Some(required_item) = &self.required_item {
+            if !item_unlocked
+                && !inventory
+                    .iter()
+                    .any(|i| i.eq_ignore_ascii_case(required_item))
+            {
+                return Err(format!(
+                    "The way to {} via {} is locked. The player needs the {} to pass.",
+                    self.scene, self.name, required_item
+                ));
+            }
+        }
+
+        if let Some(character) = &self.permission_from {
+            if !passage_granted {
+                return Err(format!(
+                    "The way to {} via {} is blocked. The player needs permission from {} to pass.",
+                    self.scene, self.name, character
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn exit(required_item: Option<&str>, permission_from: Option<&str>) -> SceneExit {
+        SceneExit {
+            name: String::from("Temple Gate"),
+            scene: String::from("Forgotten Corridors"),
+            required_item: required_item.map(String::from),
+            permission_from: permission_from.map(String::from),
+        }
+    }
+
+    #[test]
+    fn unlocked_exit_allows_access() {
+        assert!(exit(None, None).check_access(&[], false, false).is_ok());
+    }
+
+    #[test]
+    fn item_lock_requires_item() {
+        let exit = exit(Some("Temple Map"), None);
+
+        assert!(exit.check_access(&[], false, false).is_err());
+        assert!(exit
+            .check_access(&[String::from("temple map")], false, false)
+            .is_ok());
+    }
+
+    #[test]
+    fn permission_lock_requires_passage() {
+        let exit = exit(None, Some("Arin"));
+
+        assert!(exit.check_access(&[], false, false).is_err());
+        assert!(exit.check_access(&[], false, true).is_ok());
+    }
+
+    #[test]
+    fn item_unlock_satisfies_item_lock() {
+        let exit = exit(Some("Temple Map"), None);
+
+        assert!(exit.check_access(&[], true, false).is_ok());
+    }
+
+    #[test]
+    fn exit_with_both_locks_needs_both() {
+        let exit = exit(Some("Temple Map"), Some("Arin"));
+        let inventory = [String::from("Temple Map")];
+
+        assert!(exit.check_access(&inventory, false, false).is_err());
+        assert!(exit.check_access(&[], true, false).is_err());
+        assert!(exit.check_access(&[], false, true).is_err());
+        assert!(exit.check_access(&inventory, false, true).is_ok());
+        assert!(exit.check_access(&[], true, true).is_ok());
+    }
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::game::scene_summary::SummarizedScene;
+use crate::{game::scene_summary::SummarizedScene, prompt_builder::PromptInput};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SceneInput {
@@ -17,6 +17,8 @@ impl SceneInput {
     }
 }
 
+impl PromptInput for SceneInput {}
+
 #[cfg(test)]
 mod test {
     use crate::prompt_builder::PromptBuilder;
@@ -26,8 +28,9 @@ mod test {
     #[test]
     fn scene_detail_input_example1_matches() {
         let example1 = PromptBuilder::new()
-            .add_prompt("./prompts/scene_detail/example1_input.json")
-            .build();
+            .add_prompt("scene_detail/example1_input.json")
+            .build()
+            .unwrap();
 
         serde_json::from_str::<SceneInput>(&example1).unwrap();
     }
@@ -35,8 +38,9 @@ mod test {
     #[test]
     fn scene_detail_input_example2_matches() {
         let example2 = PromptBuilder::new()
-            .add_prompt("./prompts/scene_detail/example2_input.json")
-            .build();
+            .add_prompt("scene_detail/example2_input.json")
+            .build()
+            .unwrap();
 
         serde_json::from_str::<SceneInput>(&example2).unwrap();
     }
@@ -1,12 +1,13 @@
-use log::info;
+use log::{info, warn};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     game::{
         chat_completion_factory::{ChatCompletionFactory, ChatCompletionFactoryArgs},
         summary::Summary,
+        SceneExit,
     },
-    prompt_builder::PromptBuilder,
+    prompt_builder::PromptInput,
 };
 
 use super::scene_summary_input::SceneSummaryInput;
@@ -21,6 +22,8 @@ pub struct SummarizedScene {
     pub name: String,
     description: String,
     actions: String,
+    #[serde(default)]
+    pub exits: Vec<SceneExit>,
 }
 
 impl SceneSummary {
@@ -33,15 +36,16 @@ impl SceneSummary {
             summary.win_condition.to_string(),
         );
 
-        let system_message = PromptBuilder::new()
-            .add_prompt("./prompts/scene_summary/main.txt")
-            .add_example_input("./prompts/scene_summary/example1_input.json")
-            .add_example_output("./prompts/scene_summary/example1_output.json")
-            .add_example_input("./prompts/scene_summary/example2_input.json")
-            .add_example_output("./prompts/scene_summary/example2_output.json")
-            .build();
+        let system_message = factory
+            .prompt_builder()
+            .add_prompt("scene_summary/main.txt")
+            .add_example_input("scene_summary/example1_input.json")
+            .add_example_output("scene_summary/example1_output.json")
+            .add_example_input("scene_summary/example2_input.json")
+            .add_example_output("scene_summary/example2_output.json")
+            .build()?;
 
-        let user_message = input.to_string();
+        let user_message = input.to_prompt()?;
 
         info!("Prepared system and user message for scene summary.");
 
@@ -52,8 +56,90 @@ impl SceneSummary {
                     .system_message(system_message)
                     .user_message(user_message)
                     .file_name("tmp/scene_summary.json")
+                    .template_guidelines()
+                    .before_save(Box::new(|mut scene_summary: SceneSummary| {
+                        scene_summary.remove_invalid_exits();
+                        scene_summary
+                    }))
                     .build(),
             )
             .await
     }
+
+    /// Removes any exits that lead to scenes that don't exist, or back into the same scene.
+    fn remove_invalid_exits(&mut self) {
+        let scene_names = self
+            .scenes
+            .iter()
+            .map(|s| s.name.clone())
+            .collect::<Vec<String>>();
+
+        for scene in &mut self.scenes {
+            let scene_name = scene.name.clone();
+            scene.exits.retain(|e| {
+                let valid = e.scene != scene_name && scene_names.contains(&e.scene);
+                if !valid {
+                    warn!(
+                        "Removing exit '{}' from {} to unknown scene {}.",
+                        &e.name, &scene_name, &e.scene
+                    );
+                }
+                valid
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prompt_builder::PromptBuilder;
+
+    use super::*;
+
+    #[test]
+    fn scene_summary_example1_exits_are_valid() {
+        let example1 = PromptBuilder::new()
+            .add_prompt("scene_summary/example1_output.json")
+            .build()
+            .unwrap();
+        let mut scene_summary = serde_json::from_str::<SceneSummary>(&example1).unwrap();
+        let before = scene_summary.clone();
+
+        scene_summary.remove_invalid_exits();
+
+        for (scene, original) in scene_summary.scenes.iter().zip(before.scenes.iter()) {
+            assert!(!scene.exits.is_empty());
+            assert_eq!(scene.exits, original.exits);
+        }
+    }
+
+    #[test]
+    fn removes_exits_to_unknown_scenes() {
+        let mut scene_summary = serde_json::from_value::<SceneSummary>(serde_json::json!({
+            "scenes": [
+                {
+                    "name": "Village",
+                    "description": "",
+                    "actions": "",
+                    "exits": [
+                        { "name": "Forest Path", "scene": "Forest" },
+                        { "name": "Old Bridge", "scene": "Castle" },
+                        { "name": "Village Square", "scene": "Village" }
+                    ]
+                },
+                {
+                    "name": "Forest",
+                    "description": "",
+                    "actions": ""
+                }
+            ]
+        }))
+        .unwrap();
+
+        scene_summary.remove_invalid_exits();
+
+        assert_eq!(scene_summary.scenes[0].exits.len(), 1);
+        assert_eq!(scene_summary.scenes[0].exits[0].scene, "Forest");
+        assert!(scene_summary.scenes[1].exits.is_empty());
+    }
 }
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::prompt_builder::PromptInput;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SceneSummaryInput {
     summary: String,
@@ -13,12 +15,10 @@ impl SceneSummaryInput {
             win_condition,
         }
     }
-
-    pub fn to_string(self) -> String {
-        serde_json::to_string(&self).unwrap()
-    }
 }
 
+impl PromptInput for SceneSummaryInput {}
+
 #[cfg(test)]
 mod test {
     use crate::prompt_builder::PromptBuilder;
@@ -28,16 +28,18 @@ mod test {
     #[test]
     fn matches_example1_input() {
         let example1 = PromptBuilder::new()
-            .add_prompt("./prompts/scene_summary/example1_input.json")
-            .build();
+            .add_prompt("scene_summary/example1_input.json")
+            .build()
+            .unwrap();
         serde_json::from_str::<SceneSummaryInput>(&example1).unwrap();
     }
 
     #[test]
     fn matches_example2_input() {
         let example2 = PromptBuilder::new()
-            .add_prompt("./prompts/scene_summary/example2_input.json")
-            .build();
+            .add_prompt("scene_summary/example2_input.json")
+            .build()
+            .unwrap();
         serde_json::from_str::<SceneSummaryInput>(&example2).unwrap();
     }
 }
@@ -16,7 +16,6 @@ use crate::{
             Image,
         },
     },
-    prompt_builder::PromptBuilder,
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -35,13 +34,13 @@ impl Summary {
         factory: &ChatCompletionFactory<'_>,
         user_message: &str,
     ) -> Result<Self, anyhow::Error> {
-        let system_message = PromptBuilder::new()
-            .add_prompt("./prompts/summary/main.txt")
+        let system_message = factory.prompt_builder()
+            .add_prompt("summary/main.txt")
             .add_plain_text("Example Input: Make a game about mystical forests and ancient ruins")
-            .add_example_output("./prompts/summary/example1.json")
+            .add_example_output("summary/example1.json")
             .add_plain_text("Example Input: I want to wake up on an abandoned spaceship infested with alien life")
-            .add_example_output("./prompts/summary/example2.json")
-            .build();
+            .add_example_output("summary/example2.json")
+            .build()?;
 
         let user_message = String::from(user_message);
 
@@ -54,6 +53,7 @@ impl Summary {
                     .system_message(system_message)
                     .user_message(user_message)
                     .file_name("tmp/summary.json")
+                    .template_guidelines()
                     // This is the first OpenAI request - failure probably means
                     // something is amiss
                     .max_attempts(1)
@@ -1,9 +1,13 @@
 pub mod game_session_error;
+pub mod session_command;
+pub mod session_handle;
+pub mod session_request_update;
+mod turn_history;
 
 use std::sync::Arc;
 
-use anyhow::Context;
-use log::info;
+use anyhow::{anyhow, bail, ensure, Context};
+use log::{info, warn};
 use openai_lib::{
     assistant::{AssistantClient, CreateAssistantRequest},
     model::ChatModel,
@@ -14,72 +18,126 @@ use openai_lib::{
 use tokio::sync::{mpsc::Sender, Mutex};
 
 use crate::{
+    config::run_polling_config::RunPollingConfig,
     file_manager::FileManager,
     game::Game,
-    game_state::GameState,
-    prompt_builder::PromptBuilder,
-    session_context::{session_request::SessionRequest, SessionContext},
+    game_state::{player_intent::PlayerIntent, GameState},
+    prompt_builder::{PromptBuilder, PromptLibrary},
+    session_context::{
+        cancellation::Cancellation, session_request::SessionRequest,
+        state_history::StateTransition, SessionContext,
+    },
 };
 
+use self::turn_history::TurnHistory;
+
 #[derive(Debug)]
 pub struct GameSession {
     pub game: Game,
     pub game_state: GameState,
     session_context: SessionContext,
+    history: TurnHistory,
+    file_manager: FileManager,
 }
 
 impl GameSession {
     pub async fn start_new(
         game_id: String,
+        save_slot: String,
         openai_client: &OpenAIClient,
+        polling_config: &RunPollingConfig,
+        cancellation: Cancellation,
         file_manager: &FileManager,
         state_update_tx: Arc<Mutex<Sender<GameState>>>,
     ) -> Result<Self, anyhow::Error> {
         info!("Starting new game session for game id {}.", &game_id);
         let game = Game::load(&game_id, file_manager)?;
 
-        let summary_text = format!("Game Summary:\n{}", &game.summary.summary);
-        let scene_list_text = format!(
-            "Scene List:\n[{}]",
-            game.scenes
-                .iter()
-                .map(|scene| format!("{},", scene.name))
-                .collect::<String>()
-        );
+        let scene_list = game
+            .scenes
+            .iter()
+            .map(|scene| scene.name.clone())
+            .collect::<Vec<_>>()
+            .join(", ");
 
         let instructions = PromptBuilder::new()
-            .add_prompt("./prompts/narrator/main.txt")
-            .add_plain_text(&summary_text)
-            .add_plain_text(&scene_list_text)
-            .build();
-
-        let assistant_response = openai_client
-            .create_assistant(
-                CreateAssistantRequest::builder()
-                    .instructions(instructions)
-                    .model(ChatModel::Gpt_35_Turbo_1106)
-                    .name(&game_id)
-                    .add_tool(
-                        Tool::function().from_file("./prompts/narrator/add_item_function.json")?,
-                    )
-                    .add_tool(
-                        Tool::function()
-                            .from_file("./prompts/narrator/remove_item_function.json")?,
-                    )
-                    .add_tool(
-                        Tool::function().from_file("./prompts/narrator/new_scene_function.json")?,
-                    )
-                    .add_tool(
-                        Tool::function()
-                            .from_file("./prompts/narrator/character_interact_function.json")?,
-                    )
-                    .add_tool(
-                        Tool::function().from_file("./prompts/narrator/end_game_function.json")?,
-                    )
-                    .build(),
+            .add_prompt("narrator/main.txt")
+            .set_variable("game_summary", &game.summary.summary)
+            .set_variable("scene_list", scene_list)
+            .set_variable(
+                "quest",
+                game.quest
+                    .as_ref()
+                    .map(|quest| quest.to_narrator_list())
+                    .unwrap_or_default(),
             )
-            .await
-            .context("Failed to generate assistant for game narrator.")?;
+            .set_variable("attributes", game.attributes.to_narrator_list())
+            .build()
+            .context("Failed to build instructions for game narrator.")?;
+
+        let assistant_response =
+            openai_client
+                .create_assistant(
+                    CreateAssistantRequest::builder()
+                        .instructions(instructions)
+                        .model(ChatModel::Gpt_35_Turbo_1106)
+                        .name(&game_id)
+                        .add_tool(
+                            Tool::function().from_json(&PromptLibrary::read(
+                                "narrator/add_item_function.json",
+                            )?)?,
+                        )
+                        .add_tool(Tool::function().from_json(&PromptLibrary::read(
+                            "narrator/remove_item_function.json",
+                        )?)?)
+                        .add_tool(
+                            Tool::function().from_json(&PromptLibrary::read(
+                                "narrator/drop_item_function.json",
+                            )?)?,
+                        )
+                        .add_tool(
+                            Tool::function().from_json(&PromptLibrary::read(
+                                "narrator/use_item_function.json",
+                            )?)?,
+                        )
+                        .add_tool(Tool::function().from_json(&PromptLibrary::read(
+                            "narrator/combine_items_function.json",
+                        )?)?)
+                        .add_tool(
+                            Tool::function().from_json(&PromptLibrary::read(
+                                "narrator/new_scene_function.json",
+                            )?)?,
+                        )
+                        .add_tool(Tool::function().from_json(&PromptLibrary::read(
+                            "narrator/character_interact_function.json",
+                        )?)?)
+                        .add_tool(Tool::function().from_json(&PromptLibrary::read(
+                            "narrator/complete_objective_function.json",
+                        )?)?)
+                        .add_tool(Tool::function().from_json(&PromptLibrary::read(
+                            "narrator/modify_stat_function.json",
+                        )?)?)
+                        .add_tool(Tool::function().from_json(&PromptLibrary::read(
+                            "narrator/skill_check_function.json",
+                        )?)?)
+                        .add_tool(Tool::function().from_json(&PromptLibrary::read(
+                            "narrator/examine_item_function.json",
+                        )?)?)
+                        .add_tool(Tool::function().from_json(&PromptLibrary::read(
+                            "narrator/examine_character_function.json",
+                        )?)?)
+                        .add_tool(Tool::function().from_json(&PromptLibrary::read(
+                            "narrator/recall_lore_function.json",
+                        )?)?)
+                        .add_tool(
+                            Tool::function().from_json(&PromptLibrary::read(
+                                "narrator/end_game_function.json",
+                            )?)?,
+                        )
+                        .build(),
+                )
+                .await
+                .context("Failed to generate assistant for game narrator.")?;
 
         let narrator_assistant_id = assistant_response.id;
 
@@ -91,32 +149,230 @@ impl GameSession {
         let thread_id = thread_response.id;
 
         let mut game_state = GameState::new(&game, &narrator_assistant_id, &thread_id);
-        let openai_client = openai_client.clone();
-        let mut session_context = SessionContext::new(game.clone(), openai_client, state_update_tx);
+        game_state.save_slot = save_slot;
 
-        session_context
-            .process(SessionRequest::ContinueProcessing, &mut game_state)
-            .await;
+        let openai_client = openai_client.clone();
+        let session_context = SessionContext::new(
+            game.clone(),
+            openai_client,
+            polling_config.clone(),
+            cancellation,
+            file_manager.clone(),
+            state_update_tx,
+        );
 
         let game_session = GameSession {
             game,
             game_state,
             session_context,
+            history: TurnHistory::default(),
+            file_manager: file_manager.clone(),
         };
 
         Ok(game_session)
     }
 
+    /// Picks up a saved game where it left off, with the narrator assistant and thread it was
+    /// played with.
+    pub fn resume(
+        mut game_state: GameState,
+        openai_client: &OpenAIClient,
+        polling_config: &RunPollingConfig,
+        cancellation: Cancellation,
+        file_manager: &FileManager,
+        state_update_tx: Arc<Mutex<Sender<GameState>>>,
+    ) -> Result<Self, anyhow::Error> {
+        info!("Resuming game session {}.", game_state.session_id());
+        let game = Game::load(&game_state.game_id, file_manager)?;
+
+        // An offer saved while waiting on the player's answer can't be answered, since the run
+        // waiting on it isn't saved with the game
+        if let Some(character_interaction) = game_state.character_interaction.as_mut() {
+            character_interaction.trade = None;
+            character_interaction.counter_offer = None;
+        }
+
+        let session_context = SessionContext::new(
+            game.clone(),
+            openai_client.clone(),
+            polling_config.clone(),
+            cancellation,
+            file_manager.clone(),
+            state_update_tx,
+        )
+        .resumed(&game_state);
+
+        Ok(GameSession {
+            game,
+            game_state,
+            session_context,
+            history: TurnHistory::default(),
+            file_manager: file_manager.clone(),
+        })
+    }
+
+    /// Reads the game saved in the slot, if it has been played in before.
+    pub fn load_save(
+        game_id: &str,
+        save_slot: &str,
+        file_manager: &FileManager,
+    ) -> Result<Option<GameState>, anyhow::Error> {
+        let save_path = GameSession::save_path(game_id, save_slot)?;
+
+        if !file_manager.file_exists(&save_path)? {
+            return Ok(None);
+        }
+
+        let game_state = file_manager
+            .read_json::<GameState>(&save_path)
+            .context("Failed to read saved game.")?;
+
+        Ok(Some(game_state))
+    }
+
+    /// Writes the game to its save slot. The game can only be saved while it's waiting on the
+    /// player, since a run in progress can't be picked up again.
+    pub fn save(&self) -> Result<GameState, anyhow::Error> {
+        if !self.session_context.is_waiting_on_player() {
+            bail!("Unable to save while the narrator or a character is responding.");
+        }
+
+        let save_path =
+            GameSession::save_path(&self.game_state.game_id, &self.game_state.save_slot)?;
+
+        info!("Saving game session to {}.", &save_path);
+
+        self.file_manager
+            .write_json(save_path, &self.game_state)
+            .context("Failed to write saved game.")?;
+
+        Ok(self.game_state.clone())
+    }
+
+    fn save_path(game_id: &str, save_slot: &str) -> Result<String, anyhow::Error> {
+        // Both names come from the frontend, so they can't be allowed to reach outside the game
+        ensure!(
+            GameSession::is_valid_file_name(game_id),
+            "Invalid game id '{}'.",
+            game_id
+        );
+        ensure!(
+            GameSession::is_valid_file_name(save_slot),
+            "Invalid save slot name '{}'.",
+            save_slot
+        );
+
+        Ok(format!("{}/saves/{}.json", game_id, save_slot))
+    }
+
+    fn is_valid_file_name(name: &str) -> bool {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+
+    /// Runs the narrator's introduction to the game.
+    pub async fn begin(&mut self) -> Result<GameState, anyhow::Error> {
+        self.session_context
+            .process(SessionRequest::ContinueProcessing, &mut self.game_state)
+            .await;
+
+        Ok(self.game_state.clone())
+    }
+
     pub async fn receive_player_message(
         &mut self,
         message: String,
     ) -> Result<GameState, anyhow::Error> {
         info!("Received player message: {}", &message);
 
+        let narrator_waiting =
+            self.session_context.is_idle() && self.game_state.character_interaction.is_none();
+
+        // Messages to characters are passed on as they are, since they aren't commands
+        let intent = match narrator_waiting {
+            true => PlayerIntent::parse(&message, &self.game, &self.game_state),
+            false => PlayerIntent::Unrecognized,
+        };
+
+        // Questions about the game don't take up a turn, so the narrator never hears of them
+        if let PlayerIntent::Meta(command) = intent {
+            info!("Answering {:?} without the narrator.", command);
+
+            let answer = command.answer(&self.game_state);
+            self.game_state.add_meta_message(&message, &answer);
+
+            return Ok(self.game_state.clone());
+        }
+
+        // Turns can only be rewound to a point where the narrator was waiting on the player
+        if narrator_waiting {
+            self.history.record(&self.game_state);
+        }
+
+        self.game_state.player_intent = match intent {
+            PlayerIntent::Action(hint) => Some(hint),
+            _ => None,
+        };
+
         self.session_context
             .process(SessionRequest::PlayerEntry(message), &mut self.game_state)
             .await;
 
+        self.game_state.player_intent = None;
+
+        if self.session_context.is_idle()
+            && self.game_state.character_interaction.is_none()
+            && self.game_state.narrator_memory.is_due(self.game_state.turn)
+        {
+            // The narrator keeps its full thread until the next turn if this fails
+            if let Err(e) = self
+                .session_context
+                .condense_narrator_memory(&mut self.game_state)
+                .await
+            {
+                warn!("Unable to condense narrator memory: {:?}", e);
+            }
+        }
+
+        Ok(self.game_state.clone())
+    }
+
+    pub fn state_graph(&self) -> String {
+        self.session_context.state_graph()
+    }
+
+    pub fn state_history(&self) -> Vec<StateTransition> {
+        self.session_context.state_history()
+    }
+
+    /// Restores the game state to how it was at the end of `turn`, and replays the story up to
+    /// that point into a new narrator thread.
+    pub async fn rewind(&mut self, turn: u32) -> Result<GameState, anyhow::Error> {
+        info!("Rewinding game to turn {}.", turn);
+
+        if !self.session_context.is_idle() || self.game_state.character_interaction.is_some() {
+            bail!("Unable to rewind while the narrator or a character is responding.");
+        }
+
+        let mut game_state = self
+            .history
+            .snapshot(turn)
+            .ok_or(anyhow!("No snapshot is available for turn {}.", turn))?;
+
+        // The restored state still refers to the current thread, which is replaced below
+        game_state.thread_id = self.game_state.thread_id.clone();
+
+        self.session_context
+            .replace_thread(&mut game_state)
+            .await
+            .context("Failed to replay rewound game into a new thread.")?;
+
+        // Only discard later turns once the rewind has worked, so a failure can be retried
+        self.history.truncate(turn);
+        self.game_state = game_state;
+
         Ok(self.game_state.clone())
     }
 
@@ -136,6 +392,29 @@ impl GameSession {
         Ok(self.game_state.clone())
     }
 
+    pub async fn receive_trade_counter_offer(
+        &mut self,
+        to_player: Vec<String>,
+        from_player: Vec<String>,
+    ) -> Result<GameState, anyhow::Error> {
+        info!(
+            "Received trade counter-offer of {:?} for {:?}",
+            &from_player, &to_player
+        );
+
+        self.session_context
+            .process(
+                SessionRequest::CharacterTradeCounterOffer {
+                    to_player,
+                    from_player,
+                },
+                &mut self.game_state,
+            )
+            .await;
+
+        Ok(self.game_state.clone())
+    }
+
     pub async fn end_character_interaction(&mut self) -> Result<GameState, anyhow::Error> {
         info!("Ending character interaction.");
 
@@ -149,3 +428,20 @@ impl GameSession {
         Ok(self.game_state.clone())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn save_slots_stay_inside_game_directory() {
+        assert_eq!(
+            GameSession::save_path("game", "slot_2").unwrap(),
+            "game/saves/slot_2.json"
+        );
+        assert!(GameSession::save_path("game", "../other").is_err());
+        assert!(GameSession::save_path("game", "").is_err());
+        assert!(GameSession::save_path("../game", "slot").is_err());
+        assert!(GameSession::save_path("", "slot").is_err());
+    }
+}
@@ -0,0 +1,36 @@
+use tokio::sync::oneshot;
+
+use crate::session_context::state_history::StateTransition;
+
+/// Work for a game session's actor, queued by its `SessionHandle`.
+#[derive(Debug)]
+pub enum SessionCommand {
+    /// Runs the narrator's introduction for a new session.
+    Begin,
+    PlayerMessage(String),
+    TradeResponse(bool),
+    TradeCounterOffer {
+        to_player: Vec<String>,
+        from_player: Vec<String>,
+    },
+    EndCharacterInteraction,
+    Rewind(u32),
+    /// Writes the game to its save slot.
+    Save,
+    /// Saves the game and stops the session, replying with whether it could be saved. The session
+    /// keeps running if it couldn't.
+    Suspend(oneshot::Sender<Result<(), String>>),
+    ExportGraph(oneshot::Sender<(String, Vec<StateTransition>)>),
+}
+
+impl SessionCommand {
+    /// Whether the command replaces whatever the session is doing, rather than waiting its turn.
+    pub fn preempts(&self) -> bool {
+        matches!(
+            self,
+            SessionCommand::EndCharacterInteraction
+                | SessionCommand::Rewind(_)
+                | SessionCommand::Suspend(_)
+        )
+    }
+}
@@ -0,0 +1,259 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use anyhow::anyhow;
+use log::{error, info};
+use tokio::sync::{
+    mpsc::{self, Receiver, Sender},
+    oneshot, watch, Mutex,
+};
+
+use crate::{
+    game_state::GameState,
+    session_context::{cancellation::Cancellation, state_history::StateTransition},
+};
+
+use super::{
+    session_command::SessionCommand,
+    session_request_update::{SessionRequestStatus, SessionRequestUpdate},
+    GameSession,
+};
+
+const MAX_QUEUED_COMMANDS: usize = 32;
+
+/// Queues work for a game session, which is processed in order by a background task. Commands
+/// return as soon as their work is queued, so they never wait on the session's runs, and the
+/// results reach the UI as state and request updates.
+pub struct SessionHandle {
+    commands_tx: Sender<(u64, SessionCommand)>,
+    game_state_rx: watch::Receiver<GameState>,
+    cancellation: Cancellation,
+    /// Queued requests with a lower id than this were cancelled before they started.
+    superseded_before: Arc<AtomicU64>,
+    next_request_id: u64,
+}
+
+impl SessionHandle {
+    pub fn spawn(
+        game_session: GameSession,
+        cancellation: Cancellation,
+        state_update_tx: Arc<Mutex<Sender<GameState>>>,
+        request_update_tx: Arc<Mutex<Sender<SessionRequestUpdate>>>,
+    ) -> Self {
+        let (commands_tx, commands_rx) = mpsc::channel(MAX_QUEUED_COMMANDS);
+        let (game_state_tx, game_state_rx) = watch::channel(game_session.game_state.clone());
+        let superseded_before = Arc::new(AtomicU64::new(0));
+
+        let actor = SessionActor {
+            game_session,
+            commands_rx,
+            game_state_tx,
+            cancellation: cancellation.clone(),
+            superseded_before: superseded_before.clone(),
+            state_update_tx,
+            request_update_tx,
+        };
+
+        tauri::async_runtime::spawn(actor.run());
+
+        SessionHandle {
+            commands_tx,
+            game_state_rx,
+            cancellation,
+            superseded_before,
+            next_request_id: 0,
+        }
+    }
+
+    /// Queues the command and returns the id its request update will be sent with. Commands that
+    /// preempt cancel everything ahead of them first.
+    pub fn send(&mut self, command: SessionCommand) -> Result<u64, anyhow::Error> {
+        if command.preempts() {
+            self.cancel();
+        }
+
+        let request_id = self.next_request_id;
+
+        self.commands_tx
+            .try_send((request_id, command))
+            .map_err(|e| anyhow!("Unable to queue session request: {}", e))?;
+
+        self.next_request_id += 1;
+
+        Ok(request_id)
+    }
+
+    /// Stops waiting on the session's current run and drops any requests queued behind it.
+    pub fn cancel(&self) {
+        info!(
+            "Cancelling session requests before {}.",
+            self.next_request_id
+        );
+
+        self.superseded_before
+            .store(self.next_request_id, Ordering::SeqCst);
+        self.cancellation.cancel();
+    }
+
+    /// Queues a request for the session's state graph, which is answered once the requests ahead
+    /// of it are done.
+    pub fn export_graph(
+        &mut self,
+    ) -> Result<oneshot::Receiver<(String, Vec<StateTransition>)>, anyhow::Error> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.send(SessionCommand::ExportGraph(reply_tx))?;
+
+        Ok(reply_rx)
+    }
+
+    /// Queues saving the game and stopping the session, which replies once the game is saved or
+    /// couldn't be. Whatever the session is doing is cancelled first.
+    pub fn suspend(&mut self) -> Result<oneshot::Receiver<Result<(), String>>, anyhow::Error> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.send(SessionCommand::Suspend(reply_tx))?;
+
+        Ok(reply_rx)
+    }
+
+    /// The game state as of the last request the session finished.
+    pub fn game_state(&self) -> GameState {
+        self.game_state_rx.borrow().clone()
+    }
+}
+
+struct SessionActor {
+    game_session: GameSession,
+    commands_rx: Receiver<(u64, SessionCommand)>,
+    game_state_tx: watch::Sender<GameState>,
+    cancellation: Cancellation,
+    superseded_before: Arc<AtomicU64>,
+    state_update_tx: Arc<Mutex<Sender<GameState>>>,
+    request_update_tx: Arc<Mutex<Sender<SessionRequestUpdate>>>,
+}
+
+impl SessionActor {
+    async fn run(mut self) {
+        while let Some((request_id, command)) = self.commands_rx.recv().await {
+            // A cancellation only applies to the requests that were queued when it was sent
+            self.cancellation.reset();
+
+            if request_id < self.superseded_before.load(Ordering::SeqCst) {
+                info!("Skipping cancelled session request {}.", request_id);
+                self.send_update(request_id, SessionRequestStatus::Cancelled, None)
+                    .await;
+                continue;
+            }
+
+            info!("Processing session request {}: {:?}", request_id, &command);
+
+            let suspending = matches!(command, SessionCommand::Suspend(_));
+
+            let result = self.process(command).await;
+            let stopping = suspending && result.is_ok();
+
+            match result {
+                Ok(game_state) => {
+                    self.game_state_tx.send_replace(game_state.clone());
+
+                    let state_update_tx = self.state_update_tx.lock().await;
+                    if let Err(e) = state_update_tx.send(game_state).await {
+                        error!("Error sending game state update: {:?}", e);
+                    }
+
+                    self.send_update(request_id, SessionRequestStatus::Completed, None)
+                        .await;
+                }
+                Err(e) => {
+                    error!("Unable to process session request {}:\n{:?}", request_id, e);
+                    self.send_update(
+                        request_id,
+                        SessionRequestStatus::Failed,
+                        Some(e.to_string()),
+                    )
+                    .await;
+                }
+            }
+
+            if stopping {
+                break;
+            }
+        }
+
+        // Anything queued after the session was suspended won't be processed
+        self.commands_rx.close();
+        while let Some((request_id, _)) = self.commands_rx.recv().await {
+            self.send_update(request_id, SessionRequestStatus::Cancelled, None)
+                .await;
+        }
+
+        info!(
+            "Stopped session {}.",
+            self.game_session.game_state.session_id()
+        );
+    }
+
+    async fn process(&mut self, command: SessionCommand) -> Result<GameState, anyhow::Error> {
+        let game_session = &mut self.game_session;
+
+        match command {
+            SessionCommand::Begin => game_session.begin().await,
+            SessionCommand::PlayerMessage(message) => {
+                game_session.receive_player_message(message).await
+            }
+            SessionCommand::TradeResponse(accepted) => {
+                game_session.receive_trade_response(accepted).await
+            }
+            SessionCommand::TradeCounterOffer {
+                to_player,
+                from_player,
+            } => {
+                game_session
+                    .receive_trade_counter_offer(to_player, from_player)
+                    .await
+            }
+            SessionCommand::EndCharacterInteraction => {
+                game_session.end_character_interaction().await
+            }
+            SessionCommand::Rewind(turn) => game_session.rewind(turn).await,
+            SessionCommand::Save => game_session.save(),
+            SessionCommand::Suspend(reply_tx) => {
+                let result = game_session.save();
+                let reply = result.as_ref().map(|_| ()).map_err(|e| e.to_string());
+                if reply_tx.send(reply).is_err() {
+                    error!("Session was suspended, but the reply was no longer wanted.");
+                }
+
+                result
+            }
+            SessionCommand::ExportGraph(reply_tx) => {
+                let graph = (game_session.state_graph(), game_session.state_history());
+                if reply_tx.send(graph).is_err() {
+                    error!("Session graph was requested, but no longer wanted.");
+                }
+
+                Ok(game_session.game_state.clone())
+            }
+        }
+    }
+
+    async fn send_update(
+        &self,
+        request_id: u64,
+        status: SessionRequestStatus,
+        message: Option<String>,
+    ) {
+        let update = SessionRequestUpdate::new(
+            self.game_session.game_state.session_id(),
+            request_id,
+            status,
+            message,
+        );
+
+        let request_update_tx = self.request_update_tx.lock().await;
+        if let Err(e) = request_update_tx.send(update).await {
+            error!("Error sending session request update: {:?}", e);
+        }
+    }
+}
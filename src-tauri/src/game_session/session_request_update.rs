@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// Sent to the UI when a queued request finishes, since the command that queued it has already
+/// returned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionRequestUpdate {
+    pub session_id: String,
+    pub request_id: u64,
+    pub status: SessionRequestStatus,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionRequestStatus {
+    Completed,
+    Failed,
+    /// The request was dropped from the queue because the player cancelled or replaced it.
+    Cancelled,
+}
+
+impl SessionRequestUpdate {
+    pub fn new(
+        session_id: String,
+        request_id: u64,
+        status: SessionRequestStatus,
+        message: Option<String>,
+    ) -> Self {
+        SessionRequestUpdate {
+            session_id,
+            request_id,
+            status,
+            message,
+        }
+    }
+}
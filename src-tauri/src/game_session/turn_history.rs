@@ -0,0 +1,118 @@
+use std::collections::VecDeque;
+
+use crate::game_state::GameState;
+
+/// The number of turns the player can rewind through.
+const MAX_SNAPSHOTS: usize = 50;
+
+/// Snapshots of the game state taken at the start of each of the player's turns, so the game can
+/// be rewound to before a bad response.
+#[derive(Debug, Default)]
+pub struct TurnHistory {
+    snapshots: VecDeque<GameState>,
+}
+
+impl TurnHistory {
+    /// Saves the game state as it is before the player's next entry. Any snapshots from the same
+    /// turn or later are replaced, since those turns have been rewound.
+    pub fn record(&mut self, game_state: &GameState) {
+        self.snapshots.retain(|s| s.turn < game_state.turn);
+        self.snapshots.push_back(game_state.clone());
+
+        if self.snapshots.len() > MAX_SNAPSHOTS {
+            self.snapshots.pop_front();
+        }
+    }
+
+    /// Gets the game state as it was at the end of `turn`. Returns `None` if the turn is too far
+    /// back or hasn't happened yet.
+    pub fn snapshot(&self, turn: u32) -> Option<GameState> {
+        self.snapshots.iter().find(|s| s.turn == turn).cloned()
+    }
+
+    /// Discards the snapshot for `turn` and every later one, once the game has been rewound to it.
+    pub fn truncate(&mut self, turn: u32) {
+        self.snapshots.retain(|s| s.turn < turn);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::game::Game;
+
+    use super::*;
+
+    fn game_state() -> GameState {
+        let game = serde_json::from_value::<Game>(json!({
+            "id": "test",
+            "name": "Test",
+            "summary": {
+                "name": "Test",
+                "description": "",
+                "art_style": "",
+                "art_theme": "",
+                "cover_art": "",
+                "summary": "",
+                "win_condition": ""
+            },
+            "cover_art": "",
+            "narrative": { "pages": [] },
+            "scenes": [],
+            "characters": [],
+            "items": []
+        }))
+        .unwrap();
+
+        GameState::new(&game, "assistant", "thread")
+    }
+
+    fn play_turn(history: &mut TurnHistory, game_state: &mut GameState, entry: &str) {
+        history.record(game_state);
+        game_state.add_player_message(entry);
+        game_state.add_narrator_message("Nothing happens.");
+    }
+
+    #[test]
+    fn rewinds_to_end_of_turn() {
+        let mut history = TurnHistory::default();
+        let mut game_state = game_state();
+
+        play_turn(&mut history, &mut game_state, "look around");
+        play_turn(&mut history, &mut game_state, "jump");
+        play_turn(&mut history, &mut game_state, "sing");
+
+        let rewound = history.snapshot(1).unwrap();
+        assert_eq!(rewound.turn, 1);
+        assert_eq!(rewound.transcript(..), "> look around\nNothing happens.");
+
+        // Taking a snapshot leaves the history alone until it is truncated
+        assert!(history.snapshot(2).is_some());
+        history.truncate(1);
+
+        // Later turns are gone, but earlier ones can still be rewound to
+        assert!(history.snapshot(2).is_none());
+        assert!(history.snapshot(1).is_none());
+        assert!(history.snapshot(0).is_some());
+    }
+
+    #[test]
+    fn replaying_a_turn_replaces_its_snapshot() {
+        let mut history = TurnHistory::default();
+        let mut game_state = game_state();
+
+        play_turn(&mut history, &mut game_state, "look around");
+        play_turn(&mut history, &mut game_state, "jump");
+
+        let mut game_state = history.snapshot(1).unwrap();
+        history.truncate(1);
+        play_turn(&mut history, &mut game_state, "sing");
+        play_turn(&mut history, &mut game_state, "dance");
+
+        assert_eq!(
+            history.snapshot(2).unwrap().transcript(..),
+            "> look around\nNothing happens.\n> sing\nNothing happens."
+        );
+    }
+}
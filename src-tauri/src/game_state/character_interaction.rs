@@ -5,6 +5,9 @@ use super::{
     character_message::CharacterMessage, character_trade::CharacterTrade,
 };
 
+/// A conversation between the player and one or more characters. Every character has their own
+/// assistant, and they all share one thread as the transcript. `character_name` and
+/// `assistant_id` belong to whichever character is currently speaking.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CharacterInteraction {
     pub character_name: String,
@@ -15,6 +18,21 @@ pub struct CharacterInteraction {
     pub closed: bool,
     pub messages: Vec<CharacterMessage>,
     pub trade: Option<CharacterTrade>,
+    #[serde(default)]
+    pub participants: Vec<CharacterParticipant>,
+    #[serde(default)]
+    pub pending_speakers: Vec<String>,
+    #[serde(default)]
+    pub summaries: Vec<String>,
+    /// The player's latest counter-offer, which the character agrees to by offering it back.
+    #[serde(default)]
+    pub counter_offer: Option<CharacterTrade>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CharacterParticipant {
+    pub character_name: String,
+    pub assistant_id: String,
 }
 
 impl CharacterInteraction {
@@ -36,17 +54,124 @@ impl CharacterInteraction {
         });
     }
 
-    pub fn propose_trade(&mut self, to_player: &str, from_player: &str) {
+    pub fn propose_trade(&mut self, to_player: Vec<String>, from_player: Vec<String>) {
         self.trade = Some(CharacterTrade {
-            to_player: Some(to_player.to_string()),
-            from_player: Some(from_player.to_string()),
+            character_name: self.character_name.clone(),
+            to_player,
+            from_player,
         });
     }
 
     pub fn propose_gift(&mut self, to_player: &str) {
-        self.trade = Some(CharacterTrade {
-            to_player: Some(to_player.to_string()),
-            from_player: None,
-        });
+        self.propose_trade(vec![to_player.to_string()], vec![]);
+    }
+
+    /// Whether the current speaker's offer is the player's latest counter-offer, meaning both
+    /// sides have agreed to it.
+    pub fn is_agreed(&self, to_player: &[String], from_player: &[String]) -> bool {
+        let offer = CharacterTrade {
+            character_name: self.character_name.clone(),
+            to_player: to_player.to_vec(),
+            from_player: from_player.to_vec(),
+        };
+
+        self.counter_offer
+            .as_ref()
+            .is_some_and(|counter_offer| counter_offer.matches(&offer))
+    }
+
+    pub fn is_group(&self) -> bool {
+        self.participants.len() > 1
+    }
+
+    pub fn participant_names(&self) -> Vec<String> {
+        match self.participants.is_empty() {
+            true => vec![self.character_name.clone()],
+            false => self
+                .participants
+                .iter()
+                .map(|p| p.character_name.clone())
+                .collect(),
+        }
+    }
+
+    pub fn assistant_ids(&self) -> Vec<String> {
+        match self.participants.is_empty() {
+            true => vec![self.assistant_id.clone()],
+            false => self
+                .participants
+                .iter()
+                .map(|p| p.assistant_id.clone())
+                .collect(),
+        }
+    }
+
+    /// Sets the characters that should speak next, in order.
+    pub fn queue_speakers(&mut self, speakers: Vec<String>) {
+        self.pending_speakers = speakers;
+    }
+
+    /// Makes the next queued character the current speaker, returning false once every queued
+    /// character has spoken.
+    pub fn next_speaker(&mut self) -> bool {
+        if self.pending_speakers.is_empty() {
+            return false;
+        }
+
+        let speaker = self.pending_speakers.remove(0);
+        if let Some(participant) = self
+            .participants
+            .iter()
+            .find(|p| p.character_name == speaker)
+        {
+            self.character_name = participant.character_name.clone();
+            self.assistant_id = participant.assistant_id.clone();
+        }
+
+        true
+    }
+
+    /// Records the current speaker's summary of the conversation, prefixed with their name when
+    /// several characters took part.
+    pub fn add_summary(&mut self, summary: &str) {
+        let summary = match self.is_group() {
+            true => format!("{}: {}", self.character_name, summary),
+            false => summary.to_string(),
+        };
+
+        self.summaries.push(summary);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn speakers_take_turns() {
+        let mut interaction = CharacterInteraction::builder()
+            .add_participant("Arin", "assistant_1")
+            .add_participant("Maela", "assistant_2")
+            .thread_id("thread")
+            .initiating_run_id("run")
+            .initiating_tool_call_id("tool_call")
+            .build()
+            .unwrap();
+
+        assert!(interaction.is_group());
+        assert_eq!(interaction.character_name, "Arin");
+
+        interaction.queue_speakers(vec![String::from("Maela"), String::from("Arin")]);
+
+        assert!(interaction.next_speaker());
+        assert_eq!(interaction.character_name, "Maela");
+        assert_eq!(interaction.assistant_id, "assistant_2");
+
+        interaction.propose_gift("Temple Map");
+        assert_eq!(interaction.trade.as_ref().unwrap().character_name, "Maela");
+
+        assert!(interaction.next_speaker());
+        assert_eq!(interaction.character_name, "Arin");
+        assert!(!interaction.next_speaker());
     }
 }
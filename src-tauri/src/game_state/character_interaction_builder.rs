@@ -1,10 +1,9 @@
 use anyhow::anyhow;
 
-use super::character_interaction::CharacterInteraction;
+use super::character_interaction::{CharacterInteraction, CharacterParticipant};
 
 pub struct CharacterInteractionBuilder {
-    pub character_name: Option<String>,
-    pub assistant_id: Option<String>,
+    pub participants: Vec<CharacterParticipant>,
     pub thread_id: Option<String>,
     pub initiating_run_id: Option<String>,
     pub initiating_tool_call_id: Option<String>,
@@ -13,21 +12,19 @@ pub struct CharacterInteractionBuilder {
 impl CharacterInteractionBuilder {
     pub fn new() -> Self {
         CharacterInteractionBuilder {
-            character_name: None,
-            assistant_id: None,
+            participants: Vec::new(),
             thread_id: None,
             initiating_run_id: None,
             initiating_tool_call_id: None,
         }
     }
 
-    pub fn character_name(mut self, character_name: &str) -> Self {
-        self.character_name = Some(character_name.to_string());
-        self
-    }
-
-    pub fn assistant_id(mut self, assistant_id: &str) -> Self {
-        self.assistant_id = Some(assistant_id.to_string());
+    /// Adds a character to the conversation. The first character added speaks first.
+    pub fn add_participant(mut self, character_name: &str, assistant_id: &str) -> Self {
+        self.participants.push(CharacterParticipant {
+            character_name: character_name.to_string(),
+            assistant_id: assistant_id.to_string(),
+        });
         self
     }
 
@@ -47,13 +44,13 @@ impl CharacterInteractionBuilder {
     }
 
     pub fn build(self) -> Result<CharacterInteraction, anyhow::Error> {
+        let first_speaker = self.participants.first().cloned().ok_or(anyhow!(
+            "Cannot start character interaction without any characters."
+        ))?;
+
         Ok(CharacterInteraction {
-            character_name: self.character_name.ok_or(anyhow!(
-                "Cannot start character interaction without character id."
-            ))?,
-            assistant_id: self.assistant_id.ok_or(anyhow!(
-                "Cannot start character interaction without assistant id."
-            ))?,
+            character_name: first_speaker.character_name,
+            assistant_id: first_speaker.assistant_id,
             thread_id: self.thread_id.ok_or(anyhow!(
                 "Cannot start character interaction without thread id."
             ))?,
@@ -66,6 +63,10 @@ impl CharacterInteractionBuilder {
             messages: Vec::new(),
             trade: None,
             closed: false,
+            participants: self.participants,
+            pending_speakers: Vec::new(),
+            summaries: Vec::new(),
+            counter_offer: None,
         })
     }
 }
@@ -1,9 +1,16 @@
 use serde::{Deserialize, Serialize};
 
+use super::disposition::Disposition;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CharacterSaveData {
     pub previous_conversations: Vec<String>,
     pub character_inventory: Vec<String>,
+    #[serde(default)]
+    pub disposition: Disposition,
+    /// Whether the player has talked to the character, so what they know of them can be recalled.
+    #[serde(default)]
+    pub met: bool,
 }
 
 impl CharacterSaveData {
@@ -11,6 +18,8 @@ impl CharacterSaveData {
         CharacterSaveData {
             previous_conversations: vec![],
             character_inventory: initial_inventory,
+            disposition: Disposition::default(),
+            met: false,
         }
     }
 }
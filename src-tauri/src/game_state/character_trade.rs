@@ -1,7 +1,48 @@
 use serde::{Deserialize, Serialize};
 
+/// An offer to exchange items between the player and a character. A trade where the player gives
+/// nothing is a gift.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CharacterTrade {
-    pub to_player: Option<String>,
-    pub from_player: Option<String>,
+    /// The character offering the trade, which matters when several are in the conversation.
+    pub character_name: String,
+    pub to_player: Vec<String>,
+    pub from_player: Vec<String>,
+}
+
+impl CharacterTrade {
+    /// Whether two offers exchange the same items, ignoring order and case.
+    pub fn matches(&self, other: &CharacterTrade) -> bool {
+        let normalize = |items: &[String]| {
+            let mut items = items.iter().map(|i| i.to_lowercase()).collect::<Vec<_>>();
+            items.sort();
+            items
+        };
+
+        self.character_name == other.character_name
+            && normalize(&self.to_player) == normalize(&other.to_player)
+            && normalize(&self.from_player) == normalize(&other.from_player)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn trade(to_player: &[&str], from_player: &[&str]) -> CharacterTrade {
+        CharacterTrade {
+            character_name: String::from("Arin"),
+            to_player: to_player.iter().map(|i| i.to_string()).collect(),
+            from_player: from_player.iter().map(|i| i.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn matching_ignores_order_and_case() {
+        let offer = trade(&["Temple Map", "Rope"], &["Gold Coin"]);
+
+        assert!(offer.matches(&trade(&["rope", "temple map"], &["Gold Coin"])));
+        assert!(!offer.matches(&trade(&["Temple Map"], &["Gold Coin"])));
+        assert!(!offer.matches(&trade(&["Temple Map", "Rope"], &[])));
+    }
 }
@@ -0,0 +1,43 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::random::Random;
+
+/// Rolls dice for skill checks. Each roll is derived from the game's seed and the number of rolls
+/// made so far, so a saved game always rolls the same results when replayed.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DiceRoller {
+    seed: u32,
+    rolls: u32,
+}
+
+impl DiceRoller {
+    pub fn new(seed: u32) -> Self {
+        Self { seed, rolls: 0 }
+    }
+
+    pub fn roll_d20(&mut self) -> i32 {
+        let seed = Random::derive_seed(self.seed, &format!("roll {}", self.rolls));
+        self.rolls += 1;
+
+        Random::from_seed(seed).gen_range(1..=20)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rolls_are_replayable() {
+        let mut first = DiceRoller::new(42);
+        let mut second = DiceRoller::new(42);
+
+        let first_rolls = (0..50).map(|_| first.roll_d20()).collect::<Vec<_>>();
+        let second_rolls = (0..50).map(|_| second.roll_d20()).collect::<Vec<_>>();
+
+        assert_eq!(first_rolls, second_rolls);
+        assert!(first_rolls.iter().all(|roll| (1..=20).contains(roll)));
+        assert!(first_rolls.iter().any(|roll| *roll != first_rolls[0]));
+    }
+}
@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+
+const MIN_VALUE: i32 = -100;
+const MAX_VALUE: i32 = 100;
+
+/// The most a single exchange with the player can change any one value by.
+const MAX_CHANGE: i32 = 20;
+
+const TRADE_MIN_TRUST: i32 = -25;
+const GIFT_MIN_AFFECTION: i32 = 10;
+const GIFT_MIN_FEAR: i32 = 50;
+
+/// How a character feels about the player. Each value is between -100 and 100, and starts at
+/// zero for a character the player hasn't met.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Disposition {
+    pub trust: i32,
+    pub affection: i32,
+    pub fear: i32,
+    /// The character's own words on why they feel the way they do.
+    #[serde(default)]
+    pub notes: String,
+    /// The values from before the latest change, which only matter for the rest of the run the
+    /// change was made in, so they aren't saved.
+    #[serde(skip)]
+    prior: Option<PriorDisposition>,
+}
+
+/// A character's disposition from before a change made in the run `run_id`.
+#[derive(Debug, Clone, PartialEq)]
+struct PriorDisposition {
+    run_id: String,
+    trust: i32,
+    affection: i32,
+    fear: i32,
+}
+
+/// A change to a character's disposition requested by the character actor after an exchange.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DispositionChange {
+    #[serde(default)]
+    pub trust: i32,
+    #[serde(default)]
+    pub affection: i32,
+    #[serde(default)]
+    pub fear: i32,
+    #[serde(default)]
+    pub reason: String,
+}
+
+impl Disposition {
+    /// Applies a change made by the character in the run `run_id`, limiting how far a single
+    /// exchange can move each value. Only one change can be made for each message from the player,
+    /// so the character can't talk itself round over several calls.
+    pub fn apply(&mut self, change: &DispositionChange, run_id: &str) -> Result<(), String> {
+        if self.prior.as_ref().is_some_and(|p| p.run_id == run_id) {
+            return Err(String::from(
+                "You have already updated how you feel about the player since their last message.",
+            ));
+        }
+
+        self.prior = Some(PriorDisposition {
+            run_id: run_id.to_string(),
+            trust: self.trust,
+            affection: self.affection,
+            fear: self.fear,
+        });
+
+        let adjust = |value: i32, amount: i32| {
+            (value + amount.clamp(-MAX_CHANGE, MAX_CHANGE)).clamp(MIN_VALUE, MAX_VALUE)
+        };
+
+        self.trust = adjust(self.trust, change.trust);
+        self.affection = adjust(self.affection, change.affection);
+        self.fear = adjust(self.fear, change.fear);
+
+        if !change.reason.is_empty() {
+            self.notes = change.reason.clone();
+        }
+
+        Ok(())
+    }
+
+    /// Describes the disposition in words, e.g. "trusting, fond and unafraid".
+    pub fn attitude(&self) -> String {
+        let trust = match self.trust {
+            t if t <= -50 => "suspicious",
+            t if t < -10 => "wary",
+            t if t < 25 => "uncertain",
+            t if t < 60 => "trusting",
+            _ => "loyal",
+        };
+        let affection = match self.affection {
+            a if a <= -50 => "hostile",
+            a if a < -10 => "cold",
+            a if a < 25 => "indifferent",
+            a if a < 60 => "fond",
+            _ => "devoted",
+        };
+        let fear = match self.fear {
+            f if f < 25 => "unafraid",
+            f if f < GIFT_MIN_FEAR => "nervous",
+            _ => "afraid",
+        };
+
+        format!("{}, {} and {}", trust, affection, fear)
+    }
+
+    /// Checks whether the character is willing to trade with the player in the run `run_id`,
+    /// returning the reason as an error if not.
+    pub fn check_trade(&self, run_id: &str) -> Result<(), String> {
+        let disposition = self.before_run(run_id);
+
+        match disposition.trust >= TRADE_MIN_TRUST {
+            true => Ok(()),
+            false => Err(format!(
+                "You don't trust the player enough to trade with them. You are {} towards them.",
+                disposition.attitude()
+            )),
+        }
+    }
+
+    /// Checks whether the character is willing to give the player something for nothing in the
+    /// run `run_id`, which they'll do if they like the player or are afraid of them.
+    pub fn check_gift(&self, run_id: &str) -> Result<(), String> {
+        let disposition = self.before_run(run_id);
+
+        match disposition.affection >= GIFT_MIN_AFFECTION || disposition.fear >= GIFT_MIN_FEAR {
+            true => Ok(()),
+            false => Err(format!(
+                "You don't feel strongly enough about the player to give them anything for free. You are {} towards them. Offer a trade instead.",
+                disposition.attitude()
+            )),
+        }
+    }
+
+    /// The disposition as it was before any change made in the run `run_id`, so that a change
+    /// doesn't count towards trades and gifts in the same run.
+    fn before_run(&self, run_id: &str) -> Disposition {
+        match &self.prior {
+            Some(prior) if prior.run_id == run_id => Disposition {
+                trust: prior.trust,
+                affection: prior.affection,
+                fear: prior.fear,
+                notes: self.notes.clone(),
+                prior: None,
+            },
+            _ => self.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn change(trust: i32, affection: i32, fear: i32) -> DispositionChange {
+        DispositionChange {
+            trust,
+            affection,
+            fear,
+            reason: String::new(),
+        }
+    }
+
+    #[test]
+    fn changes_are_limited() {
+        let mut disposition = Disposition::default();
+
+        disposition.apply(&change(50, -5, 0), "run_0").unwrap();
+        assert_eq!(disposition.trust, 20);
+        assert_eq!(disposition.affection, -5);
+
+        for run in 1..=10 {
+            disposition
+                .apply(&change(20, 0, -20), &format!("run_{}", run))
+                .unwrap();
+        }
+        assert_eq!(disposition.trust, 100);
+        assert_eq!(disposition.fear, -100);
+        assert_eq!(disposition.attitude(), "loyal, indifferent and unafraid");
+    }
+
+    #[test]
+    fn thresholds_gate_trades_and_gifts() {
+        let mut disposition = Disposition::default();
+
+        assert!(disposition.check_trade("run_0").is_ok());
+        assert!(disposition.check_gift("run_0").is_err());
+
+        disposition.apply(&change(-20, 10, 0), "run_1").unwrap();
+        disposition.apply(&change(-20, 0, 0), "run_2").unwrap();
+        assert!(disposition.check_trade("run_3").is_err());
+        assert!(disposition.check_gift("run_3").is_ok());
+
+        let mut frightened = Disposition::default();
+        for run in 0..3 {
+            frightened
+                .apply(&change(0, -20, 20), &format!("run_{}", run))
+                .unwrap();
+        }
+        assert!(frightened.check_gift("run_3").is_ok());
+    }
+
+    #[test]
+    fn changes_only_count_after_the_run_they_were_made_in() {
+        let mut disposition = Disposition::default();
+
+        disposition.apply(&change(0, 20, 0), "run_1").unwrap();
+        assert!(disposition.apply(&change(0, 20, 0), "run_1").is_err());
+        assert_eq!(disposition.affection, 20);
+
+        assert!(disposition.check_gift("run_1").is_err());
+        assert!(disposition.check_gift("run_2").is_ok());
+    }
+}
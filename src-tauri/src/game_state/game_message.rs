@@ -0,0 +1,99 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// An entry in the game's message log, which the UI renders as the narrative window.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GameMessage {
+    /// The number of player entries made before this message, so everything the game does in
+    /// response to one entry shares a turn.
+    pub turn: u32,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub kind: GameMessageKind,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GameMessageKind {
+    Narration {
+        text: String,
+    },
+    Player {
+        text: String,
+    },
+    SystemEvent {
+        text: String,
+    },
+    Error {
+        text: String,
+        /// Whether the failure was likely temporary, so the player's entry can be sent again.
+        #[serde(default)]
+        can_retry: bool,
+    },
+    SceneTransition {
+        scene_name: String,
+    },
+    ItemDelta {
+        item: String,
+        gained: bool,
+    },
+    /// A question about the game answered without the narrator, like checking the inventory.
+    /// It's only shown to the player, so it never reaches the narrator's transcript.
+    Meta {
+        query: String,
+        answer: String,
+    },
+}
+
+impl GameMessage {
+    pub fn new(turn: u32, kind: GameMessageKind) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+
+        GameMessage {
+            turn,
+            timestamp,
+            kind,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn serializes_kind_alongside_fields() {
+        let message = GameMessage {
+            turn: 3,
+            timestamp: 1000,
+            kind: GameMessageKind::ItemDelta {
+                item: String::from("Rusty Key"),
+                gained: true,
+            },
+        };
+
+        let value = serde_json::to_value(&message).unwrap();
+
+        assert_eq!(
+            value,
+            json!({
+                "turn": 3,
+                "timestamp": 1000,
+                "kind": "item_delta",
+                "item": "Rusty Key",
+                "gained": true,
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<GameMessage>(value).unwrap(),
+            message
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::{Objective, ObjectiveTrigger, Quest};
+
+/// Tracks the player's progress through the game's quest. This is sent to the UI as part of the
+/// game state, so it can be shown to the player as their journal.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Journal {
+    pub entries: Vec<JournalEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JournalEntry {
+    #[serde(flatten)]
+    pub objective: Objective,
+    pub status: ObjectiveStatus,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectiveStatus {
+    Locked,
+    Active,
+    Completed,
+}
+
+/// Something that happened in the game that may complete an objective.
+pub enum QuestEvent<'a> {
+    ItemObtained(&'a str),
+    SceneEntered(&'a str),
+    CharacterMet(&'a str),
+}
+
+impl Journal {
+    pub fn new(quest: Option<&Quest>) -> Self {
+        let entries = quest
+            .map(|quest| quest.objectives.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|objective| JournalEntry {
+                objective,
+                status: ObjectiveStatus::Locked,
+            })
+            .collect();
+
+        let mut journal = Journal { entries };
+        journal.unlock_available();
+        journal
+    }
+
+    /// Completes an active objective, returning its title, or the reason as an error if it can't be
+    /// completed.
+    pub fn complete(&mut self, objective_id: &str) -> Result<String, String> {
+        let entry = self
+            .entries
+            .iter()
+            .find(|e| e.objective.id == objective_id)
+            .ok_or(format!("There is no objective with id '{}'.", objective_id))?;
+
+        match entry.status {
+            ObjectiveStatus::Completed => Err(format!(
+                "The objective '{}' has already been completed.",
+                objective_id
+            )),
+            ObjectiveStatus::Locked => Err(format!(
+                "The objective '{}' is not available yet. The player must first complete: {}.",
+                objective_id,
+                self.incomplete_titles(&entry.objective.prerequisites)
+                    .join(", ")
+            )),
+            ObjectiveStatus::Active => {
+                let title = entry.objective.title.clone();
+                self.set_completed(objective_id);
+                Ok(title)
+            }
+        }
+    }
+
+    /// Completes any active objectives triggered by the event, returning their titles.
+    pub fn record(&mut self, event: QuestEvent) -> Vec<String> {
+        let triggered =
+            self.entries
+                .iter()
+                .filter(|e| e.status == ObjectiveStatus::Active)
+                .filter(|e| match (&e.objective.trigger, &event) {
+                    (ObjectiveTrigger::ObtainItem { item }, QuestEvent::ItemObtained(name))
+                    | (
+                        ObjectiveTrigger::ReachScene { scene: item },
+                        QuestEvent::SceneEntered(name),
+                    )
+                    | (
+                        ObjectiveTrigger::TalkTo { character: item },
+                        QuestEvent::CharacterMet(name),
+                    ) => item.eq_ignore_ascii_case(name),
+                    _ => false,
+                })
+                .map(|e| (e.objective.id.clone(), e.objective.title.clone()))
+                .collect::<Vec<_>>();
+
+        triggered
+            .into_iter()
+            .map(|(id, title)| {
+                self.set_completed(&id);
+                title
+            })
+            .collect()
+    }
+
+    /// Whether every final objective has been completed. Games generated without a quest can
+    /// always be won.
+    pub fn is_won(&self) -> bool {
+        self.entries
+            .iter()
+            .filter(|e| e.objective.is_final)
+            .all(|e| e.status == ObjectiveStatus::Completed)
+    }
+
+    pub fn is_completed(&self, objective_id: &str) -> bool {
+        self.entries
+            .iter()
+            .any(|e| e.objective.id == objective_id && e.status == ObjectiveStatus::Completed)
+    }
+
+    /// The titles of the objectives the player can currently work towards.
+    pub fn active_titles(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter(|e| e.status == ObjectiveStatus::Active)
+            .map(|e| e.objective.title.clone())
+            .collect()
+    }
+
+    fn set_completed(&mut self, objective_id: &str) {
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|e| e.objective.id == objective_id)
+        {
+            entry.status = ObjectiveStatus::Completed;
+        }
+
+        self.unlock_available();
+    }
+
+    fn unlock_available(&mut self) {
+        let completed = self
+            .entries
+            .iter()
+            .filter(|e| e.status == ObjectiveStatus::Completed)
+            .map(|e| e.objective.id.clone())
+            .collect::<Vec<_>>();
+
+        for entry in &mut self.entries {
+            if entry.status == ObjectiveStatus::Locked
+                && entry
+                    .objective
+                    .prerequisites
+                    .iter()
+                    .all(|p| completed.contains(p))
+            {
+                entry.status = ObjectiveStatus::Active;
+            }
+        }
+    }
+
+    fn incomplete_titles(&self, objective_ids: &[String]) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter(|e| objective_ids.contains(&e.objective.id))
+            .filter(|e| e.status != ObjectiveStatus::Completed)
+            .map(|e| e.objective.title.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    fn journal() -> Journal {
+        let quest = serde_json::from_value::<Quest>(json!({
+            "objectives": [
+                {
+                    "id": "find_map",
+                    "title": "Find the Map",
+                    "description": "",
+                    "trigger": { "type": "obtain_item", "item": "Temple Map" }
+                },
+                {
+                    "id": "enter_temple",
+                    "title": "Enter the Temple",
+                    "description": "",
+                    "prerequisites": ["find_map"],
+                    "trigger": { "type": "reach_scene", "scene": "Forgotten Corridors" }
+                },
+                {
+                    "id": "restore_crystal",
+                    "title": "Restore the Crystal",
+                    "description": "",
+                    "prerequisites": ["enter_temple"],
+                    "trigger": { "type": "narrator" },
+                    "is_final": true
+                }
+            ]
+        }))
+        .unwrap();
+
+        Journal::new(Some(&quest))
+    }
+
+    #[test]
+    fn events_complete_active_objectives() {
+        let mut journal = journal();
+
+        assert!(journal
+            .record(QuestEvent::SceneEntered("Forgotten Corridors"))
+            .is_empty());
+        assert_eq!(
+            journal.record(QuestEvent::ItemObtained("temple map")),
+            vec!["Find the Map"]
+        );
+        assert_eq!(
+            journal.record(QuestEvent::SceneEntered("Forgotten Corridors")),
+            vec!["Enter the Temple"]
+        );
+        assert_eq!(journal.active_titles(), vec!["Restore the Crystal"]);
+    }
+
+    #[test]
+    fn locked_objective_cannot_be_completed() {
+        let mut journal = journal();
+
+        assert!(journal.complete("restore_crystal").is_err());
+        assert!(journal.complete("unknown").is_err());
+        assert!(!journal.is_won());
+
+        journal.complete("find_map").unwrap();
+        journal.complete("enter_temple").unwrap();
+        journal.complete("restore_crystal").unwrap();
+
+        assert!(journal.is_won());
+        assert!(journal.complete("restore_crystal").is_err());
+    }
+
+    #[test]
+    fn game_without_quest_can_be_won() {
+        assert!(Journal::new(None).is_won());
+    }
+}
@@ -0,0 +1,299 @@
+use serde_json::{json, Value};
+
+use crate::{game::Game, utils::fuzzy_match::FuzzyMatch};
+
+use super::GameState;
+
+/// Looks up what the game already knows about its items, characters and world, so the narrator
+/// can describe them from the generated canon instead of making details up.
+pub struct Lore {}
+
+impl Lore {
+    /// Details of an item the player is carrying or can see in the current scene.
+    pub fn examine_item(game: &Game, game_state: &GameState, item_name: &str) -> Value {
+        let inventory = game_state.get_player_inventory();
+        let visible = [inventory.clone(), Lore::scene_items(game_state)].concat();
+
+        let name = match FuzzyMatch::find(item_name, &visible) {
+            Some(name) => name,
+            None => {
+                return json!({
+                    "error": format!(
+                        "The player can't see an item called {}. Visible items: {}.",
+                        item_name,
+                        visible.join(", ")
+                    )
+                })
+            }
+        };
+
+        let carried = inventory.contains(name);
+
+        match game
+            .items
+            .iter()
+            .find(|i| i.name.eq_ignore_ascii_case(name))
+        {
+            Some(item) => json!({
+                "item": item.name,
+                "description": item.description,
+                "carried_by_player": carried,
+                "usable": item.properties.usable,
+                "effect": item.properties.effect,
+            }),
+            None => json!({
+                "item": name,
+                "carried_by_player": carried,
+                "description": "No details are recorded for this item. Describe it briefly, in keeping with its name.",
+            }),
+        }
+    }
+
+    /// How a character in the current scene looks and feels about the player, along with what
+    /// they have talked about before.
+    pub fn examine_character(game: &Game, game_state: &GameState, character_name: &str) -> Value {
+        let present = game_state
+            .current_scene_name
+            .as_ref()
+            .map(|scene| game_state.characters_in_scene(game, scene))
+            .unwrap_or_default();
+        let names = present.iter().map(|c| c.name.clone()).collect::<Vec<_>>();
+
+        let character = match FuzzyMatch::find(character_name, &names)
+            .and_then(|name| present.iter().find(|c| &c.name == name))
+        {
+            Some(character) => character,
+            None => {
+                return json!({
+                    "error": format!(
+                        "{} isn't here. Characters present: {}.",
+                        character_name,
+                        names.join(", ")
+                    )
+                })
+            }
+        };
+
+        let save_data = game_state.character_save_data.get(&character.name);
+
+        json!({
+            "character": character.name,
+            "description": character.short_description,
+            "appearance": character.physical_description,
+            "attitude_towards_player": save_data.map(|s| s.disposition.attitude()),
+            "previous_conversations": save_data.map(|s| s.previous_conversations.clone()),
+        })
+    }
+
+    /// The background of a scene the player has visited, a character they have met or an item
+    /// named by the topic. Any other topic is answered with what the player could know without
+    /// having been anywhere: the world's summary, the current scene's background and the story so
+    /// far.
+    pub fn recall(game: &Game, game_state: &GameState, topic: &str) -> Value {
+        let scene_names = game
+            .scenes
+            .iter()
+            .filter(|s| game_state.has_visited(&s.name))
+            .map(|s| s.name.clone())
+            .collect::<Vec<_>>();
+        if let Some(scene) = FuzzyMatch::find(topic, &scene_names)
+            .and_then(|name| game.scenes.iter().find(|s| &s.name == name))
+        {
+            return json!({
+                "scene": scene.name,
+                "description": scene.narrative,
+                "background": scene.metadata,
+            });
+        }
+
+        let character_names = game
+            .characters
+            .iter()
+            .filter(|c| game_state.has_met(&c.name))
+            .map(|c| c.name.clone())
+            .collect::<Vec<_>>();
+        if let Some(character) = FuzzyMatch::find(topic, &character_names)
+            .and_then(|name| game.characters.iter().find(|c| &c.name == name))
+        {
+            return json!({
+                "character": character.name,
+                "description": character.short_description,
+                "backstory": character.backstory,
+            });
+        }
+
+        let item_names = game
+            .items
+            .iter()
+            .map(|i| i.name.clone())
+            .collect::<Vec<_>>();
+        if let Some(item) = FuzzyMatch::find(topic, &item_names)
+            .and_then(|name| game.items.iter().find(|i| &i.name == name))
+        {
+            return json!({
+                "item": item.name,
+                "description": item.description,
+            });
+        }
+
+        let current_scene = game_state
+            .current_scene_name
+            .as_ref()
+            .and_then(|name| game.scenes.iter().find(|s| &s.name == name));
+
+        json!({
+            "topic": topic,
+            "world": game.summary.summary,
+            "current_scene_background": current_scene.map(|s| s.metadata.clone()),
+            "story_so_far": game_state.narrator_memory.story_so_far,
+        })
+    }
+
+    fn scene_items(game_state: &GameState) -> Vec<String> {
+        game_state
+            .current_scene_name
+            .as_ref()
+            .map(|scene| game_state.get_scene_inventory(scene))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::game_state::character_interaction::CharacterInteraction;
+
+    use super::*;
+
+    fn game() -> Game {
+        serde_json::from_value::<Game>(json!({
+            "id": "test",
+            "name": "Test",
+            "summary": {
+                "name": "Test",
+                "description": "",
+                "art_style": "",
+                "art_theme": "",
+                "cover_art": "",
+                "summary": "A valley where the old gods sleep.",
+                "win_condition": ""
+            },
+            "cover_art": "",
+            "narrative": { "pages": [] },
+            "scenes": [
+                {
+                    "name": "Village",
+                    "narrative": "Smoke rises from a dozen chimneys.",
+                    "metadata": "Founded by pilgrims fleeing the flood.",
+                    "image": "",
+                    "characters": ["Arin: a blacksmith"],
+                    "items": ["Brass Lantern"]
+                },
+                {
+                    "name": "Temple",
+                    "narrative": "",
+                    "metadata": "",
+                    "image": "",
+                    "characters": ["Elder Maela: the village elder"],
+                    "items": []
+                }
+            ],
+            "characters": [
+                {
+                    "name": "Arin",
+                    "short_description": "a blacksmith",
+                    "physical_description": "Broad shoulders and soot-black hands.",
+                    "speech": "",
+                    "personality": "",
+                    "backstory": "Forged the temple's bells.",
+                    "thoughts": "",
+                    "inventory": [],
+                    "image": ""
+                }
+            ],
+            "items": [
+                {
+                    "name": "Brass Lantern",
+                    "description": "A lantern engraved with river spirits.",
+                    "image": ""
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn game_state() -> GameState {
+        let mut game_state = GameState::new(&game(), "assistant", "thread");
+        game_state.new_scene("Village");
+        game_state
+    }
+
+    #[test]
+    fn examines_visible_items_and_characters() {
+        let game = game();
+        let game_state = game_state();
+
+        let item = Lore::examine_item(&game, &game_state, "the lantern");
+        assert_eq!(item["item"], "Brass Lantern");
+        assert_eq!(
+            item["description"],
+            "A lantern engraved with river spirits."
+        );
+        assert_eq!(item["carried_by_player"], false);
+
+        let character = Lore::examine_character(&game, &game_state, "arin");
+        assert_eq!(
+            character["appearance"],
+            "Broad shoulders and soot-black hands."
+        );
+
+        assert!(Lore::examine_item(&game, &game_state, "Silver Key")["error"].is_string());
+        assert!(Lore::examine_character(&game, &game_state, "Maela")["error"].is_string());
+    }
+
+    #[test]
+    fn recalls_lore_by_topic() {
+        let game = game();
+        let mut game_state = game_state();
+        game_state.new_scene("Temple");
+        game_state.character_interact(
+            CharacterInteraction::builder()
+                .add_participant("Arin", "assistant")
+                .thread_id("thread")
+                .initiating_run_id("run")
+                .initiating_tool_call_id("tool_call")
+                .build()
+                .unwrap(),
+        );
+
+        let village = Lore::recall(&game, &game_state, "village");
+        assert_eq!(
+            village["background"],
+            "Founded by pilgrims fleeing the flood."
+        );
+
+        let arin = Lore::recall(&game, &game_state, "Arin");
+        assert_eq!(arin["backstory"], "Forged the temple's bells.");
+
+        let gods = Lore::recall(&game, &game_state, "the old gods");
+        assert_eq!(gods["world"], "A valley where the old gods sleep.");
+    }
+
+    #[test]
+    fn recalls_only_public_lore_of_unvisited_scenes_and_strangers() {
+        let game = game();
+        let game_state = game_state();
+
+        let temple = Lore::recall(&game, &game_state, "temple");
+        assert!(temple["scene"].is_null());
+        assert_eq!(temple["world"], "A valley where the old gods sleep.");
+        assert_eq!(
+            temple["current_scene_background"],
+            "Founded by pilgrims fleeing the flood."
+        );
+
+        let arin = Lore::recall(&game, &game_state, "Arin");
+        assert!(arin["backstory"].is_null());
+    }
+}
@@ -4,24 +4,70 @@ pub mod character_message;
 pub mod character_profile;
 pub mod character_save_data;
 pub mod character_trade;
+pub mod dice_roller;
+pub mod disposition;
+pub mod game_message;
+pub mod journal;
+pub mod lore;
+pub mod narrator_context;
+pub mod narrator_memory;
+pub mod player_intent;
+pub mod player_stats;
+pub mod skill_check;
+pub mod turn_policy;
 
-use std::collections::HashMap;
+use std::{collections::HashMap, ops::RangeBounds};
 
 use serde::{Deserialize, Serialize};
 
-use crate::game::Game;
+use crate::game::{Character, CharacterMovement, Game};
 
-use self::{character_interaction::CharacterInteraction, character_save_data::CharacterSaveData};
+use self::{
+    character_interaction::CharacterInteraction,
+    character_save_data::CharacterSaveData,
+    dice_roller::DiceRoller,
+    disposition::Disposition,
+    game_message::{GameMessage, GameMessageKind},
+    journal::{Journal, QuestEvent},
+    narrator_memory::NarratorMemory,
+    player_intent::IntentHint,
+    player_stats::PlayerStats,
+};
+
+/// The save slot a game is played in when the player doesn't pick one.
+pub const DEFAULT_SAVE_SLOT: &str = "default";
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct GameState {
     pub game_id: String,
+    #[serde(default = "GameState::default_save_slot")]
+    pub save_slot: String,
     pub current_scene_name: Option<String>,
-    pub messages: Vec<String>,
+    pub messages: Vec<GameMessage>,
+    #[serde(default)]
+    pub turn: u32,
     pub inventory: Vec<String>,
     pub character_interaction: Option<CharacterInteraction>,
     pub character_save_data: HashMap<String, CharacterSaveData>,
     pub scene_inventories: HashMap<String, Vec<String>>,
+    pub unlocked_exits: HashMap<String, Vec<String>>,
+    /// Exits the player has unlocked by using an item, which are kept apart from the passages
+    /// characters have granted so that an exit with both locks needs both.
+    #[serde(default)]
+    pub item_unlocked_exits: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub character_locations: HashMap<String, String>,
+    #[serde(default)]
+    pub journal: Journal,
+    #[serde(default)]
+    pub stats: PlayerStats,
+    #[serde(default)]
+    pub dice: DiceRoller,
+    #[serde(default)]
+    pub narrator_memory: NarratorMemory,
+    /// What the player's current entry was read as, which is given to the narrator as a hint.
+    #[serde(skip)]
+    pub player_intent: Option<IntentHint>,
     pub assistant_id: String,
     pub thread_id: String,
     pub end_game: Option<String>,
@@ -43,34 +89,341 @@ impl GameState {
             .map(|s| (s.name.clone(), s.items.clone()))
             .collect::<HashMap<String, Vec<String>>>();
 
+        let character_locations = game
+            .characters
+            .iter()
+            .filter_map(|c| {
+                GameState::starting_scene(game, &c.name).map(|scene| (c.name.clone(), scene))
+            })
+            .collect::<HashMap<String, String>>();
+
         GameState {
             game_id,
+            save_slot: GameState::default_save_slot(),
             current_scene_name: None,
             messages: vec![],
+            turn: 0,
             inventory: vec![],
             character_interaction: None,
             character_save_data,
             scene_inventories,
+            unlocked_exits: HashMap::new(),
+            item_unlocked_exits: HashMap::new(),
+            character_locations,
+            journal: Journal::new(game.quest.as_ref()),
+            stats: PlayerStats::new(&game.attributes),
+            dice: DiceRoller::new(game.seed),
+            narrator_memory: NarratorMemory::default(),
+            player_intent: None,
             assistant_id: assistant_id.to_string(),
             thread_id: thread_id.to_string(),
             end_game: None,
         }
     }
 
+    /// Identifies the session playing this game and save slot, which is also where the game is
+    /// saved to.
+    pub fn session_id(&self) -> String {
+        GameState::session_id_for(&self.game_id, &self.save_slot)
+    }
+
+    pub fn session_id_for(game_id: &str, save_slot: &str) -> String {
+        format!("{}/{}", game_id, save_slot)
+    }
+
+    fn default_save_slot() -> String {
+        String::from(DEFAULT_SAVE_SLOT)
+    }
+
+    /// Records the player's entry, which starts a new turn.
     pub fn add_player_message(&mut self, message: &str) {
-        self.messages.push(format!("> {}", message));
+        self.turn += 1;
+        self.log(GameMessageKind::Player {
+            text: message.to_string(),
+        });
     }
 
     pub fn add_narrator_message(&mut self, message: &str) {
-        self.messages.push(message.to_string());
+        self.log(GameMessageKind::Narration {
+            text: message.to_string(),
+        });
+    }
+
+    /// Records something that happened in the game outside of the narration, like an objective
+    /// being completed.
+    pub fn add_system_message(&mut self, message: &str) {
+        self.log(GameMessageKind::SystemEvent {
+            text: message.to_string(),
+        });
+    }
+
+    /// Records a question the game answered for the player without the narrator. It doesn't take
+    /// up a turn, since nothing happens in the game.
+    pub fn add_meta_message(&mut self, query: &str, answer: &str) {
+        self.log(GameMessageKind::Meta {
+            query: query.to_string(),
+            answer: answer.to_string(),
+        });
+    }
+
+    /// Records that something went wrong processing the player's request, so they know to try
+    /// again.
+    pub fn add_error_message(&mut self, message: &str, can_retry: bool) {
+        self.log(GameMessageKind::Error {
+            text: message.to_string(),
+            can_retry,
+        });
+    }
+
+    fn log(&mut self, kind: GameMessageKind) {
+        self.messages.push(GameMessage::new(self.turn, kind));
+    }
+
+    fn log_item(&mut self, item: &str, gained: bool) {
+        self.log(GameMessageKind::ItemDelta {
+            item: item.to_string(),
+            gained,
+        });
+    }
+
+    /// The messages logged during `turns` as plain text, with the player's entries prefixed by `>`
+    /// and game events in brackets. Errors are left out, since they only concern the player.
+    pub fn transcript(&self, turns: impl RangeBounds<u32>) -> String {
+        self.messages
+            .iter()
+            .filter(|message| turns.contains(&message.turn))
+            .filter_map(|message| GameState::transcript_line(&message.kind))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The game events logged during `turns`, in brackets as they appear in the transcript.
+    pub fn events(&self, turns: impl RangeBounds<u32>) -> Vec<String> {
+        self.messages
+            .iter()
+            .filter(|message| turns.contains(&message.turn))
+            .filter(|message| {
+                !matches!(
+                    message.kind,
+                    GameMessageKind::Narration { .. } | GameMessageKind::Player { .. }
+                )
+            })
+            .filter_map(|message| GameState::transcript_line(&message.kind))
+            .collect()
+    }
+
+    fn transcript_line(kind: &GameMessageKind) -> Option<String> {
+        match kind {
+            GameMessageKind::Narration { text } => Some(text.clone()),
+            GameMessageKind::Player { text } => Some(format!("> {}", text)),
+            GameMessageKind::SystemEvent { text } => Some(format!("[{}]", text)),
+            GameMessageKind::Error { .. } | GameMessageKind::Meta { .. } => None,
+            GameMessageKind::SceneTransition { scene_name } => {
+                Some(format!("[Entered {}]", scene_name))
+            }
+            GameMessageKind::ItemDelta { item, gained } => Some(match gained {
+                true => format!("[Gained {}]", item),
+                false => format!("[Lost {}]", item),
+            }),
+        }
     }
 
     pub fn new_scene(&mut self, new_scene_name: &str) {
+        if self.current_scene_name.as_deref() != Some(new_scene_name) {
+            self.log(GameMessageKind::SceneTransition {
+                scene_name: new_scene_name.to_string(),
+            });
+        }
+
         self.current_scene_name = Some(new_scene_name.to_string());
     }
 
+    /// Whether the player has been to the scene at any point in the game.
+    pub fn has_visited(&self, scene_name: &str) -> bool {
+        self.messages.iter().any(|message| {
+            matches!(
+                &message.kind,
+                GameMessageKind::SceneTransition { scene_name: name } if name == scene_name
+            )
+        })
+    }
+
+    /// Whether the player has talked to the character at any point in the game.
+    pub fn has_met(&self, character_name: &str) -> bool {
+        self.character_save_data
+            .get(character_name)
+            .map(|s| s.met || !s.previous_conversations.is_empty())
+            .unwrap_or(false)
+    }
+
+    /// Records a quest event in the journal, logging and returning the titles of any objectives it
+    /// completes.
+    pub fn record_quest_event(&mut self, event: QuestEvent) -> Vec<String> {
+        let completed = self.journal.record(event);
+
+        for title in &completed {
+            self.add_system_message(&format!("Objective completed: {}", title));
+        }
+
+        completed
+    }
+
+    /// Records that a character has allowed the player through the exit from `scene_name` to
+    /// `to_scene_name`.
+    pub fn grant_passage(&mut self, scene_name: &str, to_scene_name: &str) {
+        let unlocked = self
+            .unlocked_exits
+            .entry(scene_name.to_string())
+            .or_insert(vec![]);
+
+        if !unlocked.iter().any(|s| s == to_scene_name) {
+            unlocked.push(to_scene_name.to_string());
+        }
+    }
+
+    pub fn has_passage(&self, scene_name: &str, to_scene_name: &str) -> bool {
+        self.unlocked_exits
+            .get(scene_name)
+            .map(|unlocked| unlocked.iter().any(|s| s == to_scene_name))
+            .unwrap_or(false)
+    }
+
+    /// Records that the player has unlocked the exit from `scene_name` to `to_scene_name` by using
+    /// an item.
+    pub fn unlock_exit(&mut self, scene_name: &str, to_scene_name: &str) {
+        let unlocked = self
+            .item_unlocked_exits
+            .entry(scene_name.to_string())
+            .or_insert(vec![]);
+
+        if !unlocked.iter().any(|s| s == to_scene_name) {
+            unlocked.push(to_scene_name.to_string());
+        }
+    }
+
+    pub fn is_exit_unlocked(&self, scene_name: &str, to_scene_name: &str) -> bool {
+        self.item_unlocked_exits
+            .get(scene_name)
+            .map(|unlocked| unlocked.iter().any(|s| s == to_scene_name))
+            .unwrap_or(false)
+    }
+
+    /// The scene a character is currently in. Characters on a schedule are in the scene of the
+    /// latest stop whose objective has been completed.
+    pub fn character_scene(&self, game: &Game, character_name: &str) -> Option<String> {
+        let character = game.characters.iter().find(|c| c.name == character_name)?;
+
+        if let CharacterMovement::Schedule { stops } = &character.movement {
+            if let Some(stop) = stops
+                .iter()
+                .rev()
+                .find(|s| self.journal.is_completed(&s.after_objective))
+            {
+                return Some(stop.scene.clone());
+            }
+        }
+
+        // Games saved before character locations were tracked keep everyone where they started
+        self.character_locations
+            .get(character_name)
+            .cloned()
+            .or_else(|| GameState::starting_scene(game, character_name))
+    }
+
+    pub fn characters_in_scene<'a>(&self, game: &'a Game, scene_name: &str) -> Vec<&'a Character> {
+        game.characters
+            .iter()
+            .filter(|c| self.character_scene(game, &c.name).as_deref() == Some(scene_name))
+            .collect()
+    }
+
+    /// Moves the characters following the player from the scene the player left into the scene
+    /// they entered, returning the names of the characters that moved.
+    pub fn move_followers(&mut self, game: &Game, from_scene: &str, to_scene: &str) -> Vec<String> {
+        let followers = game
+            .characters
+            .iter()
+            .filter(|c| c.movement == CharacterMovement::FollowsPlayer)
+            .filter(|c| self.character_scene(game, &c.name).as_deref() == Some(from_scene))
+            .map(|c| c.name.clone())
+            .collect::<Vec<_>>();
+
+        for follower in &followers {
+            self.character_locations
+                .insert(follower.clone(), to_scene.to_string());
+            self.add_system_message(&format!("{} follows you.", follower));
+        }
+
+        followers
+    }
+
+    fn starting_scene(game: &Game, character_name: &str) -> Option<String> {
+        game.scenes
+            .iter()
+            .find(|s| {
+                s.characters
+                    .iter()
+                    .any(|c| Character::name_from_summary(c) == character_name)
+            })
+            .map(|s| s.name.clone())
+    }
+
     pub fn add_item(&mut self, item_name: &str) {
         self.inventory.push(item_name.to_string());
+        self.log_item(item_name, true);
+    }
+
+    /// The items currently lying in a scene, which may differ from the scene's original items
+    /// once the player has taken or dropped anything there.
+    pub fn get_scene_inventory(&self, scene_name: &str) -> Vec<String> {
+        self.scene_inventories
+            .get(scene_name)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Moves an item from the current scene into the player's inventory, returning the item's name
+    /// as it is listed in the scene. Returns `None` if the item isn't in the current scene.
+    pub fn take_scene_item(&mut self, item_name: &str) -> Option<String> {
+        let scene_items = self
+            .scene_inventories
+            .get_mut(self.current_scene_name.as_ref()?)?;
+        let index = scene_items
+            .iter()
+            .position(|i| i.eq_ignore_ascii_case(item_name))?;
+
+        let item = scene_items.remove(index);
+        self.inventory.push(item.clone());
+        self.log_item(&item, true);
+
+        Some(item)
+    }
+
+    /// Moves an item from the player's inventory into the current scene, returning the reason as an
+    /// error if the player can't drop it.
+    pub fn drop_item(&mut self, item_name: &str) -> Result<String, String> {
+        let scene_name = self
+            .current_scene_name
+            .clone()
+            .ok_or(String::from("The player is not in a scene."))?;
+
+        let index = self
+            .inventory
+            .iter()
+            .position(|i| i.eq_ignore_ascii_case(item_name))
+            .ok_or(format!(
+                "Player does not have any {} in their inventory.",
+                item_name
+            ))?;
+
+        let item = self.inventory.remove(index);
+        self.scene_inventories
+            .entry(scene_name)
+            .or_default()
+            .push(item.clone());
+        self.log_item(&item, false);
+
+        Ok(item)
     }
 
     pub fn remove_item(&mut self, item_id: &str) -> Result<(), anyhow::Error> {
@@ -80,12 +433,22 @@ impl GameState {
             .position(|i| i == item_id)
             .ok_or_else(|| anyhow::anyhow!("Item '{}' not found in inventory.", item_id))?;
 
-        self.inventory.remove(index);
+        let item = self.inventory.remove(index);
+        self.log_item(&item, false);
 
         Ok(())
     }
 
     pub fn character_interact(&mut self, character_interaction: CharacterInteraction) {
+        for name in character_interaction.participant_names() {
+            self.add_system_message(&format!("{} joins the conversation.", name));
+
+            self.character_save_data
+                .entry(name)
+                .or_insert(CharacterSaveData::new(vec![]))
+                .met = true;
+        }
+
         self.character_interaction = Some(character_interaction);
     }
 
@@ -105,32 +468,85 @@ impl GameState {
             .clone()
     }
 
-    pub fn remove_character_item(&mut self, character_name: &str, item: &str) {
-        match self.character_save_data.entry(character_name.to_string()) {
-            std::collections::hash_map::Entry::Occupied(mut entry) => {
-                let csd = entry.get_mut();
-                csd.character_inventory.retain(|i| i.ne(item));
-            }
-            _ => {}
-        }
+    pub fn get_character_disposition(&mut self, character_name: &str) -> &mut Disposition {
+        &mut self
+            .character_save_data
+            .entry(character_name.to_string())
+            .or_insert(CharacterSaveData::new(vec![]))
+            .disposition
     }
 
-    pub fn add_player_item(&mut self, item: &str) {
-        self.inventory.push(item.to_string());
-    }
+    /// Checks that the character has every item in `to_player` and the player has every item in
+    /// `from_player`, returning the reason as an error if not. Returns the canonical names of the
+    /// items on each side, as they're listed in the inventories.
+    pub fn check_exchange(
+        &mut self,
+        character_name: &str,
+        to_player: &[String],
+        from_player: &[String],
+    ) -> Result<(Vec<String>, Vec<String>), String> {
+        let take_all = |inventory: &mut Vec<String>, items: &[String], owner: &str| {
+            items
+                .iter()
+                .map(|item| {
+                    let index = inventory
+                        .iter()
+                        .position(|i| i.eq_ignore_ascii_case(item))
+                        .ok_or(format!("{} does not have {}.", owner, item))?;
+                    Ok(inventory.remove(index))
+                })
+                .collect::<Result<Vec<String>, String>>()
+        };
+
+        let to_player = take_all(
+            &mut self.get_character_inventory(character_name),
+            to_player,
+            character_name,
+        )?;
+        let from_player = take_all(&mut self.get_player_inventory(), from_player, "The player")?;
 
-    pub fn remove_player_item(&mut self, item: &str) {
-        self.inventory.retain(|i| i.ne(item));
+        Ok((to_player, from_player))
     }
 
-    pub fn add_character_item(&mut self, character_name: &str, from_player_item: &str) {
-        match self.character_save_data.entry(character_name.to_string()) {
-            std::collections::hash_map::Entry::Occupied(mut entry) => {
-                let csd = entry.get_mut();
-                csd.character_inventory.push(from_player_item.to_string());
+    /// Swaps items between the player and a character. Every item is checked against both
+    /// inventories first, so either the whole exchange happens or none of it does.
+    pub fn exchange_items(
+        &mut self,
+        character_name: &str,
+        to_player: &[String],
+        from_player: &[String],
+    ) -> Result<(), String> {
+        let (to_player, from_player) =
+            self.check_exchange(character_name, to_player, from_player)?;
+
+        let character_inventory = &mut self
+            .character_save_data
+            .entry(character_name.to_string())
+            .or_insert(CharacterSaveData::new(vec![]))
+            .character_inventory;
+
+        for item in &to_player {
+            if let Some(index) = character_inventory.iter().position(|i| i == item) {
+                character_inventory.remove(index);
             }
-            _ => {}
         }
+        character_inventory.extend(from_player.iter().cloned());
+
+        for item in &from_player {
+            if let Some(index) = self.inventory.iter().position(|i| i == item) {
+                self.inventory.remove(index);
+            }
+        }
+        self.inventory.extend(to_player.iter().cloned());
+
+        for item in &from_player {
+            self.log_item(item, false);
+        }
+        for item in &to_player {
+            self.log_item(item, true);
+        }
+
+        Ok(())
     }
 
     pub fn save_previous_conversation(&mut self, character_name: &str, summary: &str) {
@@ -143,3 +559,294 @@ impl GameState {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{narrator_context::NarratorContext, *};
+
+    fn character(name: &str, movement: serde_json::Value) -> serde_json::Value {
+        json!({
+            "name": name,
+            "short_description": "",
+            "physical_description": "",
+            "speech": "",
+            "personality": "",
+            "backstory": "",
+            "thoughts": "",
+            "inventory": [],
+            "image": "",
+            "movement": movement
+        })
+    }
+
+    fn game() -> Game {
+        serde_json::from_value::<Game>(json!({
+            "id": "test",
+            "name": "Test",
+            "summary": {
+                "name": "Test",
+                "description": "",
+                "art_style": "",
+                "art_theme": "",
+                "cover_art": "",
+                "summary": "",
+                "win_condition": ""
+            },
+            "cover_art": "",
+            "narrative": { "pages": [] },
+            "scenes": [
+                {
+                    "name": "Village",
+                    "narrative": "",
+                    "metadata": "",
+                    "image": "",
+                    "characters": ["Pip: a curious fox", "Maela: the village elder"],
+                    "items": ["Old Map", "Lantern"]
+                },
+                {
+                    "name": "Temple",
+                    "narrative": "",
+                    "metadata": "",
+                    "image": "",
+                    "characters": [],
+                    "items": []
+                }
+            ],
+            "characters": [
+                character("Pip", json!({ "type": "follows_player" })),
+                character("Maela", json!({
+                    "type": "schedule",
+                    "stops": [{ "after_objective": "find_map", "scene": "Temple" }]
+                }))
+            ],
+            "items": [],
+            "quest": {
+                "objectives": [
+                    {
+                        "id": "find_map",
+                        "title": "Find the Map",
+                        "description": "",
+                        "trigger": { "type": "narrator" },
+                        "is_final": true
+                    }
+                ]
+            }
+        }))
+        .unwrap()
+    }
+
+    fn game_state() -> GameState {
+        let mut game_state = GameState::new(&game(), "assistant", "thread");
+        game_state.new_scene("Village");
+        game_state
+    }
+
+    #[test]
+    fn items_can_only_be_taken_once() {
+        let mut game_state = game_state();
+
+        assert_eq!(
+            game_state.take_scene_item("old map"),
+            Some(String::from("Old Map"))
+        );
+        assert_eq!(game_state.take_scene_item("Old Map"), None);
+        assert_eq!(game_state.inventory, vec!["Old Map"]);
+        assert_eq!(game_state.get_scene_inventory("Village"), vec!["Lantern"]);
+    }
+
+    #[test]
+    fn dropped_items_stay_in_the_scene() {
+        let mut game_state = game_state();
+
+        game_state.take_scene_item("Old Map").unwrap();
+        game_state.new_scene("Temple");
+
+        assert!(game_state.drop_item("Lantern").is_err());
+        assert_eq!(game_state.drop_item("old map").unwrap(), "Old Map");
+        assert!(game_state.inventory.is_empty());
+        assert_eq!(game_state.get_scene_inventory("Temple"), vec!["Old Map"]);
+
+        game_state.new_scene("Village");
+        assert_eq!(game_state.take_scene_item("Old Map"), None);
+    }
+
+    fn character_names(characters: Vec<&Character>) -> Vec<&str> {
+        characters.iter().map(|c| c.name.as_str()).collect()
+    }
+
+    #[test]
+    fn followers_travel_with_player() {
+        let game = game();
+        let mut game_state = game_state();
+
+        assert_eq!(
+            character_names(game_state.characters_in_scene(&game, "Village")),
+            vec!["Pip", "Maela"]
+        );
+
+        assert_eq!(
+            game_state.move_followers(&game, "Village", "Temple"),
+            vec!["Pip"]
+        );
+        assert_eq!(
+            character_names(game_state.characters_in_scene(&game, "Temple")),
+            vec!["Pip"]
+        );
+        assert!(game_state
+            .move_followers(&game, "Village", "Temple")
+            .is_empty());
+    }
+
+    #[test]
+    fn scheduled_characters_move_with_quest() {
+        let game = game();
+        let mut game_state = game_state();
+
+        assert_eq!(
+            game_state.character_scene(&game, "Maela"),
+            Some(String::from("Village"))
+        );
+
+        game_state.journal.complete("find_map").unwrap();
+
+        assert_eq!(
+            game_state.character_scene(&game, "Maela"),
+            Some(String::from("Temple"))
+        );
+    }
+
+    #[test]
+    fn exchanges_are_all_or_nothing() {
+        let mut game_state = game_state();
+        game_state.add_item("Gold Coin");
+        game_state.add_item("Gold Coin");
+        game_state.character_save_data.insert(
+            String::from("Pip"),
+            CharacterSaveData::new(vec![String::from("Rope")]),
+        );
+
+        let gold = |count| vec![String::from("gold coin"); count];
+
+        assert!(game_state
+            .exchange_items("Pip", &[String::from("Rope")], &gold(3))
+            .is_err());
+        assert!(game_state
+            .exchange_items(
+                "Pip",
+                &[String::from("Rope"), String::from("Rope")],
+                &gold(1)
+            )
+            .is_err());
+        assert_eq!(game_state.inventory, vec!["Gold Coin", "Gold Coin"]);
+
+        game_state
+            .exchange_items("Pip", &[String::from("rope")], &gold(2))
+            .unwrap();
+
+        assert_eq!(game_state.inventory, vec!["Rope"]);
+        assert_eq!(
+            game_state.get_character_inventory("Pip"),
+            vec!["Gold Coin", "Gold Coin"]
+        );
+    }
+
+    #[test]
+    fn narrator_context_reflects_live_state() {
+        let game = game();
+        let mut game_state = game_state();
+
+        game_state.add_player_message("take the map");
+        game_state.take_scene_item("Old Map");
+        game_state.add_player_message("take the lantern");
+        game_state.take_scene_item("Lantern");
+        game_state.add_player_message("rest");
+        game_state.add_narrator_message("You rest.");
+
+        let context = NarratorContext::new(&game_state, &game);
+
+        assert_eq!(context.scene.as_deref(), Some("Village"));
+        assert_eq!(context.characters, vec!["Pip", "Maela"]);
+        assert!(context.scene_items.is_empty());
+        assert_eq!(context.inventory, vec!["Old Map", "Lantern"]);
+        assert_eq!(context.objectives, vec!["Find the Map"]);
+        assert_eq!(context.recent_events, vec!["[Gained Lantern]"]);
+
+        let instructions = context.to_instructions().unwrap();
+        assert!(instructions.contains("Items in the scene: none"));
+        assert!(instructions.contains("Player inventory: Old Map, Lantern"));
+    }
+
+    #[test]
+    fn messages_are_logged_by_turn() {
+        let mut game_state = game_state();
+
+        game_state.add_player_message("take the map");
+        game_state.take_scene_item("Old Map");
+        game_state.add_narrator_message("You pick up the map.");
+        game_state.add_meta_message("i", "Old Map");
+        game_state.add_player_message("go to the temple");
+        game_state.new_scene("Temple");
+        game_state.new_scene("Temple");
+
+        let kinds = game_state
+            .messages
+            .iter()
+            .map(|m| (m.turn, m.kind.clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            kinds,
+            vec![
+                (
+                    0,
+                    GameMessageKind::SceneTransition {
+                        scene_name: String::from("Village")
+                    }
+                ),
+                (
+                    1,
+                    GameMessageKind::Player {
+                        text: String::from("take the map")
+                    }
+                ),
+                (
+                    1,
+                    GameMessageKind::ItemDelta {
+                        item: String::from("Old Map"),
+                        gained: true
+                    }
+                ),
+                (
+                    1,
+                    GameMessageKind::Narration {
+                        text: String::from("You pick up the map.")
+                    }
+                ),
+                (
+                    1,
+                    GameMessageKind::Meta {
+                        query: String::from("i"),
+                        answer: String::from("Old Map"),
+                    }
+                ),
+                (
+                    2,
+                    GameMessageKind::Player {
+                        text: String::from("go to the temple")
+                    }
+                ),
+                (
+                    2,
+                    GameMessageKind::SceneTransition {
+                        scene_name: String::from("Temple")
+                    }
+                ),
+            ]
+        );
+
+        // Answers to questions about the game are kept from the narrator
+        assert!(!game_state.transcript(..).contains("> i"));
+    }
+}
@@ -0,0 +1,75 @@
+use anyhow::Context;
+
+use crate::{game::Game, prompt_builder::PromptBuilder};
+
+use super::{player_intent::IntentHint, GameState};
+
+/// How many turns back, including the current one, game events are recalled.
+const RECENT_EVENT_TURNS: u32 = 2;
+
+/// The live game state given to the narrator with each run. The narrator's thread only holds what
+/// was said, so without this it loses track of things like what the player is carrying.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NarratorContext {
+    pub scene: Option<String>,
+    pub characters: Vec<String>,
+    pub scene_items: Vec<String>,
+    pub inventory: Vec<String>,
+    pub stats: String,
+    pub objectives: Vec<String>,
+    pub recent_events: Vec<String>,
+    pub player_intent: Option<String>,
+}
+
+impl NarratorContext {
+    pub fn new(game_state: &GameState, game: &Game) -> Self {
+        let scene = game_state.current_scene_name.clone();
+
+        let (characters, scene_items) = match &scene {
+            Some(scene) => (
+                game_state
+                    .characters_in_scene(game, scene)
+                    .into_iter()
+                    .map(|c| c.name.clone())
+                    .collect(),
+                game_state.get_scene_inventory(scene),
+            ),
+            None => (Vec::new(), Vec::new()),
+        };
+
+        let first_recent_turn = (game_state.turn + 1).saturating_sub(RECENT_EVENT_TURNS);
+
+        NarratorContext {
+            scene,
+            characters,
+            scene_items,
+            inventory: game_state.get_player_inventory(),
+            stats: game_state.stats.to_instructions(),
+            objectives: game_state.journal.active_titles(),
+            recent_events: game_state.events(first_recent_turn..),
+            player_intent: game_state
+                .player_intent
+                .as_ref()
+                .map(IntentHint::to_instructions),
+        }
+    }
+
+    /// Renders the context as additional instructions for a narrator run.
+    pub fn to_instructions(&self) -> Result<String, anyhow::Error> {
+        PromptBuilder::new()
+            .add_prompt("narrator/run_context.txt")
+            .set_variable("scene", self.scene.clone().unwrap_or_default())
+            .set_variable("characters", self.characters.join(", "))
+            .set_variable("scene_items", self.scene_items.join(", "))
+            .set_variable("inventory", self.inventory.join(", "))
+            .set_variable("stats", &self.stats)
+            .set_variable("objectives", self.objectives.join(", "))
+            .set_variable("recent_events", self.recent_events.join("\n"))
+            .set_variable(
+                "player_intent",
+                self.player_intent.clone().unwrap_or_default(),
+            )
+            .build()
+            .context("Failed to build run context for game narrator.")
+    }
+}
@@ -0,0 +1,78 @@
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+/// How many turns the narrator's thread can run before older turns are condensed.
+const CONDENSE_AFTER_TURNS: u32 = 20;
+
+/// How many of the latest turns are carried over to a new thread word for word.
+const RECENT_TURNS_KEPT: u32 = 4;
+
+/// What the narrator remembers of turns that have been condensed out of its thread. Long games
+/// are moved onto a fresh thread now and then, seeded with a summary of the story so far and the
+/// latest turns, so the narrator's context doesn't grow without limit.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct NarratorMemory {
+    /// A summary of every turn before `summarized_turns`.
+    pub story_so_far: Option<String>,
+    /// The number of turns, counted from the start of the game, that the summary covers.
+    pub summarized_turns: u32,
+    /// The turn the narrator's current thread was started on.
+    pub thread_start_turn: u32,
+}
+
+impl NarratorMemory {
+    /// Whether the narrator's thread has run long enough to be condensed at `turn`.
+    pub fn is_due(&self, turn: u32) -> bool {
+        turn >= self.thread_start_turn + CONDENSE_AFTER_TURNS
+    }
+
+    /// The turns to fold into the summary at `turn`: everything not summarized yet, except for
+    /// the latest turns, which the new thread is given as they were.
+    pub fn turns_to_condense(&self, turn: u32) -> Range<u32> {
+        let end = (turn + 1).saturating_sub(RECENT_TURNS_KEPT);
+
+        self.summarized_turns..end.max(self.summarized_turns)
+    }
+
+    /// Records a summary covering every turn before `summarized_turns`, and that the narrator
+    /// continues on a new thread from `turn`.
+    pub fn condense(&mut self, story_so_far: String, summarized_turns: u32, turn: u32) {
+        self.story_so_far = Some(story_so_far);
+        self.summarized_turns = summarized_turns;
+        self.thread_start_turn = turn;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn condenses_all_but_the_latest_turns() {
+        let mut memory = NarratorMemory::default();
+
+        assert!(!memory.is_due(CONDENSE_AFTER_TURNS - 1));
+        assert!(memory.is_due(CONDENSE_AFTER_TURNS));
+
+        let turns = memory.turns_to_condense(20);
+        assert_eq!(turns, 0..17);
+
+        memory.condense(String::from("A long story."), turns.end, 20);
+
+        assert!(!memory.is_due(39));
+        assert!(memory.is_due(40));
+        assert_eq!(memory.turns_to_condense(40), 17..37);
+    }
+
+    #[test]
+    fn never_condenses_turns_twice() {
+        let memory = NarratorMemory {
+            story_so_far: Some(String::from("A short story.")),
+            summarized_turns: 10,
+            thread_start_turn: 10,
+        };
+
+        assert_eq!(memory.turns_to_condense(12), 10..10);
+    }
+}
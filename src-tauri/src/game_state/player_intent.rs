@@ -0,0 +1,333 @@
+use crate::{game::Game, utils::fuzzy_match::FuzzyMatch};
+
+use super::GameState;
+
+const INVENTORY: &[&str] = &[
+    "i",
+    "inv",
+    "inventory",
+    "items",
+    "check inventory",
+    "show inventory",
+];
+const OBJECTIVES: &[&str] = &["objectives", "quests", "quest", "quest log", "journal"];
+const STATS: &[&str] = &["stats", "status", "check stats"];
+const LOOK: &[&str] = &["l", "look", "look around", "where am i"];
+
+// Longer phrases come first, so "go to the temple" isn't read as going to "to temple"
+const GO: &[&str] = &[
+    "go to",
+    "walk to",
+    "head to",
+    "travel to",
+    "return to",
+    "go",
+    "enter",
+];
+const TALK: &[&str] = &[
+    "talk to",
+    "talk with",
+    "speak to",
+    "speak with",
+    "chat with",
+    "greet",
+];
+const EXAMINE: &[&str] = &["look at", "examine", "inspect", "x"];
+const TAKE: &[&str] = &["pick up", "take", "grab", "get"];
+const DROP: &[&str] = &["put down", "drop"];
+const USE: &[&str] = &["use"];
+
+/// What the player is asking for, as far as it can be worked out without the narrator.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlayerIntent {
+    /// A question about the game that is answered locally, without a run.
+    Meta(MetaCommand),
+    /// An action the narrator still responds to, with the names in it resolved.
+    Action(IntentHint),
+    /// Anything else, which is passed to the narrator as it is.
+    Unrecognized,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetaCommand {
+    Inventory,
+    Objectives,
+    Stats,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntentHint {
+    Look,
+    Go { scene: String },
+    Talk { character: String },
+    Examine { item: String },
+    Take { item: String },
+    Drop { item: String },
+    Use { item: String },
+}
+
+impl PlayerIntent {
+    /// Reads the player's entry as a common command. Names are matched against the game's scenes,
+    /// characters and items, and commands whose name can't be matched to exactly one of them are
+    /// left unrecognized.
+    pub fn parse(message: &str, game: &Game, game_state: &GameState) -> Self {
+        let text = normalize(message);
+
+        if INVENTORY.contains(&text.as_str()) {
+            return PlayerIntent::Meta(MetaCommand::Inventory);
+        }
+        if OBJECTIVES.contains(&text.as_str()) {
+            return PlayerIntent::Meta(MetaCommand::Objectives);
+        }
+        if STATS.contains(&text.as_str()) {
+            return PlayerIntent::Meta(MetaCommand::Stats);
+        }
+        if LOOK.contains(&text.as_str()) {
+            return PlayerIntent::Action(IntentHint::Look);
+        }
+
+        let scenes = game
+            .scenes
+            .iter()
+            .map(|s| s.name.clone())
+            .collect::<Vec<_>>();
+        let characters = game
+            .characters
+            .iter()
+            .map(|c| c.name.clone())
+            .collect::<Vec<_>>();
+        let inventory = game_state.get_player_inventory();
+        let scene_items = game_state
+            .current_scene_name
+            .as_ref()
+            .map(|scene| game_state.get_scene_inventory(scene))
+            .unwrap_or_default();
+        let all_items = [inventory.clone(), scene_items.clone()].concat();
+
+        let hint = if let Some(target) = strip_verb(&text, GO) {
+            FuzzyMatch::find_unique(target, &scenes)
+                .cloned()
+                .map(|scene| IntentHint::Go { scene })
+        } else if let Some(target) = strip_verb(&text, TALK) {
+            FuzzyMatch::find_unique(target, &characters)
+                .cloned()
+                .map(|character| IntentHint::Talk { character })
+        } else if let Some(target) = strip_verb(&text, EXAMINE) {
+            FuzzyMatch::find_unique(target, &all_items)
+                .cloned()
+                .map(|item| IntentHint::Examine { item })
+        } else if let Some(target) = strip_verb(&text, TAKE) {
+            FuzzyMatch::find_unique(target, &scene_items)
+                .cloned()
+                .map(|item| IntentHint::Take { item })
+        } else if let Some(target) = strip_verb(&text, DROP) {
+            FuzzyMatch::find_unique(target, &inventory)
+                .cloned()
+                .map(|item| IntentHint::Drop { item })
+        } else if let Some(target) = strip_verb(&text, USE) {
+            FuzzyMatch::find_unique(target, &inventory)
+                .cloned()
+                .map(|item| IntentHint::Use { item })
+        } else {
+            None
+        };
+
+        match hint {
+            Some(hint) => PlayerIntent::Action(hint),
+            None => PlayerIntent::Unrecognized,
+        }
+    }
+}
+
+impl MetaCommand {
+    /// The answer shown to the player.
+    pub fn answer(&self, game_state: &GameState) -> String {
+        let (label, values, none) = match self {
+            MetaCommand::Inventory => (
+                "You are carrying",
+                game_state.get_player_inventory().join(", "),
+                "You aren't carrying anything.",
+            ),
+            MetaCommand::Objectives => (
+                "Objectives",
+                game_state.journal.active_titles().join(", "),
+                "You have no objectives right now.",
+            ),
+            MetaCommand::Stats => (
+                "Stats",
+                game_state.stats.to_instructions(),
+                "You have no stats in this game.",
+            ),
+        };
+
+        match values.is_empty() {
+            true => String::from(none),
+            false => format!("{}: {}.", label, values),
+        }
+    }
+}
+
+impl IntentHint {
+    /// Describes the intent for the narrator's run instructions.
+    pub fn to_instructions(&self) -> String {
+        match self {
+            IntentHint::Look => String::from("look around the current scene"),
+            IntentHint::Go { scene } => format!("go to the {} scene", scene),
+            IntentHint::Talk { character } => format!("talk to {}", character),
+            IntentHint::Examine { item } => format!("examine the {}", item),
+            IntentHint::Take { item } => format!("take the {} from the scene", item),
+            IntentHint::Drop { item } => format!("drop the {} from their inventory", item),
+            IntentHint::Use { item } => format!("use the {} from their inventory", item),
+        }
+    }
+}
+
+/// Lowercases the text and strips punctuation and articles.
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty() && !["a", "an", "the"].contains(w))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The rest of the text after the first verb it starts with, if any.
+fn strip_verb<'a>(text: &'a str, verbs: &[&str]) -> Option<&'a str> {
+    verbs.iter().find_map(|verb| {
+        text.strip_prefix(verb)
+            .and_then(|rest| rest.strip_prefix(' '))
+            .filter(|rest| !rest.is_empty())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    fn game() -> Game {
+        let character = |name: &str| {
+            json!({
+                "name": name,
+                "short_description": "",
+                "physical_description": "",
+                "speech": "",
+                "personality": "",
+                "backstory": "",
+                "thoughts": "",
+                "inventory": [],
+                "image": ""
+            })
+        };
+
+        serde_json::from_value::<Game>(json!({
+            "id": "test",
+            "name": "Test",
+            "summary": {
+                "name": "Test",
+                "description": "",
+                "art_style": "",
+                "art_theme": "",
+                "cover_art": "",
+                "summary": "",
+                "win_condition": ""
+            },
+            "cover_art": "",
+            "narrative": { "pages": [] },
+            "scenes": [
+                {
+                    "name": "Village Square",
+                    "narrative": "",
+                    "metadata": "",
+                    "image": "",
+                    "characters": ["Arin: a blacksmith"],
+                    "items": ["Old Map", "Brass Lantern", "Iron Key", "Rusty Key"]
+                },
+                {
+                    "name": "Temple",
+                    "narrative": "",
+                    "metadata": "",
+                    "image": "",
+                    "characters": [],
+                    "items": []
+                }
+            ],
+            "characters": [character("Arin"), character("Elder Maela")],
+            "items": []
+        }))
+        .unwrap()
+    }
+
+    fn parse(message: &str) -> PlayerIntent {
+        let game = game();
+        let mut game_state = GameState::new(&game, "assistant", "thread");
+        game_state.new_scene("Village Square");
+        game_state.add_item("Rope");
+
+        PlayerIntent::parse(message, &game, &game_state)
+    }
+
+    fn action(hint: IntentHint) -> PlayerIntent {
+        PlayerIntent::Action(hint)
+    }
+
+    #[test]
+    fn answers_meta_commands() {
+        assert_eq!(
+            parse("Inventory"),
+            PlayerIntent::Meta(MetaCommand::Inventory)
+        );
+        assert_eq!(parse("i"), PlayerIntent::Meta(MetaCommand::Inventory));
+        assert_eq!(
+            parse("quest log"),
+            PlayerIntent::Meta(MetaCommand::Objectives)
+        );
+        assert_eq!(parse("look around."), action(IntentHint::Look));
+    }
+
+    #[test]
+    fn resolves_names_with_synonyms_and_typos() {
+        let temple = String::from("Temple");
+        let maela = String::from("Elder Maela");
+
+        assert_eq!(
+            parse("go to the temple"),
+            action(IntentHint::Go {
+                scene: temple.clone()
+            })
+        );
+        assert_eq!(
+            parse("head to the tmple"),
+            action(IntentHint::Go { scene: temple })
+        );
+        assert_eq!(
+            parse("talk to maela"),
+            action(IntentHint::Talk { character: maela })
+        );
+        assert_eq!(
+            parse("pick up the lantern"),
+            action(IntentHint::Take {
+                item: String::from("Brass Lantern")
+            })
+        );
+        assert_eq!(
+            parse("x rope"),
+            action(IntentHint::Examine {
+                item: String::from("Rope")
+            })
+        );
+    }
+
+    #[test]
+    fn leaves_unknown_or_ambiguous_names_to_the_narrator() {
+        assert_eq!(parse("go north"), PlayerIntent::Unrecognized);
+        assert_eq!(parse("take key"), PlayerIntent::Unrecognized);
+        assert_eq!(parse("drop the lantern"), PlayerIntent::Unrecognized);
+        assert_eq!(parse("take it"), PlayerIntent::Unrecognized);
+        assert_eq!(
+            parse("sing a song about the temple"),
+            PlayerIntent::Unrecognized
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::PlayerAttributes;
+
+/// The current value of each of the player's attributes. This is sent to the UI as part of the
+/// game state, so it can be shown to the player.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PlayerStats {
+    pub stats: Vec<PlayerStat>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PlayerStat {
+    pub name: String,
+    pub value: i32,
+    pub max_value: i32,
+    pub fatal_at_zero: bool,
+}
+
+impl PlayerStat {
+    /// The bonus or penalty this stat gives to skill checks, from -5 at zero to +5 at its max.
+    pub fn modifier(&self) -> i32 {
+        match self.max_value > 0 {
+            true => self.value * 10 / self.max_value - 5,
+            false => 0,
+        }
+    }
+}
+
+impl PlayerStats {
+    pub fn new(attributes: &PlayerAttributes) -> Self {
+        let stats = attributes
+            .attributes
+            .iter()
+            .map(|a| PlayerStat {
+                name: a.name.clone(),
+                value: a.starting_value,
+                max_value: a.max_value,
+                fatal_at_zero: a.fatal_at_zero,
+            })
+            .collect();
+
+        Self { stats }
+    }
+
+    pub fn get(&self, name: &str) -> Result<&PlayerStat, String> {
+        self.stats
+            .iter()
+            .find(|s| s.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| self.unknown_stat(name))
+    }
+
+    /// Adds `amount` to a stat, which is negative for damage, keeping it between zero and its max.
+    pub fn modify(&mut self, name: &str, amount: i32) -> Result<&PlayerStat, String> {
+        let unknown_stat = self.unknown_stat(name);
+
+        let stat = self
+            .stats
+            .iter_mut()
+            .find(|s| s.name.eq_ignore_ascii_case(name))
+            .ok_or(unknown_stat)?;
+
+        stat.value = (stat.value + amount).clamp(0, stat.max_value);
+
+        Ok(stat)
+    }
+
+    /// The first stat that has defeated the player by reaching zero, if any.
+    pub fn defeated_by(&self) -> Option<&PlayerStat> {
+        self.stats.iter().find(|s| s.fatal_at_zero && s.value <= 0)
+    }
+
+    /// Formats the stats to be included in the narrator's run instructions.
+    pub fn to_instructions(&self) -> String {
+        self.stats
+            .iter()
+            .map(|s| format!("{} {}/{}", s.name, s.value, s.max_value))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn unknown_stat(&self, name: &str) -> String {
+        format!(
+            "The player has no stat named '{}'. Available stats: {}.",
+            name,
+            self.stats
+                .iter()
+                .map(|s| s.name.clone())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn modify_clamps_and_detects_defeat() {
+        let mut stats = PlayerStats::new(&PlayerAttributes::default());
+
+        assert_eq!(stats.modify("health", -5).unwrap().value, 15);
+        assert_eq!(stats.modify("Health", 50).unwrap().value, 20);
+        assert!(stats.defeated_by().is_none());
+
+        assert_eq!(stats.modify("Stamina", -50).unwrap().value, 0);
+        assert!(stats.defeated_by().is_none());
+
+        assert_eq!(stats.modify("Health", -25).unwrap().value, 0);
+        assert_eq!(stats.defeated_by().unwrap().name, "Health");
+
+        assert!(stats.modify("Sanity", -1).is_err());
+        assert_eq!(stats.to_instructions(), "Health 0/20, Stamina 0/10");
+    }
+
+    #[test]
+    fn modifier_scales_with_max_value() {
+        let stat = |value, max_value| PlayerStat {
+            name: String::from("Lore"),
+            value,
+            max_value,
+            fatal_at_zero: false,
+        };
+
+        assert_eq!(stat(0, 20).modifier(), -5);
+        assert_eq!(stat(10, 20).modifier(), 0);
+        assert_eq!(stat(20, 20).modifier(), 5);
+        assert_eq!(stat(5, 10).modifier(), 0);
+    }
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+use super::player_stats::PlayerStat;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty {
+    Easy,
+    Moderate,
+    Hard,
+    VeryHard,
+}
+
+impl Difficulty {
+    /// The total the player needs to roll, after their modifier, to pass the check.
+    pub fn target(&self) -> i32 {
+        match self {
+            Difficulty::Easy => 8,
+            Difficulty::Moderate => 12,
+            Difficulty::Hard => 16,
+            Difficulty::VeryHard => 20,
+        }
+    }
+}
+
+/// The outcome of a d20 roll against one of the player's stats. A natural 20 always succeeds and
+/// a natural 1 always fails.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SkillCheck {
+    pub stat: String,
+    pub roll: i32,
+    pub modifier: i32,
+    pub total: i32,
+    pub target: i32,
+    pub success: bool,
+}
+
+impl SkillCheck {
+    pub fn resolve(stat: &PlayerStat, difficulty: Difficulty, roll: i32) -> Self {
+        let modifier = stat.modifier();
+        let total = roll + modifier;
+        let target = difficulty.target();
+
+        let success = match roll {
+            20 => true,
+            1 => false,
+            _ => total >= target,
+        };
+
+        Self {
+            stat: stat.name.clone(),
+            roll,
+            modifier,
+            total,
+            target,
+            success,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn stat(value: i32) -> PlayerStat {
+        PlayerStat {
+            name: String::from("Lore"),
+            value,
+            max_value: 20,
+            fatal_at_zero: false,
+        }
+    }
+
+    #[test]
+    fn modifier_is_added_to_roll() {
+        let check = SkillCheck::resolve(&stat(16), Difficulty::Hard, 13);
+
+        assert_eq!(check.modifier, 3);
+        assert_eq!(check.total, 16);
+        assert!(check.success);
+
+        assert!(!SkillCheck::resolve(&stat(4), Difficulty::Moderate, 14).success);
+    }
+
+    #[test]
+    fn natural_rolls_ignore_modifier() {
+        assert!(SkillCheck::resolve(&stat(0), Difficulty::VeryHard, 20).success);
+        assert!(!SkillCheck::resolve(&stat(20), Difficulty::Easy, 1).success);
+    }
+}
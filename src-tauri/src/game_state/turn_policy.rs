@@ -0,0 +1,93 @@
+/// Decides which characters in a conversation respond to the player, and in what order.
+pub struct TurnPolicy {}
+
+impl TurnPolicy {
+    /// Characters the player addresses by name respond, in the order they were named. If the
+    /// player doesn't name anyone, everyone responds, starting after whoever spoke last.
+    pub fn next_speakers(
+        participants: &[String],
+        message: &str,
+        last_speaker: &str,
+    ) -> Vec<String> {
+        if participants.len() <= 1 {
+            return participants.to_vec();
+        }
+
+        let words = message
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(String::from)
+            .collect::<Vec<_>>();
+
+        let mut addressed = participants
+            .iter()
+            .filter_map(|name| {
+                TurnPolicy::first_mention(&words, name).map(|position| (position, name.clone()))
+            })
+            .collect::<Vec<_>>();
+
+        if !addressed.is_empty() {
+            addressed.sort_by_key(|(position, _)| *position);
+            return addressed.into_iter().map(|(_, name)| name).collect();
+        }
+
+        let start = participants
+            .iter()
+            .position(|name| name == last_speaker)
+            .map(|index| index + 1)
+            .unwrap_or(0);
+
+        participants[start..]
+            .iter()
+            .chain(participants[..start].iter())
+            .cloned()
+            .collect()
+    }
+
+    /// The position of the first word in the message that matches any word of the character's
+    /// name, so "Elder Maela" is addressed by "Maela".
+    fn first_mention(words: &[String], name: &str) -> Option<usize> {
+        let name_words = name
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| w.len() > 2)
+            .map(String::from)
+            .collect::<Vec<_>>();
+
+        words.iter().position(|word| name_words.contains(word))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn participants() -> Vec<String> {
+        vec![
+            String::from("Arin"),
+            String::from("Elder Maela"),
+            String::from("Pip"),
+        ]
+    }
+
+    #[test]
+    fn addressed_characters_respond_in_order() {
+        assert_eq!(
+            TurnPolicy::next_speakers(&participants(), "Maela, what does Arin mean?", "Arin"),
+            vec!["Elder Maela", "Arin"]
+        );
+    }
+
+    #[test]
+    fn everyone_responds_in_rotation() {
+        assert_eq!(
+            TurnPolicy::next_speakers(&participants(), "What happened here?", "Elder Maela"),
+            vec!["Pip", "Arin", "Elder Maela"]
+        );
+        assert_eq!(
+            TurnPolicy::next_speakers(&participants()[..1], "Hello", "Arin"),
+            vec!["Arin"]
+        );
+    }
+}
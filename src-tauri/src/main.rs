@@ -1,9 +1,16 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+use crate::commands::cancel_request::cancel_request;
 use crate::commands::character_prompt::character_prompt;
+use crate::commands::export_session_graph::export_session_graph;
 use crate::commands::game_prompt::game_prompt;
+use crate::commands::list_generation_templates::list_generation_templates;
+use crate::commands::regenerate::regenerate;
+use crate::commands::rewind_turn::rewind_turn;
+use crate::commands::save_game::save_game;
 use crate::commands::setup::setup;
 use crate::commands::start_game::start_game;
+use crate::commands::suspend_game::suspend_game;
 use crate::{
     application_state::session_state::SessionState, commands::create_new_game::create_new_game,
 };
@@ -11,6 +18,7 @@ use crate::{
 use application_state::ApplicationState;
 use log::{error, info};
 use nosleep::{NoSleep, NoSleepType};
+use prompt_builder::PromptLibrary;
 use tokio::sync::{mpsc, Mutex};
 
 use tauri::Manager;
@@ -37,6 +45,9 @@ fn main() -> Result<(), anyhow::Error> {
     let (state_update_tx, mut state_update_rx) = mpsc::channel(1);
     let state_update_tx = Mutex::new(state_update_tx);
 
+    let (request_update_tx, mut request_update_rx) = mpsc::channel(1);
+    let request_update_tx = Mutex::new(request_update_tx);
+
     tauri::Builder::default()
         .invoke_handler(tauri::generate_handler![
             create_new_game,
@@ -44,17 +55,31 @@ fn main() -> Result<(), anyhow::Error> {
             start_game,
             game_prompt,
             character_prompt,
+            list_generation_templates,
+            regenerate,
+            rewind_turn,
+            export_session_graph,
+            cancel_request,
+            save_game,
+            suspend_game,
         ])
         .setup(|app| {
             Logger::setup(app);
 
+            info!("Validating prompt files.");
+            PromptLibrary::init(&app.path_resolver());
+            if let Err(e) = PromptLibrary::validate() {
+                error!("{:?}", e);
+                return Err(e.into());
+            }
+
             info!("Initializing application state.");
             let application_state = ApplicationState::new(updates_tx);
             let application_state = Mutex::new(application_state);
             app.manage(application_state);
 
             info!("Initializing session state.");
-            let session_state = SessionState::new(state_update_tx);
+            let session_state = SessionState::new(state_update_tx, request_update_tx);
             let session_state = Mutex::new(session_state);
             app.manage(session_state);
 
@@ -82,6 +107,18 @@ fn main() -> Result<(), anyhow::Error> {
                 }
             });
 
+            info!("Initializing request update event emitter.");
+            let app_handle = app.handle();
+            tauri::async_runtime::spawn(async move {
+                loop {
+                    if let Some(update) = request_update_rx.recv().await {
+                        if let Err(e) = app_handle.emit_all("request", update) {
+                            error!("Failed to emit update to UI:\n{:?}", e);
+                        }
+                    }
+                }
+            });
+
             info!("App initialization complete.");
             Ok(())
         })
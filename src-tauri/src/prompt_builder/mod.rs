@@ -1,46 +1,225 @@
-use std::fs;
+use std::path::{Path, PathBuf};
 
+use anyhow::{anyhow, bail, Context};
+
+mod prompt_input;
+mod prompt_library;
+mod prompt_template;
+
+pub use prompt_input::PromptInput;
+pub use prompt_library::PromptLibrary;
+pub use prompt_template::{PromptTemplate, PromptVariables};
+
+enum PromptPart {
+    Text(String),
+    Prompt(String),
+    Example(&'static str, String),
+}
+
+/// Builds a system message from prompt files in the `PromptLibrary`. Prompt files are rendered as
+/// templates with the variables set on the builder, while example files are included as-is.
 pub struct PromptBuilder {
-    prompt: String,
+    parts: Vec<PromptPart>,
+    variables: PromptVariables,
+    overrides_dir: Option<PathBuf>,
 }
 
 impl PromptBuilder {
     pub fn new() -> Self {
         Self {
-            prompt: String::new(),
+            parts: Vec::new(),
+            variables: PromptVariables::new(),
+            overrides_dir: None,
         }
     }
 
+    /// Sets a directory that mirrors the layout of the `prompts` directory. Any prompt or example
+    /// file found there will be used in place of the default file.
+    pub fn overrides_dir(&mut self, overrides_dir: impl AsRef<Path>) -> &mut Self {
+        self.overrides_dir = Some(overrides_dir.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn set_variable(&mut self, name: &str, value: impl Into<String>) -> &mut Self {
+        self.variables.set(name, value);
+        self
+    }
+
     pub fn add_plain_text(&mut self, prompt: &str) -> &mut Self {
-        self.prompt += prompt;
-        self.prompt += "\n";
+        self.parts.push(PromptPart::Text(prompt.to_string()));
         self
     }
 
-    pub fn add_prompt(&mut self, path: &str) -> &mut Self {
-        self.prompt +=
-            &fs::read_to_string(path).expect("Something went wrong reading the prompt file.");
-        self.prompt += "\n";
+    pub fn add_prompt(&mut self, name: &str) -> &mut Self {
+        self.parts.push(PromptPart::Prompt(name.to_string()));
         self
     }
 
-    pub fn add_example_input(&mut self, path: &str) -> &mut Self {
-        self.prompt += "\nExample Input:\n";
-        self.prompt +=
-            &fs::read_to_string(path).expect("Something went wrong reading the example file.");
-        self.prompt += "\n";
+    pub fn add_example_input(&mut self, name: &str) -> &mut Self {
+        self.parts
+            .push(PromptPart::Example("Example Input", name.to_string()));
         self
     }
 
-    pub fn add_example_output(&mut self, path: &str) -> &mut Self {
-        self.prompt += "\nExample Output:\n";
-        self.prompt +=
-            &fs::read_to_string(path).expect("Something went wrong reading the example file.");
-        self.prompt += "\n";
+    pub fn add_example_output(&mut self, name: &str) -> &mut Self {
+        self.parts
+            .push(PromptPart::Example("Example Output", name.to_string()));
         self
     }
 
-    pub fn build(&self) -> String {
-        self.prompt.clone()
+    pub fn build(&self) -> Result<String, anyhow::Error> {
+        self.check_variables()?;
+
+        let mut prompt = String::new();
+
+        for part in &self.parts {
+            match part {
+                PromptPart::Text(text) => {
+                    prompt += text;
+                }
+                PromptPart::Prompt(name) => {
+                    let template = PromptTemplate::parse(&self.read(name)?)
+                        .with_context(|| format!("Unable to parse prompt '{}'.", name))?;
+                    prompt += &template
+                        .render(&self.variables, &|include| self.read(include))
+                        .with_context(|| format!("Unable to render prompt '{}'.", name))?;
+                }
+                PromptPart::Example(label, name) => {
+                    prompt += &format!("\n{}:\n", label);
+                    prompt += &self.read(name)?;
+                }
+            }
+            prompt += "\n";
+        }
+
+        Ok(prompt)
+    }
+
+    /// Checks that the variables set on the builder are exactly the ones the `PromptLibrary` lists
+    /// for its prompts, which are the ones the prompt files are validated against at startup.
+    fn check_variables(&self) -> Result<(), anyhow::Error> {
+        let mut bound = Vec::new();
+
+        for part in &self.parts {
+            if let PromptPart::Prompt(name) = part {
+                let variables = PromptLibrary::bound_variables(name)?;
+
+                let unset = variables
+                    .iter()
+                    .filter(|variable| self.variables.get(variable).is_none())
+                    .copied()
+                    .collect::<Vec<_>>();
+
+                if !unset.is_empty() {
+                    bail!(
+                        "Prompt '{}' is missing variables: {}",
+                        name,
+                        unset.join(", ")
+                    );
+                }
+
+                bound.extend_from_slice(variables);
+            }
+        }
+
+        let unlisted = self
+            .variables
+            .names()
+            .filter(|variable| !bound.contains(&variable.as_str()))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        match unlisted.is_empty() {
+            true => Ok(()),
+            false => Err(anyhow!(
+                "Variables are not listed for any prompt in the prompt library: {}",
+                unlisted.join(", ")
+            )),
+        }
+    }
+
+    fn read(&self, name: &str) -> Result<String, anyhow::Error> {
+        let override_path = self
+            .overrides_dir
+            .as_ref()
+            .map(|overrides_dir| overrides_dir.join(name))
+            .filter(|path| path.exists());
+
+        match override_path {
+            Some(path) => std::fs::read_to_string(&path)
+                .with_context(|| format!("Unable to read prompt file at {}.", path.display())),
+            None => PromptLibrary::read(name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn uses_override_when_present() {
+        let prompt = PromptBuilder::new()
+            .overrides_dir("./prompts/templates/short_adventure")
+            .add_prompt("scene_summary/main.txt")
+            .build()
+            .unwrap();
+
+        assert!(prompt.contains("4-6 scenes"));
+    }
+
+    #[test]
+    fn falls_back_to_default_prompt() {
+        let prompt = PromptBuilder::new()
+            .overrides_dir("./prompts/templates/short_adventure")
+            .add_prompt("scene_detail/main.txt")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            prompt,
+            PromptBuilder::new()
+                .add_prompt("scene_detail/main.txt")
+                .build()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn missing_prompt_is_an_error() {
+        assert!(PromptBuilder::new()
+            .add_prompt("missing/main.txt")
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn renders_prompt_variables() {
+        let prompt = PromptBuilder::new()
+            .add_prompt("narrator/main.txt")
+            .set_variable("game_summary", "A test game.")
+            .set_variable("scene_list", "Village, Forest")
+            .set_variable("quest", "")
+            .set_variable("attributes", "")
+            .build()
+            .unwrap();
+
+        assert!(prompt.contains("A test game."));
+        assert!(prompt.contains("Village, Forest"));
+    }
+
+    #[test]
+    fn variables_must_match_the_prompt_library() {
+        assert!(PromptBuilder::new()
+            .add_prompt("narrator/main.txt")
+            .set_variable("game_summary", "A test game.")
+            .build()
+            .is_err());
+
+        assert!(PromptBuilder::new()
+            .add_prompt("scene_detail/main.txt")
+            .set_variable("game_summary", "A test game.")
+            .build()
+            .is_err());
     }
 }
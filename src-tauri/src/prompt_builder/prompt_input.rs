@@ -0,0 +1,9 @@
+use anyhow::Context;
+use serde::Serialize;
+
+/// A structured input that is sent to the model as the user message.
+pub trait PromptInput: Serialize {
+    fn to_prompt(&self) -> Result<String, anyhow::Error> {
+        serde_json::to_string(self).context("Unable to serialize prompt input.")
+    }
+}
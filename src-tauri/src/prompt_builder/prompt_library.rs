@@ -0,0 +1,239 @@
+use std::{path::PathBuf, sync::OnceLock};
+
+use anyhow::{anyhow, Context};
+use log::info;
+use tauri::PathResolver;
+
+use super::prompt_template::PromptTemplate;
+
+const PROMPTS_DIR: &str = "prompts";
+const TEMPLATES_DIR: &str = "templates";
+
+static PROMPT_DIRS: OnceLock<Vec<PathBuf>> = OnceLock::new();
+
+/// Every prompt file the app loads, along with the variables that are bound when it is rendered.
+const PROMPT_FILES: &[(&str, &[&str])] = &[
+    ("summary/main.txt", &[]),
+    ("summary/example1.json", &[]),
+    ("summary/example2.json", &[]),
+    ("narrative/main.txt", &[]),
+    ("narrative/example1_input.json", &[]),
+    ("narrative/example1_output.json", &[]),
+    ("narrative/example2_input.json", &[]),
+    ("narrative/example2_output.json", &[]),
+    ("scene_summary/main.txt", &[]),
+    ("scene_summary/example1_input.json", &[]),
+    ("scene_summary/example1_output.json", &[]),
+    ("scene_summary/example2_input.json", &[]),
+    ("scene_summary/example2_output.json", &[]),
+    ("scene_detail/main.txt", &[]),
+    ("scene_detail/example1_input.json", &[]),
+    ("scene_detail/example1_output.json", &[]),
+    ("scene_detail/example2_input.json", &[]),
+    ("scene_detail/example2_output.json", &[]),
+    ("character_detail/main.txt", &[]),
+    ("character_detail/example1_input.json", &[]),
+    ("character_detail/example1_output.json", &[]),
+    ("character_detail/example2_input.json", &[]),
+    ("character_detail/example2_output.json", &[]),
+    ("item_detail/main.txt", &[]),
+    ("item_detail/example1_input.json", &[]),
+    ("item_detail/example1_output.json", &[]),
+    ("item_detail/example2_input.json", &[]),
+    ("item_detail/example2_output.json", &[]),
+    ("quest/main.txt", &[]),
+    ("quest/example1_input.json", &[]),
+    ("quest/example1_output.json", &[]),
+    ("character_movement/main.txt", &[]),
+    ("character_movement/example1_input.json", &[]),
+    ("character_movement/example1_output.json", &[]),
+    ("attributes/main.txt", &[]),
+    ("attributes/example1_input.json", &[]),
+    ("attributes/example1_output.json", &[]),
+    (
+        "narrator/main.txt",
+        &["game_summary", "scene_list", "quest", "attributes"],
+    ),
+    ("narrator/add_item_function.json", &[]),
+    ("narrator/remove_item_function.json", &[]),
+    ("narrator/drop_item_function.json", &[]),
+    ("narrator/use_item_function.json", &[]),
+    ("narrator/combine_items_function.json", &[]),
+    ("narrator/new_scene_function.json", &[]),
+    ("narrator/character_interact_function.json", &[]),
+    ("narrator/end_game_function.json", &[]),
+    ("narrator/complete_objective_function.json", &[]),
+    ("narrator/modify_stat_function.json", &[]),
+    ("narrator/skill_check_function.json", &[]),
+    ("narrator/examine_item_function.json", &[]),
+    ("narrator/examine_character_function.json", &[]),
+    ("narrator/recall_lore_function.json", &[]),
+    (
+        "narrator/rewind.txt",
+        &["story_so_far", "transcript", "scene", "inventory"],
+    ),
+    (
+        "narrator/continue.txt",
+        &["story_so_far", "transcript", "scene", "inventory"],
+    ),
+    ("narrator/summarize.txt", &[]),
+    (
+        "narrator/run_context.txt",
+        &[
+            "scene",
+            "characters",
+            "scene_items",
+            "inventory",
+            "stats",
+            "objectives",
+            "recent_events",
+            "player_intent",
+        ],
+    ),
+    (
+        "character_actor/main.txt",
+        &["character_profile", "additional_context"],
+    ),
+    ("character_actor/give_function.json", &[]),
+    ("character_actor/trade_function.json", &[]),
+    ("character_actor/grant_passage_function.json", &[]),
+    ("character_actor/update_disposition_function.json", &[]),
+];
+
+/// Locates prompt files by name, e.g. `narrator/main.txt`. Files in the `prompts` directory inside
+/// the app data directory take priority, so users can override any prompt. Otherwise the prompts
+/// bundled with the app in the resource directory are used.
+pub struct PromptLibrary {}
+
+impl PromptLibrary {
+    /// Sets the directories that prompts are loaded from. Until this is called, prompts are loaded
+    /// relative to the working directory, which is only correct during development and testing.
+    pub fn init(path_resolver: &PathResolver) {
+        let dirs = [
+            path_resolver
+                .app_local_data_dir()
+                .map(|dir| dir.join(PROMPTS_DIR)),
+            path_resolver.resolve_resource(PROMPTS_DIR),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        info!("Loading prompts from {:?}", &dirs);
+
+        let _ = PROMPT_DIRS.set(dirs);
+    }
+
+    /// Gets the path to the prompt file with the provided name.
+    pub fn resolve(name: &str) -> PathBuf {
+        let dirs = PROMPT_DIRS.get().map(Vec::as_slice).unwrap_or_default();
+
+        dirs.iter()
+            .map(|dir| dir.join(name))
+            .find(|path| path.exists())
+            .unwrap_or_else(|| PathBuf::from(".").join(PROMPTS_DIR).join(name))
+    }
+
+    pub fn read(name: &str) -> Result<String, anyhow::Error> {
+        let path = PromptLibrary::resolve(name);
+
+        std::fs::read_to_string(&path)
+            .with_context(|| format!("Unable to read prompt file at {}.", path.display()))
+    }
+
+    /// The variables that are bound when a prompt is rendered. Prompts that aren't listed can't
+    /// be validated at startup, so they're an error.
+    pub fn bound_variables(name: &str) -> Result<&'static [&'static str], anyhow::Error> {
+        PROMPT_FILES
+            .iter()
+            .find(|(file, _)| *file == name)
+            .map(|(_, bound)| *bound)
+            .ok_or(anyhow!(
+                "Prompt '{}' is not listed in the prompt library.",
+                name
+            ))
+    }
+
+    /// Checks that every prompt file exists, is a valid template, and only uses variables that are
+    /// bound when it is rendered. Prompt overrides in the built-in generation templates are
+    /// checked the same way. Returns every problem found.
+    pub fn validate() -> Result<(), anyhow::Error> {
+        let mut problems = PROMPT_FILES
+            .iter()
+            .filter_map(|(name, bound)| {
+                PromptLibrary::validate_file(name, bound, &PromptLibrary::read)
+                    .err()
+                    .map(|e| format!("{}: {:#}", name, e))
+            })
+            .collect::<Vec<_>>();
+
+        for template_dir in PromptLibrary::template_dirs() {
+            let read_override = |name: &str| {
+                let path = template_dir.join(name);
+                match path.exists() {
+                    true => std::fs::read_to_string(&path).with_context(|| {
+                        format!("Unable to read prompt file at {}.", path.display())
+                    }),
+                    false => PromptLibrary::read(name),
+                }
+            };
+
+            problems.extend(
+                PROMPT_FILES
+                    .iter()
+                    .filter(|(name, _)| template_dir.join(name).exists())
+                    .filter_map(|(name, bound)| {
+                        PromptLibrary::validate_file(name, bound, &read_override)
+                            .err()
+                            .map(|e| format!("{}: {:#}", template_dir.join(name).display(), e))
+                    }),
+            );
+        }
+
+        match problems.is_empty() {
+            true => Ok(()),
+            false => Err(anyhow!("Invalid prompt files:\n{}", problems.join("\n"))),
+        }
+    }
+
+    fn validate_file(
+        name: &str,
+        bound: &[&str],
+        load: &impl Fn(&str) -> Result<String, anyhow::Error>,
+    ) -> Result<(), anyhow::Error> {
+        let template = PromptTemplate::parse(&load(name)?)?;
+
+        let unbound = template
+            .required_variables(load)?
+            .into_iter()
+            .filter(|variable| !bound.contains(&variable.as_str()))
+            .collect::<Vec<_>>();
+
+        match unbound.is_empty() {
+            true => Ok(()),
+            false => Err(anyhow!("Unbound variables: {}", unbound.join(", "))),
+        }
+    }
+
+    /// The directories of the built-in generation templates, which can override prompt files.
+    fn template_dirs() -> Vec<PathBuf> {
+        match std::fs::read_dir(PromptLibrary::resolve(TEMPLATES_DIR)) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .collect(),
+            Err(_) => vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn all_prompts_are_valid() {
+        PromptLibrary::validate().unwrap();
+    }
+}
@@ -0,0 +1,361 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{anyhow, bail, Context};
+
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// A prompt file with placeholders that are filled in when it is rendered. Supported tags are:
+///
+/// - `{{name}}` inserts the value of a variable, which must be bound.
+/// - `{{#if name}}...{{else}}...{{/if}}` includes a section only if the variable is bound and not
+///   empty. The `{{else}}` section is optional.
+/// - `{{> path/to/file.txt}}` includes another prompt file, which is rendered with the same
+///   variables.
+pub struct PromptTemplate {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Variable(String),
+    If {
+        name: String,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    Include(String),
+}
+
+/// A block being parsed: the `{{#if}}` variable that opened it, the nodes parsed so far, and the
+/// `then` nodes if an `{{else}}` has been reached.
+type Block = (Option<String>, Vec<Node>, Option<Vec<Node>>);
+
+enum Tag {
+    Variable(String),
+    If(String),
+    Else,
+    EndIf,
+    Include(String),
+}
+
+/// The values bound to variables when rendering a prompt template.
+#[derive(Debug, Clone, Default)]
+pub struct PromptVariables {
+    values: HashMap<String, String>,
+}
+
+impl PromptVariables {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, name: &str, value: impl Into<String>) -> &mut Self {
+        self.values.insert(name.to_string(), value.into());
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&String> {
+        self.values.get(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &String> {
+        self.values.keys()
+    }
+}
+
+impl PromptTemplate {
+    pub fn parse(source: &str) -> Result<Self, anyhow::Error> {
+        let mut rest = source;
+        let mut stack: Vec<Block> = vec![(None, Vec::new(), None)];
+
+        while let Some(start) = rest.find("{{") {
+            let (text, after) = rest.split_at(start);
+            let end = after
+                .find("}}")
+                .ok_or(anyhow!("Unclosed tag in prompt template: {}", after))?;
+
+            push_text(&mut stack, text);
+
+            let tag = PromptTemplate::parse_tag(&after[2..end])?;
+            rest = &after[end + 2..];
+
+            match tag {
+                Tag::Variable(name) => push_node(&mut stack, Node::Variable(name)),
+                Tag::Include(path) => push_node(&mut stack, Node::Include(path)),
+                Tag::If(name) => stack.push((Some(name), Vec::new(), None)),
+                Tag::Else => match stack.last_mut() {
+                    Some((Some(_), then, otherwise @ None)) => {
+                        *otherwise = Some(std::mem::take(then));
+                    }
+                    _ => bail!("Unexpected {{{{else}}}} in prompt template."),
+                },
+                Tag::EndIf => {
+                    if stack.len() < 2 {
+                        bail!("Unexpected {{{{/if}}}} in prompt template.");
+                    }
+
+                    let (name, nodes, then) = stack.pop().unwrap();
+                    let node = match then {
+                        Some(then) => Node::If {
+                            name: name.unwrap(),
+                            then,
+                            otherwise: nodes,
+                        },
+                        None => Node::If {
+                            name: name.unwrap(),
+                            then: nodes,
+                            otherwise: Vec::new(),
+                        },
+                    };
+
+                    push_node(&mut stack, node);
+                }
+            }
+        }
+
+        push_text(&mut stack, rest);
+
+        if stack.len() > 1 {
+            bail!("Missing {{{{/if}}}} in prompt template.");
+        }
+
+        let (_, nodes, _) = stack.pop().unwrap();
+        Ok(Self { nodes })
+    }
+
+    /// Renders the template with the provided variables, using `load` to read any included files.
+    pub fn render(
+        &self,
+        variables: &PromptVariables,
+        load: &impl Fn(&str) -> Result<String, anyhow::Error>,
+    ) -> Result<String, anyhow::Error> {
+        let mut output = String::new();
+        PromptTemplate::render_nodes(&self.nodes, variables, load, &mut output, 0)?;
+        Ok(output)
+    }
+
+    /// Lists the variables that must be bound to render this template, including any used by
+    /// included files. Variables only used in `{{#if}}` tags are optional and are not listed.
+    pub fn required_variables(
+        &self,
+        load: &impl Fn(&str) -> Result<String, anyhow::Error>,
+    ) -> Result<HashSet<String>, anyhow::Error> {
+        let mut variables = HashSet::new();
+        PromptTemplate::collect_variables(&self.nodes, load, &mut variables, 0)?;
+        Ok(variables)
+    }
+
+    fn parse_tag(tag: &str) -> Result<Tag, anyhow::Error> {
+        let tag = tag.trim();
+
+        let parsed = if let Some(name) = tag.strip_prefix("#if ") {
+            Tag::If(name.trim().to_string())
+        } else if tag == "else" {
+            Tag::Else
+        } else if tag == "/if" {
+            Tag::EndIf
+        } else if let Some(path) = tag.strip_prefix('>') {
+            Tag::Include(path.trim().to_string())
+        } else {
+            Tag::Variable(tag.to_string())
+        };
+
+        match &parsed {
+            Tag::Variable(name) | Tag::If(name)
+                if name.is_empty()
+                    || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') =>
+            {
+                bail!("Invalid variable name in prompt template: '{}'", name)
+            }
+            Tag::Include(path) if path.is_empty() => bail!("Missing path in prompt include."),
+            _ => Ok(parsed),
+        }
+    }
+
+    fn render_nodes(
+        nodes: &[Node],
+        variables: &PromptVariables,
+        load: &impl Fn(&str) -> Result<String, anyhow::Error>,
+        output: &mut String,
+        depth: usize,
+    ) -> Result<(), anyhow::Error> {
+        for node in nodes {
+            match node {
+                Node::Text(text) => output.push_str(text),
+                Node::Variable(name) => output.push_str(
+                    variables
+                        .get(name)
+                        .ok_or(anyhow!("Prompt variable '{}' is not bound.", name))?,
+                ),
+                Node::If {
+                    name,
+                    then,
+                    otherwise,
+                } => {
+                    let nodes = match variables.get(name) {
+                        Some(value) if !value.trim().is_empty() => then,
+                        _ => otherwise,
+                    };
+                    PromptTemplate::render_nodes(nodes, variables, load, output, depth)?;
+                }
+                Node::Include(path) => {
+                    let template = PromptTemplate::load_include(path, load, depth)?;
+                    PromptTemplate::render_nodes(
+                        &template.nodes,
+                        variables,
+                        load,
+                        output,
+                        depth + 1,
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn collect_variables(
+        nodes: &[Node],
+        load: &impl Fn(&str) -> Result<String, anyhow::Error>,
+        variables: &mut HashSet<String>,
+        depth: usize,
+    ) -> Result<(), anyhow::Error> {
+        for node in nodes {
+            match node {
+                Node::Text(_) => {}
+                Node::Variable(name) => {
+                    variables.insert(name.clone());
+                }
+                Node::If {
+                    then, otherwise, ..
+                } => {
+                    PromptTemplate::collect_variables(then, load, variables, depth)?;
+                    PromptTemplate::collect_variables(otherwise, load, variables, depth)?;
+                }
+                Node::Include(path) => {
+                    let template = PromptTemplate::load_include(path, load, depth)?;
+                    PromptTemplate::collect_variables(&template.nodes, load, variables, depth + 1)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn load_include(
+        path: &str,
+        load: &impl Fn(&str) -> Result<String, anyhow::Error>,
+        depth: usize,
+    ) -> Result<PromptTemplate, anyhow::Error> {
+        if depth >= MAX_INCLUDE_DEPTH {
+            bail!("Prompt includes are nested too deeply at '{}'.", path);
+        }
+
+        let source = load(path).with_context(|| format!("Unable to include '{}'.", path))?;
+        PromptTemplate::parse(&source).with_context(|| format!("Unable to parse '{}'.", path))
+    }
+}
+
+fn push_node(stack: &mut [Block], node: Node) {
+    if let Some((_, nodes, _)) = stack.last_mut() {
+        nodes.push(node);
+    }
+}
+
+fn push_text(stack: &mut [Block], text: &str) {
+    if !text.is_empty() {
+        push_node(stack, Node::Text(text.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn no_includes(path: &str) -> Result<String, anyhow::Error> {
+        Err(anyhow!("No file at {}", path))
+    }
+
+    #[test]
+    fn renders_variables_and_conditionals() {
+        let template = PromptTemplate::parse(
+            "Hello {{ name }}.{{#if title}} Your title is {{title}}.{{else}} No title.{{/if}}",
+        )
+        .unwrap();
+
+        let mut variables = PromptVariables::new();
+        variables.set("name", "Arin");
+
+        assert_eq!(
+            template.render(&variables, &no_includes).unwrap(),
+            "Hello Arin. No title."
+        );
+
+        variables.set("title", "Guard");
+
+        assert_eq!(
+            template.render(&variables, &no_includes).unwrap(),
+            "Hello Arin. Your title is Guard."
+        );
+    }
+
+    #[test]
+    fn unbound_variable_is_an_error() {
+        let template = PromptTemplate::parse("Hello {{name}}").unwrap();
+
+        assert!(template
+            .render(&PromptVariables::new(), &no_includes)
+            .is_err());
+    }
+
+    #[test]
+    fn renders_includes() {
+        let load = |path: &str| match path {
+            "shared/rules.txt" => Ok(String::from("Rules for {{name}}.")),
+            _ => no_includes(path),
+        };
+
+        let template = PromptTemplate::parse("{{> shared/rules.txt}}\nEnd").unwrap();
+        let mut variables = PromptVariables::new();
+        variables.set("name", "the narrator");
+
+        assert_eq!(
+            template.render(&variables, &load).unwrap(),
+            "Rules for the narrator.\nEnd"
+        );
+        assert_eq!(
+            template.required_variables(&load).unwrap(),
+            HashSet::from([String::from("name")])
+        );
+    }
+
+    #[test]
+    fn recursive_include_is_an_error() {
+        let load = |_: &str| Ok(String::from("{{> loop.txt}}"));
+        let template = PromptTemplate::parse("{{> loop.txt}}").unwrap();
+
+        assert!(template.render(&PromptVariables::new(), &load).is_err());
+    }
+
+    #[test]
+    fn malformed_templates_are_rejected() {
+        assert!(PromptTemplate::parse("{{#if name}}unclosed").is_err());
+        assert!(PromptTemplate::parse("{{/if}}").is_err());
+        assert!(PromptTemplate::parse("{{name").is_err());
+        assert!(PromptTemplate::parse("{{not a name}}").is_err());
+    }
+
+    #[test]
+    fn plain_json_is_unchanged() {
+        let json = r#"{ "name": "Arin", "items": [{ "name": "Key" }] }"#;
+        let template = PromptTemplate::parse(json).unwrap();
+
+        assert_eq!(
+            template
+                .render(&PromptVariables::new(), &no_includes)
+                .unwrap(),
+            json
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use tokio::sync::watch;
+
+/// Signals the session to stop waiting on its current run. Clones share the same signal, so the
+/// session's handle can cancel a run the session is busy polling.
+#[derive(Debug, Clone)]
+pub struct Cancellation {
+    cancelled_tx: Arc<watch::Sender<bool>>,
+}
+
+impl Default for Cancellation {
+    fn default() -> Self {
+        let (cancelled_tx, _) = watch::channel(false);
+
+        Cancellation {
+            cancelled_tx: Arc::new(cancelled_tx),
+        }
+    }
+}
+
+impl Cancellation {
+    pub fn cancel(&self) {
+        self.cancelled_tx.send_replace(true);
+    }
+
+    /// Clears the signal, so it only applies to the request that was being processed when it was
+    /// sent.
+    pub fn reset(&self) {
+        self.cancelled_tx.send_replace(false);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        *self.cancelled_tx.borrow()
+    }
+
+    /// Completes once the signal is sent.
+    pub async fn cancelled(&self) {
+        let mut cancelled_rx = self.cancelled_tx.subscribe();
+        let _ = cancelled_rx.wait_for(|cancelled| *cancelled).await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn wakes_waiters_when_cancelled() {
+        let cancellation = Cancellation::default();
+        let waiter = cancellation.clone();
+
+        let waiting = tokio::spawn(async move { waiter.cancelled().await });
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        cancellation.cancel();
+
+        tokio::time::timeout(Duration::from_secs(1), waiting)
+            .await
+            .expect("Waiter wasn't woken")
+            .unwrap();
+        assert!(cancellation.is_cancelled());
+
+        cancellation.reset();
+        assert!(!cancellation.is_cancelled());
+    }
+}
@@ -1,20 +1,44 @@
 use std::sync::Arc;
 
-use log::error;
-use openai_lib::OpenAIClient;
+use anyhow::{anyhow, Context};
+use log::{error, info, warn};
+use openai_lib::{
+    chat_completion::{ChatCompletionClient, ChatCompletionRequest},
+    model::ChatModel,
+    thread::{CreateThreadRequest, ThreadClient, ThreadMessage},
+    OpenAIClient,
+};
 use tokio::sync::{mpsc::Sender, Mutex};
 
-use crate::{game::Game, game_state::GameState};
+use crate::{
+    config::run_polling_config::RunPollingConfig,
+    file_manager::FileManager,
+    game::Game,
+    game_state::GameState,
+    prompt_builder::{PromptBuilder, PromptLibrary},
+};
 
-use self::{session_request::SessionRequest, session_state::SessionState};
+use self::{
+    cancellation::Cancellation,
+    run_poller::RunPoller,
+    session_request::SessionRequest,
+    session_state::{Recovery, SessionState},
+    state_history::{StateHistory, StateTransition},
+};
 
+pub mod cancellation;
+mod run_poller;
 pub mod session_request;
 mod session_state;
+pub mod state_history;
 
 #[derive(Debug)]
 pub struct SessionContext {
     state: Option<SessionState>,
+    history: StateHistory,
     openai_client: OpenAIClient,
+    run_poller: RunPoller,
+    file_manager: FileManager,
     game: Game,
     state_update_tx: Arc<Mutex<Sender<GameState>>>,
 }
@@ -23,13 +47,19 @@ impl SessionContext {
     pub fn new(
         game: Game,
         openai_client: OpenAIClient,
+        polling_config: RunPollingConfig,
+        cancellation: Cancellation,
+        file_manager: FileManager,
         state_update_tx: Arc<Mutex<Sender<GameState>>>,
     ) -> Self {
         let state = SessionState::PendingRunState;
 
         SessionContext {
             state: Some(state),
+            history: StateHistory::default(),
             openai_client,
+            run_poller: RunPoller::new(polling_config, cancellation),
+            file_manager,
             game,
             state_update_tx,
         }
@@ -53,27 +83,210 @@ impl SessionContext {
         }
     }
 
+    /// Picks up a saved game where it left off, waiting on the player in the conversation if one
+    /// was open.
+    pub fn resumed(mut self, game_state: &GameState) -> Self {
+        let state = match game_state.character_interaction.as_ref() {
+            Some(character_interaction) if !character_interaction.closed => {
+                SessionState::CharacterIdleState
+            }
+            _ => SessionState::IdleState,
+        };
+
+        self.state = Some(state);
+        self
+    }
+
+    /// Whether the session is waiting on the player, either for their next entry, their next
+    /// message to a character or their answer to a character's offer.
+    pub fn is_waiting_on_player(&self) -> bool {
+        matches!(
+            self.state,
+            Some(SessionState::IdleState)
+                | Some(SessionState::CharacterIdleState)
+                | Some(SessionState::AwaitingPlayerTradeResponseState { .. })
+                | Some(SessionState::AwaitingPlayerGiftResponseState { .. })
+                | None
+        )
+    }
+
+    /// Whether the narrator is waiting for the player's next entry.
+    pub fn is_idle(&self) -> bool {
+        matches!(self.state, Some(SessionState::IdleState) | None)
+    }
+
+    /// Moves the narrator onto a new thread seeded with the game's transcript, so that after a
+    /// rewind the narrator only remembers what happened up to the restored state. The old thread
+    /// can't be truncated, so it is deleted.
+    pub async fn replace_thread(
+        &mut self,
+        game_state: &mut GameState,
+    ) -> Result<(), anyhow::Error> {
+        self.move_to_new_thread("narrator/rewind.txt", game_state)
+            .await
+    }
+
+    /// Condenses every turn but the latest few into the story so far, and moves the narrator onto
+    /// a new thread seeded with it, so that long games don't outgrow the narrator's context.
+    pub async fn condense_narrator_memory(
+        &mut self,
+        game_state: &mut GameState,
+    ) -> Result<(), anyhow::Error> {
+        let memory = &game_state.narrator_memory;
+        let turns = memory.turns_to_condense(game_state.turn);
+
+        info!("Condensing narrator memory of turns {:?}.", &turns);
+
+        let system_message = PromptLibrary::read("narrator/summarize.txt")?;
+        let user_message = format!(
+            "Story so far:\n{}\n\nTranscript:\n{}",
+            memory.story_so_far.as_deref().unwrap_or("None"),
+            game_state.transcript(turns.clone())
+        );
+
+        let response = self
+            .openai_client
+            .create_chat_completion(
+                ChatCompletionRequest::builder()
+                    .add_system_message(&system_message)
+                    .add_user_message(&user_message)
+                    .model(ChatModel::Gpt_35_Turbo_1106)
+                    .build(),
+            )
+            .await
+            .map_err(|e| anyhow!("Failed to summarize the story so far: {}", e))?;
+
+        // The summary is only kept once the new thread exists, so a failure changes nothing
+        let mut condensed = game_state.clone();
+        condensed
+            .narrator_memory
+            .condense(response.get_content(), turns.end, game_state.turn);
+
+        self.move_to_new_thread("narrator/continue.txt", &mut condensed)
+            .await?;
+
+        *game_state = condensed;
+
+        Ok(())
+    }
+
+    /// Starts a new narrator thread seeded with the prompt, which is given the story so far and
+    /// the transcript of every turn since, and deletes the old one.
+    async fn move_to_new_thread(
+        &mut self,
+        prompt: &str,
+        game_state: &mut GameState,
+    ) -> Result<(), anyhow::Error> {
+        let scene = game_state.current_scene_name.clone().ok_or(anyhow!(
+            "Unable to replay a game that hasn't entered a scene."
+        ))?;
+        let memory = &game_state.narrator_memory;
+
+        let message = PromptBuilder::new()
+            .add_prompt(prompt)
+            .set_variable(
+                "story_so_far",
+                memory.story_so_far.clone().unwrap_or_default(),
+            )
+            .set_variable(
+                "transcript",
+                game_state.transcript(memory.summarized_turns..),
+            )
+            .set_variable("scene", scene)
+            .set_variable("inventory", game_state.inventory.join(", "))
+            .build()
+            .with_context(|| format!("Failed to build {} for game narrator.", prompt))?;
+
+        let thread_response = self
+            .openai_client
+            .create_thread(
+                CreateThreadRequest::builder()
+                    .messages(vec![ThreadMessage::builder().content(message).build()])
+                    .build(),
+            )
+            .await
+            .map_err(|e| anyhow!("Failed to create replacement narrator thread: {:?}", e))?;
+
+        let previous_thread_id = std::mem::replace(&mut game_state.thread_id, thread_response.id);
+
+        info!(
+            "Replaced narrator thread {} with {}.",
+            &previous_thread_id, &game_state.thread_id
+        );
+
+        if let Err(e) = self.openai_client.delete_thread(&previous_thread_id).await {
+            warn!("Unable to delete previous narrator thread: {:?}", e);
+        }
+
+        self.state = Some(SessionState::IdleState);
+
+        Ok(())
+    }
+
+    /// The session's recent state transitions, oldest first.
+    pub fn state_history(&self) -> Vec<StateTransition> {
+        self.history.transitions()
+    }
+
+    /// The session's state machine as a Graphviz graph, marking the transitions taken so far.
+    pub fn state_graph(&self) -> String {
+        self.history
+            .to_dot(self.state.as_ref().map(SessionState::kind))
+    }
+
     async fn process_state_change(
         &mut self,
         session_request: SessionRequest,
         game_state: &mut GameState,
     ) {
-        let new_state = self
-            .state
-            .take()
-            .unwrap_or(SessionState::IdleState)
-            .process(session_request, &self.openai_client, game_state, &self.game)
+        let state = self.state.take().unwrap_or(SessionState::IdleState);
+        let from = state.kind();
+        let request = format!("{:?}", &session_request);
+        let recovery = Recovery::for_state(&state);
+
+        // Changes made by a state that fails part way through are rolled back
+        let snapshot = game_state.clone();
+
+        let result = state
+            .process(
+                session_request,
+                &self.openai_client,
+                &self.run_poller,
+                &self.file_manager,
+                game_state,
+                &mut self.game,
+            )
             .await
-            .unwrap_or_else(|e| {
+            .and_then(|new_state| match from.can_move_to(new_state.kind()) {
+                true => Ok(new_state),
+                false => Err(anyhow!(
+                    "Illegal session state transition from {:?} to {:?}.",
+                    from,
+                    new_state.kind()
+                )),
+            });
+
+        let recovered = result.is_err();
+        let new_state = match result {
+            Ok(new_state) => new_state,
+            Err(e) => {
                 error!(
-                    "Error occurred processing in session state: {:?}. Resetting state.",
+                    "Error occurred processing in session state: {:?}. Recovering.",
                     e
                 );
 
-                // TODO: Add a state reset method to GameState that clears out anything stateful
+                *game_state = snapshot;
 
-                SessionState::IdleState
-            });
+                recovery.recover(&e, &self.openai_client, game_state).await
+            }
+        };
+
+        self.history.record(StateTransition {
+            from,
+            to: new_state.kind(),
+            request,
+            recovered,
+        });
 
         self.state = Some(new_state);
         let state_update_tx = self.state_update_tx.lock().await;
@@ -0,0 +1,171 @@
+use std::{error::Error, fmt::Display, time::Instant};
+
+use log::{info, warn};
+use openai_lib::{
+    run::{RunClient, RunObject, RunStatus},
+    OpenAIClient,
+};
+use rand::Rng;
+
+use crate::config::run_polling_config::RunPollingConfig;
+
+use super::cancellation::Cancellation;
+
+/// Why a run couldn't be waited on. Recovery uses this to tell the player the problem is likely
+/// temporary, rather than something wrong with their request.
+#[derive(Debug, PartialEq)]
+pub enum PollError {
+    /// The run didn't finish before the deadline.
+    TimedOut { run_id: String },
+    /// Checking on the run failed too many times in a row, usually because the connection is down.
+    Unreachable { run_id: String, attempts: u32 },
+    /// The run ended without completing.
+    Ended { run_id: String, status: RunStatus },
+    /// The player stopped waiting on the run, or sent something that took its place.
+    Cancelled { run_id: String },
+}
+
+impl Display for PollError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PollError::TimedOut { run_id } => write!(f, "Run {} timed out.", run_id),
+            PollError::Unreachable { run_id, attempts } => write!(
+                f,
+                "Unable to retrieve status of run {} after {} attempts.",
+                run_id, attempts
+            ),
+            PollError::Ended { run_id, status } => {
+                write!(f, "Run {} ended with status {:?}.", run_id, status)
+            }
+            PollError::Cancelled { run_id } => write!(f, "Run {} was cancelled.", run_id),
+        }
+    }
+}
+
+impl Error for PollError {}
+
+impl PollError {
+    /// Whether the player's request might succeed if they try it again.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            PollError::TimedOut { .. } | PollError::Unreachable { .. }
+        )
+    }
+
+    pub fn player_message(&self) -> &'static str {
+        match self {
+            PollError::TimedOut { .. } => "The response took too long.",
+            PollError::Unreachable { .. } => "Unable to reach OpenAI. Check your connection.",
+            PollError::Ended { .. } => "The response was interrupted.",
+            PollError::Cancelled { .. } => "Stopped waiting for a response.",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RunPoller {
+    config: RunPollingConfig,
+    cancellation: Cancellation,
+}
+
+impl RunPoller {
+    pub fn new(config: RunPollingConfig, cancellation: Cancellation) -> Self {
+        RunPoller {
+            config,
+            cancellation,
+        }
+    }
+
+    /// Waits until the run needs a tool call answered or has completed, or until the wait is
+    /// cancelled.
+    pub async fn poll(
+        &self,
+        openai_client: &OpenAIClient,
+        thread_id: &str,
+        run_id: &str,
+    ) -> Result<RunObject, PollError> {
+        let config = &self.config;
+        let started = Instant::now();
+        let mut consecutive_errors = 0;
+        let mut attempt = 0;
+
+        loop {
+            if self.cancellation.is_cancelled() {
+                return Err(PollError::Cancelled {
+                    run_id: run_id.to_string(),
+                });
+            }
+
+            info!("Retrieving run status for {}.", run_id);
+
+            // A request that hangs would otherwise keep the player waiting past the deadline
+            let remaining = config.deadline().saturating_sub(started.elapsed());
+            let retrieved = match tokio::time::timeout(
+                remaining,
+                openai_client.retrieve_run(thread_id, run_id),
+            )
+            .await
+            {
+                Ok(retrieved) => retrieved,
+                Err(_) => {
+                    return Err(PollError::TimedOut {
+                        run_id: run_id.to_string(),
+                    })
+                }
+            };
+
+            match retrieved {
+                Ok(run) => {
+                    consecutive_errors = 0;
+
+                    match run.status {
+                        RunStatus::RequiresAction | RunStatus::Completed => return Ok(run),
+                        RunStatus::Cancelling
+                        | RunStatus::Cancelled
+                        | RunStatus::Failed
+                        | RunStatus::Expired => {
+                            return Err(PollError::Ended {
+                                run_id: run_id.to_string(),
+                                status: run.status,
+                            })
+                        }
+                        RunStatus::Queued | RunStatus::InProgress => {
+                            info!("Run status in progress. Polling...");
+                        }
+                    }
+                }
+                Err(e) => {
+                    consecutive_errors += 1;
+                    warn!("Failed to retrieve run status: {:?}", e);
+
+                    if consecutive_errors >= config.max_consecutive_errors {
+                        return Err(PollError::Unreachable {
+                            run_id: run_id.to_string(),
+                            attempts: consecutive_errors,
+                        });
+                    }
+                }
+            }
+
+            let delay = config.backoff(attempt, rand::thread_rng().gen_range(0.5..=1.0));
+
+            if started.elapsed() + delay > config.deadline() {
+                return Err(PollError::TimedOut {
+                    run_id: run_id.to_string(),
+                });
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = self.cancellation.cancelled() => {
+                    return Err(PollError::Cancelled {
+                        run_id: run_id.to_string(),
+                    })
+                }
+            }
+
+            attempt += 1;
+        }
+    }
+}
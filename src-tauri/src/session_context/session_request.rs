@@ -3,5 +3,9 @@ pub enum SessionRequest {
     ContinueProcessing,
     PlayerEntry(String),
     CharacterTradeResponse(bool),
+    CharacterTradeCounterOffer {
+        to_player: Vec<String>,
+        from_player: Vec<String>,
+    },
     CharacterEndInteraction,
 }
@@ -6,7 +6,10 @@ use openai_lib::{
 };
 use serde_json::json;
 
-use crate::{game_state::GameState, session_context::session_request::SessionRequest};
+use crate::{
+    game_state::{journal::QuestEvent, GameState},
+    session_context::session_request::SessionRequest,
+};
 
 use super::SessionState;
 
@@ -34,23 +37,22 @@ impl AwaitingPlayerGiftResponseState {
                             .take()
                             .ok_or(anyhow!("No active trade to process in state."))?;
 
-                        let item = trade.to_player.ok_or(anyhow!("Missing to player item."))?;
-
-                        let character_name = game_state
-                            .character_interaction
-                            .as_ref()
-                            .ok_or(anyhow!("Missing character interaction."))?
-                            .character_name
-                            .clone();
+                        let character_name = trade.character_name.clone();
 
-                        game_state.remove_character_item(&character_name, &item);
-                        game_state.add_player_item(&item);
+                        match game_state.exchange_items(&character_name, &trade.to_player, &[]) {
+                            Ok(_) => {
+                                for item in &trade.to_player {
+                                    game_state.record_quest_event(QuestEvent::ItemObtained(item));
+                                }
 
-                        let updated_character_inventory =
-                            game_state.get_character_inventory(&character_name);
-                        let updated_player_inventory = game_state.get_player_inventory();
+                                let updated_character_inventory =
+                                    game_state.get_character_inventory(&character_name);
+                                let updated_player_inventory = game_state.get_player_inventory();
 
-                        json!({ "player_response": "accept", "updated_player_inventory": updated_player_inventory, "updated_character_inventory": updated_character_inventory}).to_string()
+                                json!({ "player_response": "accept", "updated_player_inventory": updated_player_inventory, "updated_character_inventory": updated_character_inventory}).to_string()
+                            }
+                            Err(reason) => json!({ "error": reason }).to_string(),
+                        }
                     }
                     false => {
                         info!("Processing declined trade request.");
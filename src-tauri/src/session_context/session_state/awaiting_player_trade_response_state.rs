@@ -6,7 +6,10 @@ use openai_lib::{
 };
 use serde_json::json;
 
-use crate::{game_state::GameState, session_context::session_request::SessionRequest};
+use crate::{
+    game_state::{character_trade::CharacterTrade, journal::QuestEvent, GameState},
+    session_context::session_request::SessionRequest,
+};
 
 use super::SessionState;
 
@@ -22,81 +25,120 @@ impl AwaitingPlayerTradeResponseState {
     ) -> Result<SessionState, anyhow::Error> {
         match request {
             SessionRequest::CharacterTradeResponse(accepted) => {
+                let character_interaction = game_state
+                    .character_interaction
+                    .as_mut()
+                    .ok_or(anyhow!("Missing character interaction."))?;
+
+                let trade = character_interaction
+                    .trade
+                    .take()
+                    .ok_or(anyhow!("No active trade to process in state."))?;
+
+                character_interaction.counter_offer = None;
+
                 let output = match accepted {
                     true => {
                         info!("Processing trade acceptance.");
 
-                        let trade = game_state
-                            .character_interaction
-                            .as_mut()
-                            .ok_or(anyhow!("Missing character interaction."))?
-                            .trade
-                            .take()
-                            .ok_or(anyhow!("No active trade to process in state."))?;
-
-                        let from_player_item = trade
-                            .from_player
-                            .ok_or(anyhow!("Missing from player item."))?;
-                        let to_player_item =
-                            trade.to_player.ok_or(anyhow!("Missing to player item."))?;
-
-                        let character_name = game_state
-                            .character_interaction
-                            .as_ref()
-                            .ok_or(anyhow!("Missing character interaction."))?
-                            .character_name
-                            .clone();
-
-                        game_state.remove_character_item(&character_name, &to_player_item);
-                        game_state.add_character_item(&character_name, &from_player_item);
-                        game_state.remove_player_item(&from_player_item);
-                        game_state.add_player_item(&to_player_item);
-
-                        let updated_character_inventory =
-                            game_state.get_character_inventory(&character_name);
-                        let updated_player_inventory = game_state.get_player_inventory();
-
-                        json!({ "player_response": "accept", "updated_player_inventory": updated_player_inventory, "updated_character_inventory": updated_character_inventory}).to_string()
+                        let character_name = trade.character_name.clone();
+
+                        match game_state.exchange_items(
+                            &character_name,
+                            &trade.to_player,
+                            &trade.from_player,
+                        ) {
+                            Ok(_) => {
+                                for item in &trade.to_player {
+                                    game_state.record_quest_event(QuestEvent::ItemObtained(item));
+                                }
+
+                                let updated_character_inventory =
+                                    game_state.get_character_inventory(&character_name);
+                                let updated_player_inventory = game_state.get_player_inventory();
+
+                                json!({ "player_response": "accept", "updated_player_inventory": updated_player_inventory, "updated_character_inventory": updated_character_inventory}).to_string()
+                            }
+                            Err(reason) => json!({ "error": reason }).to_string(),
+                        }
                     }
                     false => {
                         info!("Processing declined trade request.");
 
-                        game_state
-                            .character_interaction
-                            .as_mut()
-                            .ok_or(anyhow!("Missing character interaction."))?
-                            .trade = None;
-
                         json!({ "player_response": "reject" }).to_string()
                     }
                 };
 
-                info!("Submitting trade function tool outputs response.");
+                submit_output(openai_client, game_state, &run_id, &tool_call_id, &output).await?;
 
-                let submit_tool_outputs_request = SubmitToolOutputsRequest::builder()
-                    .add_tool_output(&tool_call_id, &output)
-                    .build();
+                Ok(SessionState::CharacterPollingRunState { run_id })
+            }
+            SessionRequest::CharacterTradeCounterOffer {
+                to_player,
+                from_player,
+            } => {
+                info!(
+                    "Processing counter-offer of {:?} for {:?}.",
+                    from_player, to_player
+                );
 
-                let thread_id = &game_state
+                let character_name = game_state
                     .character_interaction
                     .as_ref()
                     .ok_or(anyhow!("Missing character interaction."))?
-                    .thread_id;
-
-                let submit_tool_outputs_response = openai_client
-                    .submit_tool_outputs(submit_tool_outputs_request, &thread_id, &run_id)
-                    .await
-                    .map_err(|e| {
-                        anyhow!(
-                            "Unable to submit tool outputs for character session: {:?}",
-                            e
-                        )
-                    })?;
-
-                trace!(
-                    "Received tool outputs response:\n{:?}",
-                    submit_tool_outputs_response
-                );
+                    .trade
+                    .as_ref()
+                    .ok_or(anyhow!("No active trade to counter in state."))?
+                    .character_name
+                    .clone();
+
+                let check = match to_player.is_empty() || from_player.is_empty() {
+                    true => Err(String::from(
+                        "A counter-offer needs at least one item from each side.",
+                    )),
+                    false => game_state.check_exchange(&character_name, &to_player, &from_player),
+                };
+
+                let character_interaction = game_state
+                    .character_interaction
+                    .as_mut()
+                    .ok_or(anyhow!("Missing character interaction."))?;
+
+                // An invalid counter-offer is the player's mistake, so the original trade stays
+                // open for them to respond to again
+                let (to_player, from_player) = match check {
+                    Ok(items) => items,
+                    Err(reason) => {
+                        character_interaction.add_nonverbal(&reason);
+
+                        return Ok(SessionState::AwaitingPlayerTradeResponseState {
+                            run_id,
+                            tool_call_id,
+                        });
+                    }
+                };
+
+                character_interaction.add_nonverbal(&format!(
+                    "The player offers {} in exchange for {}.",
+                    from_player.join(", "),
+                    to_player.join(", ")
+                ));
+
+                character_interaction.trade = None;
+                character_interaction.counter_offer = Some(CharacterTrade {
+                    character_name,
+                    to_player: to_player.clone(),
+                    from_player: from_player.clone(),
+                });
+
+                let output = json!({
+                    "player_response": "counter_offer",
+                    "player_offers": from_player,
+                    "player_requests": to_player,
+                })
+                .to_string();
+
+                submit_output(openai_client, game_state, &run_id, &tool_call_id, &output).await?;
 
                 Ok(SessionState::CharacterPollingRunState { run_id })
             }
@@ -107,3 +149,40 @@ impl AwaitingPlayerTradeResponseState {
         }
     }
 }
+
+async fn submit_output(
+    openai_client: &OpenAIClient,
+    game_state: &GameState,
+    run_id: &str,
+    tool_call_id: &str,
+    output: &str,
+) -> Result<(), anyhow::Error> {
+    info!("Submitting trade function tool outputs response.");
+
+    let submit_tool_outputs_request = SubmitToolOutputsRequest::builder()
+        .add_tool_output(tool_call_id, output)
+        .build();
+
+    let thread_id = &game_state
+        .character_interaction
+        .as_ref()
+        .ok_or(anyhow!("Missing character interaction."))?
+        .thread_id;
+
+    let submit_tool_outputs_response = openai_client
+        .submit_tool_outputs(submit_tool_outputs_request, thread_id, run_id)
+        .await
+        .map_err(|e| {
+            anyhow!(
+                "Unable to submit tool outputs for character session: {:?}",
+                e
+            )
+        })?;
+
+    trace!(
+        "Received tool outputs response:\n{:?}",
+        submit_tool_outputs_response
+    );
+
+    Ok(())
+}
@@ -8,7 +8,7 @@ use crate::{session_context::{session_request::SessionRequest, session_state::Se
 pub struct CharacterEndInteractionState {}
 
 impl CharacterEndInteractionState {
-    pub async fn process(request: SessionRequest, openai_client: &OpenAIClient, game_state: &mut GameState, summary: Option<String>) -> Result<SessionState, anyhow::Error> {
+    pub async fn process(request: SessionRequest, openai_client: &OpenAIClient, game_state: &mut GameState) -> Result<SessionState, anyhow::Error> {
         match request {
             SessionRequest::ContinueProcessing => {
 
@@ -16,30 +16,39 @@ impl CharacterEndInteractionState {
 
                 match closed {
                     true => {
-                        let summary = summary.ok_or(anyhow!("Processing end of character interaction, but no summary provided."))?;
-                        let character_name = game_state.character_interaction.as_ref().ok_or(anyhow!("Unable to access character interaction."))?.character_name.clone();
-
-                        game_state.save_previous_conversation(&character_name, &summary);
+                        // Each character's summary has already been saved as it was read
+                        let summaries = game_state.character_interaction.as_ref().ok_or(anyhow!("Unable to access character interaction."))?.summaries.clone();
+                        if summaries.is_empty() {
+                            bail!("Processing end of character interaction, but no summary provided.");
+                        }
 
                         let run_id = game_state.character_interaction.as_ref().ok_or(anyhow!("Unable to access character interaction."))?.initiating_run_id.clone();
                         let tool_call_id = game_state.character_interaction.as_ref().ok_or(anyhow!("Unable to access character interaction."))?.initiating_tool_call_id.clone();
 
                         let output = json!({
-                            "conversation_summary": summary,
+                            "conversation_summary": summaries.join("\n"),
                         }).to_string();
 
                         let thread_id = game_state.character_interaction.as_ref().ok_or(anyhow!("Unable to access character interaction."))?.thread_id.clone();
-                        let assistant_id = game_state.character_interaction.as_ref().ok_or(anyhow!("Unable to access character interaction."))?.assistant_id.clone();
+                        let assistant_ids = game_state.character_interaction.as_ref().ok_or(anyhow!("Unable to access character interaction."))?.assistant_ids();
 
                         openai_client.delete_thread(&thread_id).await.map_err(|e| anyhow!("Unable to delete thread: {:?}", e))?;
-                        openai_client.delete_assistant(&assistant_id).await.map_err(|e| anyhow!("Unable to delete character assistant: {:?}", e))?;
+                        for assistant_id in assistant_ids {
+                            openai_client.delete_assistant(&assistant_id).await.map_err(|e| anyhow!("Unable to delete character assistant: {:?}", e))?;
+                        }
 
                         game_state.end_character_interaction();
 
                         Ok(SessionState::SubmitToolOutputsState { run_id, tool_call_id, output })
                     }
                     false => {
-                        game_state.character_interaction.as_mut().ok_or(anyhow!("Unable to access character interaction."))?.closed = true;
+                        let character_interaction = game_state.character_interaction.as_mut().ok_or(anyhow!("Unable to access character interaction."))?;
+                        character_interaction.closed = true;
+
+                        // Every character summarizes the conversation in turn
+                        let speakers = character_interaction.participant_names();
+                        character_interaction.queue_speakers(speakers);
+                        character_interaction.next_speaker();
 
                         let prompt = String::from("$summarize");
 
@@ -5,7 +5,10 @@ use openai_lib::{
     OpenAIClient,
 };
 
-use crate::{game_state::GameState, session_context::session_request::SessionRequest};
+use crate::{
+    game_state::{turn_policy::TurnPolicy, GameState},
+    session_context::session_request::SessionRequest,
+};
 
 use super::SessionState;
 
@@ -42,17 +45,28 @@ impl CharacterIdleState {
                 info!("New message appended to character thread. Adding to UI state.");
                 trace!("{:#?}", create_message_response);
 
-                game_state
+                let character_interaction = game_state
                     .character_interaction
                     .as_mut()
-                    .ok_or(anyhow!("No character interaction to read messages from."))?
-                    .add_message(&create_message_response.get_text_content());
+                    .ok_or(anyhow!("No character interaction to read messages from."))?;
+
+                character_interaction.add_message(&create_message_response.get_text_content());
+
+                let speakers = TurnPolicy::next_speakers(
+                    &character_interaction.participant_names(),
+                    &prompt,
+                    &character_interaction.character_name,
+                );
+                info!("Characters responding to the player: {:?}", &speakers);
+
+                character_interaction.queue_speakers(speakers);
+                character_interaction.next_speaker();
 
                 info!("Transitioning to pending run state.");
                 Ok(SessionState::CharacterRunRequestState)
             }
             SessionRequest::CharacterEndInteraction => {
-                return Ok(SessionState::CharacterEndInteractionState { summary: None })
+                return Ok(SessionState::CharacterEndInteractionState)
             }
             _ => bail!(
                 "Received invalid session request for idle state: {:?}. Expected PlayerEntry.",
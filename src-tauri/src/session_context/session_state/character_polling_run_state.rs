@@ -1,16 +1,27 @@
-use anyhow::{bail, anyhow};
-use log::{info, error};
-use openai_lib::{OpenAIClient, run::{RunClient, RunStatus}};
+use anyhow::{anyhow, bail};
+use log::info;
+use openai_lib::{run::RunStatus, OpenAIClient};
 
-use crate::{session_context::session_request::SessionRequest, game_state::GameState};
+use crate::{
+    game_state::GameState,
+    session_context::{
+        run_poller::{PollError, RunPoller},
+        session_request::SessionRequest,
+    },
+};
 
 use super::SessionState;
 
-pub struct CharacterPollingRunState {
-}
+pub struct CharacterPollingRunState {}
 
 impl CharacterPollingRunState {
-    pub async fn process(request: SessionRequest, openai_client: &OpenAIClient, game_state: &mut GameState, run_id: String) -> Result<SessionState, anyhow::Error>{
+    pub async fn process(
+        request: SessionRequest,
+        openai_client: &OpenAIClient,
+        run_poller: &RunPoller,
+        game_state: &mut GameState,
+        run_id: String,
+    ) -> Result<SessionState, anyhow::Error> {
         match request {
             SessionRequest::ContinueProcessing => {
                 let thread_id = game_state
@@ -18,54 +29,49 @@ impl CharacterPollingRunState {
                     .as_ref()
                     .ok_or(anyhow!("No character interaction available in game state."))?
                     .thread_id.clone();
-                loop {
-                    info!("Polling character run status...");
-                    if let Ok(retrieve_run_response) = openai_client
-                        .retrieve_run(
-                            &thread_id,
-                            &run_id,
-                        )
-                        .await
-                    {
-                        match retrieve_run_response.status {
-                            RunStatus::RequiresAction => {
-                                info!("Run requested function response.");
 
-                                let tool_calls = retrieve_run_response
-                                    .required_action
-                                    .ok_or(anyhow!(
-                                        "No required actions despite requires_action run status."
-                                    ))?
-                                    .submit_tool_outputs
-                                    .tool_calls;
-                                if tool_calls.len() > 1 {
-                                    bail!("Assistant tried to trigger two functions.");
-                                }
+                info!("Polling character run status...");
+                let retrieve_run_response = run_poller
+                    .poll(openai_client, &thread_id, &run_id)
+                    .await?;
+
+                match retrieve_run_response.status {
+                    RunStatus::RequiresAction => {
+                        info!("Run requested function response.");
 
-                                let tool_call = tool_calls
-                                    .into_iter()
-                                    .next()
-                                    .ok_or(anyhow!("No tool calls available despite action required."))?;
+                        let tool_calls = retrieve_run_response
+                            .required_action
+                            .ok_or(anyhow!(
+                                "No required actions despite requires_action run status."
+                            ))?
+                            .submit_tool_outputs
+                            .tool_calls;
 
-                                return Ok(SessionState::CharacterRequiresActionState { run_id, tool_call })
-                            }
-                            RunStatus::Cancelling | RunStatus::Cancelled | RunStatus::Failed | RunStatus::Expired => {
-                                error!("The run has expired or has failed.");
-                                bail!("Assistant run failed.")
-                            }
-                            RunStatus::Completed => return Ok(SessionState::CharacterReadMessageState),
-                            RunStatus::Queued | RunStatus::InProgress => {},
+                        if tool_calls.len() > 1 {
+                            bail!("Assistant tried to trigger two functions.");
                         }
-                    }
 
-                    tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
+                        let tool_call = tool_calls
+                            .into_iter()
+                            .next()
+                            .ok_or(anyhow!("No tool calls available despite action required."))?;
+
+                        Ok(SessionState::CharacterRequiresActionState { run_id, tool_call })
+                    }
+                    RunStatus::Completed => Ok(SessionState::CharacterReadMessageState),
+                    status @ (RunStatus::Cancelling
+                    | RunStatus::Cancelled
+                    | RunStatus::Failed
+                    | RunStatus::Expired) => Err(PollError::Ended { run_id, status }.into()),
+                    status @ (RunStatus::Queued | RunStatus::InProgress) => {
+                        bail!("Run {} was still {:?} after polling.", run_id, status)
+                    }
                 }
             }
             _ => bail!(
                 "Unexpected request received for CharacterPollingRunState: {:?}. Expected ContinueProcessing.",
                 request
             ),
-                    
         }
     }
 }
@@ -72,9 +72,20 @@ impl CharacterReadMessageState {
                     .clone();
 
                 if closed {
-                    return Ok(SessionState::CharacterEndInteractionState {
-                        summary: Some(character_message),
-                    });
+                    game_state.save_previous_conversation(&character_name, &character_message);
+
+                    let character_interaction = game_state
+                        .character_interaction
+                        .as_mut()
+                        .ok_or(anyhow!("Unable to access character interaction."))?;
+
+                    character_interaction.add_summary(&character_message);
+
+                    // Everyone in the conversation summarizes it before it ends
+                    return match character_interaction.next_speaker() {
+                        true => Ok(SessionState::CharacterRunRequestState),
+                        false => Ok(SessionState::CharacterEndInteractionState),
+                    };
                 }
 
                 let processed_messages = CharacterReadMessageState::process_meta_commands(
@@ -100,7 +111,17 @@ impl CharacterReadMessageState {
                             .add_nonverbal(&message);
                     }
                 }
-                Ok(SessionState::CharacterIdleState)
+
+                let has_next_speaker = game_state
+                    .character_interaction
+                    .as_mut()
+                    .ok_or(anyhow!("Unable to access character interaction."))?
+                    .next_speaker();
+
+                match has_next_speaker {
+                    true => Ok(SessionState::CharacterRunRequestState),
+                    false => Ok(SessionState::CharacterIdleState),
+                }
             }
             _ => bail!(
                 "Unexpected request received for CharacterReadMessageState: {:?}.",
@@ -37,6 +37,20 @@ impl CharacterRequiresActionState {
                             arguments,
                         })
                     }
+                    "grant_passage" => {
+                        return Ok(SessionState::ProcessCharacterGrantPassageState {
+                            run_id,
+                            tool_call_id,
+                            arguments,
+                        })
+                    }
+                    "update_disposition" => {
+                        return Ok(SessionState::ProcessCharacterUpdateDispositionState {
+                            run_id,
+                            tool_call_id,
+                            arguments,
+                        })
+                    }
 
                     _ => bail!(
                         "Invalid function call received: {}",
@@ -0,0 +1,55 @@
+use anyhow::{anyhow, bail};
+use log::info;
+use openai_lib::{
+    run::{RunClient, SubmitToolOutputsRequest},
+    OpenAIClient,
+};
+
+use crate::{game_state::GameState, session_context::session_request::SessionRequest};
+
+use super::SessionState;
+
+pub struct CharacterSubmitToolOutputsState {}
+
+impl CharacterSubmitToolOutputsState {
+    pub async fn process(
+        request: SessionRequest,
+        openai_client: &OpenAIClient,
+        game_state: &mut GameState,
+        run_id: String,
+        tool_call_id: String,
+        output: String,
+    ) -> Result<SessionState, anyhow::Error> {
+        match request {
+            SessionRequest::ContinueProcessing => {
+                info!("Sending tool outputs to character run {}", &run_id);
+
+                let thread_id = &game_state
+                    .character_interaction
+                    .as_ref()
+                    .ok_or(anyhow!("Missing character interaction."))?
+                    .thread_id;
+
+                let submit_tool_outputs_request = SubmitToolOutputsRequest::builder()
+                    .add_tool_output(&tool_call_id, &output)
+                    .build();
+
+                openai_client
+                    .submit_tool_outputs(submit_tool_outputs_request, thread_id, &run_id)
+                    .await
+                    .map_err(|e| {
+                        anyhow!(
+                            "Unable to submit tool outputs for character session: {:?}",
+                            e
+                        )
+                    })?;
+
+                Ok(SessionState::CharacterPollingRunState { run_id })
+            }
+            _ => bail!(
+                "Invalid session request for character submit tool outputs state: {:?}. Expected ContinueProcessing.",
+                &request
+            ),
+        }
+    }
+}
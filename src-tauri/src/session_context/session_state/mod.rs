@@ -1,7 +1,7 @@
 use anyhow::Context;
 use openai_lib::{tool::ToolCall, OpenAIClient};
 
-use crate::{game::Game, game_state::GameState};
+use crate::{file_manager::FileManager, game::Game, game_state::GameState};
 
 use self::{
     awaiting_player_gift_response_state::AwaitingPlayerGiftResponseState,
@@ -11,18 +11,31 @@ use self::{
     character_polling_run_state::CharacterPollingRunState,
     character_read_message_state::CharacterReadMessageState,
     character_requires_action_state::CharacterRequiresActionState,
-    character_run_request_state::CharacterRunRequestState, idle_state::IdleState,
+    character_run_request_state::CharacterRunRequestState,
+    character_submit_tool_outputs_state::CharacterSubmitToolOutputsState, idle_state::IdleState,
     pending_run_state::PendingRunState, polling_run_state::PollingRunState,
     process_add_item_state::ProcessAddItemState,
     process_character_gift_state::ProcessCharacterGiftState,
+    process_character_grant_passage_state::ProcessCharacterGrantPassageState,
     process_character_interact_state::ProcessCharacterInteractState,
     process_character_trade_state::ProcessCharacterTradeState,
-    process_end_game::ProcessEndGameState, process_new_scene_state::ProcessNewSceneState,
-    process_remove_item_state::ProcessRemoveItemState, read_message_state::ReadMessageState,
-    requires_action_state::RequiresActionState, submit_tool_outputs_state::SubmitToolOutputsState,
+    process_character_update_disposition_state::ProcessCharacterUpdateDispositionState,
+    process_combine_items_state::ProcessCombineItemsState,
+    process_complete_objective_state::ProcessCompleteObjectiveState,
+    process_create_item_state::ProcessCreateItemState,
+    process_drop_item_state::ProcessDropItemState, process_end_game::ProcessEndGameState,
+    process_examine_character_state::ProcessExamineCharacterState,
+    process_examine_item_state::ProcessExamineItemState,
+    process_modify_stat_state::ProcessModifyStatState,
+    process_new_scene_state::ProcessNewSceneState,
+    process_recall_lore_state::ProcessRecallLoreState,
+    process_remove_item_state::ProcessRemoveItemState,
+    process_skill_check_state::ProcessSkillCheckState, process_use_item_state::ProcessUseItemState,
+    read_message_state::ReadMessageState, requires_action_state::RequiresActionState,
+    submit_tool_outputs_state::SubmitToolOutputsState,
 };
 
-use super::session_request::SessionRequest;
+use super::{run_poller::RunPoller, session_request::SessionRequest};
 
 mod awaiting_player_gift_response_state;
 mod awaiting_player_trade_response_state;
@@ -32,19 +45,36 @@ mod character_polling_run_state;
 mod character_read_message_state;
 mod character_requires_action_state;
 mod character_run_request_state;
+mod character_submit_tool_outputs_state;
 mod idle_state;
 mod pending_run_state;
 mod polling_run_state;
 mod process_add_item_state;
 mod process_character_gift_state;
+mod process_character_grant_passage_state;
 mod process_character_interact_state;
 mod process_character_trade_state;
+mod process_character_update_disposition_state;
+mod process_combine_items_state;
+mod process_complete_objective_state;
+mod process_create_item_state;
+mod process_drop_item_state;
 mod process_end_game;
+mod process_examine_character_state;
+mod process_examine_item_state;
+mod process_modify_stat_state;
 mod process_new_scene_state;
+mod process_recall_lore_state;
 mod process_remove_item_state;
+mod process_skill_check_state;
+mod process_use_item_state;
 mod read_message_state;
+mod recovery;
 mod requires_action_state;
 mod submit_tool_outputs_state;
+mod transitions;
+
+pub use self::{recovery::Recovery, transitions::StateKind};
 
 #[derive(Debug)]
 pub enum SessionState {
@@ -68,11 +98,31 @@ pub enum SessionState {
         tool_call_id: String,
         arguments: serde_json::Value,
     },
+    ProcessCreateItemState {
+        run_id: String,
+        tool_call_id: String,
+        item: String,
+    },
     ProcessRemoveItemState {
         run_id: String,
         tool_call_id: String,
         arguments: serde_json::Value,
     },
+    ProcessDropItemState {
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+    },
+    ProcessUseItemState {
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+    },
+    ProcessCombineItemsState {
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+    },
     ProcessCharacterInteractState {
         run_id: String,
         tool_call_id: String,
@@ -83,6 +133,36 @@ pub enum SessionState {
         tool_call_id: String,
         arguments: serde_json::Value,
     },
+    ProcessCompleteObjectiveState {
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+    },
+    ProcessModifyStatState {
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+    },
+    ProcessSkillCheckState {
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+    },
+    ProcessExamineItemState {
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+    },
+    ProcessExamineCharacterState {
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+    },
+    ProcessRecallLoreState {
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+    },
     SubmitToolOutputsState {
         run_id: String,
         tool_call_id: String,
@@ -106,6 +186,21 @@ pub enum SessionState {
         tool_call_id: String,
         arguments: serde_json::Value,
     },
+    ProcessCharacterGrantPassageState {
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+    },
+    ProcessCharacterUpdateDispositionState {
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+    },
+    CharacterSubmitToolOutputsState {
+        run_id: String,
+        tool_call_id: String,
+        output: String,
+    },
     AwaitingPlayerTradeResponseState {
         run_id: String,
         tool_call_id: String,
@@ -116,9 +211,7 @@ pub enum SessionState {
     },
     CharacterReadMessageState,
     CharacterIdleState,
-    CharacterEndInteractionState {
-        summary: Option<String>,
-    },
+    CharacterEndInteractionState,
 }
 
 impl SessionState {
@@ -126,20 +219,22 @@ impl SessionState {
         self,
         request: SessionRequest,
         openai_client: &OpenAIClient,
+        run_poller: &RunPoller,
+        file_manager: &FileManager,
         game_state: &mut GameState,
-        game: &Game,
+        game: &mut Game,
     ) -> Result<SessionState, anyhow::Error> {
         match self {
             SessionState::IdleState => IdleState::process(request, openai_client, game_state)
                 .await
                 .context("Failed to process state change from IdleState."),
             SessionState::PendingRunState => {
-                PendingRunState::process(request, openai_client, game_state)
+                PendingRunState::process(request, openai_client, game_state, game)
                     .await
                     .context("Failed to process state change from PendingRunState")
             }
             SessionState::PollingRunState { run_id } => {
-                PollingRunState::process(request, openai_client, game_state, run_id)
+                PollingRunState::process(request, openai_client, run_poller, game_state, run_id)
                     .await
                     .context("Failed to process state change from PollingRunState")
             }
@@ -171,9 +266,31 @@ impl SessionState {
                 run_id,
                 tool_call_id,
                 arguments,
-            } => ProcessAddItemState::process(request, game_state, run_id, tool_call_id, arguments)
-                .await
-                .context("Failed to process state change from ProcessAddItemState."),
+            } => ProcessAddItemState::process(
+                request,
+                game_state,
+                run_id,
+                tool_call_id,
+                arguments,
+                game,
+            )
+            .await
+            .context("Failed to process state change from ProcessAddItemState."),
+            SessionState::ProcessCreateItemState {
+                run_id,
+                tool_call_id,
+                item,
+            } => ProcessCreateItemState::process(
+                request,
+                openai_client,
+                file_manager,
+                run_id,
+                tool_call_id,
+                item,
+                game,
+            )
+            .await
+            .context("Failed to process state change from ProcessCreateItemState."),
             SessionState::ProcessRemoveItemState {
                 run_id,
                 tool_call_id,
@@ -187,6 +304,43 @@ impl SessionState {
             )
             .await
             .context("Failed to process state change from ProcessRemoveItemState."),
+            SessionState::ProcessDropItemState {
+                run_id,
+                tool_call_id,
+                arguments,
+            } => {
+                ProcessDropItemState::process(request, game_state, run_id, tool_call_id, arguments)
+                    .await
+                    .context("Failed to process state change from ProcessDropItemState.")
+            }
+            SessionState::ProcessUseItemState {
+                run_id,
+                tool_call_id,
+                arguments,
+            } => ProcessUseItemState::process(
+                request,
+                game_state,
+                run_id,
+                tool_call_id,
+                arguments,
+                game,
+            )
+            .await
+            .context("Failed to process state change from ProcessUseItemState."),
+            SessionState::ProcessCombineItemsState {
+                run_id,
+                tool_call_id,
+                arguments,
+            } => ProcessCombineItemsState::process(
+                request,
+                game_state,
+                run_id,
+                tool_call_id,
+                arguments,
+                game,
+            )
+            .await
+            .context("Failed to process state change from ProcessCombineItemsState."),
             SessionState::ProcessCharacterInteractState {
                 run_id,
                 tool_call_id,
@@ -209,6 +363,87 @@ impl SessionState {
             } => ProcessEndGameState::process(request, game_state, run_id, tool_call_id, arguments)
                 .await
                 .context("Failed to process state change from ProcessEndGameState."),
+            SessionState::ProcessCompleteObjectiveState {
+                run_id,
+                tool_call_id,
+                arguments,
+            } => ProcessCompleteObjectiveState::process(
+                request,
+                game_state,
+                run_id,
+                tool_call_id,
+                arguments,
+            )
+            .await
+            .context("Failed to process state change from ProcessCompleteObjectiveState."),
+            SessionState::ProcessModifyStatState {
+                run_id,
+                tool_call_id,
+                arguments,
+            } => ProcessModifyStatState::process(
+                request,
+                game_state,
+                run_id,
+                tool_call_id,
+                arguments,
+            )
+            .await
+            .context("Failed to process state change from ProcessModifyStatState."),
+            SessionState::ProcessSkillCheckState {
+                run_id,
+                tool_call_id,
+                arguments,
+            } => ProcessSkillCheckState::process(
+                request,
+                game_state,
+                run_id,
+                tool_call_id,
+                arguments,
+            )
+            .await
+            .context("Failed to process state change from ProcessSkillCheckState."),
+            SessionState::ProcessExamineItemState {
+                run_id,
+                tool_call_id,
+                arguments,
+            } => ProcessExamineItemState::process(
+                request,
+                game,
+                game_state,
+                run_id,
+                tool_call_id,
+                arguments,
+            )
+            .await
+            .context("Failed to process state change from ProcessExamineItemState."),
+            SessionState::ProcessExamineCharacterState {
+                run_id,
+                tool_call_id,
+                arguments,
+            } => ProcessExamineCharacterState::process(
+                request,
+                game,
+                game_state,
+                run_id,
+                tool_call_id,
+                arguments,
+            )
+            .await
+            .context("Failed to process state change from ProcessExamineCharacterState."),
+            SessionState::ProcessRecallLoreState {
+                run_id,
+                tool_call_id,
+                arguments,
+            } => ProcessRecallLoreState::process(
+                request,
+                game,
+                game_state,
+                run_id,
+                tool_call_id,
+                arguments,
+            )
+            .await
+            .context("Failed to process state change from ProcessRecallLoreState."),
             SessionState::SubmitToolOutputsState {
                 run_id,
                 tool_call_id,
@@ -228,11 +463,15 @@ impl SessionState {
                     .await
                     .context("Failed to process state change from CharacterRunRequestState.")
             }
-            SessionState::CharacterPollingRunState { run_id } => {
-                CharacterPollingRunState::process(request, openai_client, game_state, run_id)
-                    .await
-                    .context("Failed to process state change from CharacterPollingRunState.")
-            }
+            SessionState::CharacterPollingRunState { run_id } => CharacterPollingRunState::process(
+                request,
+                openai_client,
+                run_poller,
+                game_state,
+                run_id,
+            )
+            .await
+            .context("Failed to process state change from CharacterPollingRunState."),
             SessionState::CharacterRequiresActionState { run_id, tool_call } => {
                 CharacterRequiresActionState::process(request, run_id, tool_call)
                     .await
@@ -264,6 +503,47 @@ impl SessionState {
             )
             .await
             .context("Failed to process state change from ProcessCharacterGiftState."),
+            SessionState::ProcessCharacterGrantPassageState {
+                run_id,
+                tool_call_id,
+                arguments,
+            } => ProcessCharacterGrantPassageState::process(
+                request,
+                game_state,
+                run_id,
+                tool_call_id,
+                arguments,
+                game,
+            )
+            .await
+            .context("Failed to process state change from ProcessCharacterGrantPassageState."),
+            SessionState::ProcessCharacterUpdateDispositionState {
+                run_id,
+                tool_call_id,
+                arguments,
+            } => ProcessCharacterUpdateDispositionState::process(
+                request,
+                game_state,
+                run_id,
+                tool_call_id,
+                arguments,
+            )
+            .await
+            .context("Failed to process state change from ProcessCharacterUpdateDispositionState."),
+            SessionState::CharacterSubmitToolOutputsState {
+                run_id,
+                tool_call_id,
+                output,
+            } => CharacterSubmitToolOutputsState::process(
+                request,
+                openai_client,
+                game_state,
+                run_id,
+                tool_call_id,
+                output,
+            )
+            .await
+            .context("Failed to process state change from CharacterSubmitToolOutputsState."),
             SessionState::AwaitingPlayerTradeResponseState {
                 run_id,
                 tool_call_id,
@@ -298,8 +578,8 @@ impl SessionState {
                     .await
                     .context("Failed to process state change from CharacterIdleState.")
             }
-            SessionState::CharacterEndInteractionState { summary } => {
-                CharacterEndInteractionState::process(request, openai_client, game_state, summary)
+            SessionState::CharacterEndInteractionState => {
+                CharacterEndInteractionState::process(request, openai_client, game_state)
                     .await
                     .context("Failed to process state change from CharacterEndInteractionState.")
             }
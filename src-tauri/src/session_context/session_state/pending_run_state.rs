@@ -3,7 +3,8 @@ use log::info;
 use openai_lib::{OpenAIClient, run::{CreateRunRequest, RunClient}};
 
 use crate::{
-    game_state::GameState, 
+    game::Game,
+    game_state::{narrator_context::NarratorContext, GameState},
     session_context::session_request::SessionRequest,
 };
 
@@ -16,17 +17,19 @@ impl PendingRunState {
         session_request: SessionRequest,
         openai_client: &OpenAIClient,
         game_state: &mut GameState,
+        game: &Game,
     ) -> Result<SessionState, anyhow::Error> {
         match session_request {
             SessionRequest::ContinueProcessing => {
                 info!("Creating new run on thread.");
 
+                // The narrator is told the live game state with every run, rather than relying
+                // on what it remembers from the thread
                 let run_request = CreateRunRequest::builder()
                     .assistant_id(&game_state.assistant_id)
-                    .additional_instructions(format!(
-                        "Current player inventory: [{}]",
-                        game_state.get_player_inventory().join(", ")
-                    ))
+                    .additional_instructions(
+                        NarratorContext::new(game_state, game).to_instructions()?,
+                    )
                     .build();
                 let create_run_response = openai_client
                     .create_run(run_request, &game_state.thread_id)
@@ -1,7 +1,12 @@
 use anyhow::{anyhow, bail};
 use serde_json::json;
 
-use crate::{game_state::GameState, session_context::session_request::SessionRequest};
+use crate::{
+    game::Game,
+    game_state::{journal::QuestEvent, GameState},
+    session_context::session_request::SessionRequest,
+    utils::fuzzy_match::FuzzyMatch,
+};
 
 use super::SessionState;
 
@@ -14,25 +19,109 @@ impl ProcessAddItemState {
         run_id: String,
         tool_call_id: String,
         arguments: serde_json::Value,
+        game: &Game,
     ) -> Result<SessionState, anyhow::Error> {
         match request {
             SessionRequest::ContinueProcessing => {
-
                 let item = arguments["item"]
                     .as_str()
                     .ok_or(anyhow!("Invalid arguments to add_item function."))?
                     .to_string();
-                // TODO: Remove the item from the scene
-                game_state.add_item(&item);
+
+                let item = match resolve_item(game_state, game, &item) {
+                    Ok(ItemLocation::Scene(item)) => {
+                        game_state.take_scene_item(&item);
+                        item
+                    }
+                    Ok(ItemLocation::Catalog(item)) => {
+                        game_state.add_item(&item);
+                        item
+                    }
+                    Ok(ItemLocation::Unknown) => {
+                        return Ok(SessionState::ProcessCreateItemState {
+                            run_id,
+                            tool_call_id,
+                            item,
+                        });
+                    }
+                    Err(reason) => {
+                        let output = json!({ "error": reason }).to_string();
+                        return Ok(SessionState::SubmitToolOutputsState {
+                            run_id,
+                            tool_call_id,
+                            output,
+                        });
+                    }
+                };
+
                 let updated_player_inventory = game_state.get_player_inventory();
+                let objectives_completed =
+                    game_state.record_quest_event(QuestEvent::ItemObtained(&item));
 
-                let output = json!({
+                let mut output = json!({
                     "update_player_inventory": format!("[{}]", updated_player_inventory.join(", "))
-                }).to_string();
+                });
+
+                if !objectives_completed.is_empty() {
+                    output["objectives_completed"] = json!(objectives_completed);
+                }
+
+                let output = output.to_string();
 
-                Ok(SessionState::SubmitToolOutputsState { run_id, tool_call_id, output })
+                Ok(SessionState::SubmitToolOutputsState {
+                    run_id,
+                    tool_call_id,
+                    output,
+                })
             }
-            _ => bail!("Invalid session request for add item processing state: {:?}. Expected ContinueProcessing.", &request),
+            _ => bail!(
+                "Invalid session request for add item processing state: {:?}. Expected ContinueProcessing.",
+                &request
+            ),
         }
     }
 }
+
+/// Where an item the narrator wants to add to the player's inventory can be taken from.
+enum ItemLocation {
+    /// The item is lying in the current scene.
+    Scene(String),
+    /// The item exists in the game, but isn't held by anyone or lying in any scene.
+    Catalog(String),
+    /// The item doesn't exist in the game yet.
+    Unknown,
+}
+
+/// Matches the item name against the items in the current scene, and then the rest of the game.
+/// Returns the reason as an error if the item is somewhere the player can't take it from.
+fn resolve_item(game_state: &GameState, game: &Game, item: &str) -> Result<ItemLocation, String> {
+    let scene_items = game_state
+        .current_scene_name
+        .as_ref()
+        .map(|scene_name| game_state.get_scene_inventory(scene_name))
+        .unwrap_or_default();
+
+    if let Some(item) = FuzzyMatch::find(item, &scene_items) {
+        return Ok(ItemLocation::Scene(item.clone()));
+    }
+
+    if let Some(item) = FuzzyMatch::find(item, &game_state.inventory) {
+        return Err(format!("The player already has the {}.", item));
+    }
+
+    let placed_items = game_state.scene_inventories.values().flatten().chain(
+        game_state
+            .character_save_data
+            .values()
+            .flat_map(|c| c.character_inventory.iter()),
+    );
+
+    if let Some(item) = FuzzyMatch::find(item, placed_items) {
+        return Err(format!("There is no {} in the current scene.", item));
+    }
+
+    match FuzzyMatch::find(item, game.items.iter().map(|i| &i.name)) {
+        Some(item) => Ok(ItemLocation::Catalog(item.clone())),
+        None => Ok(ItemLocation::Unknown),
+    }
+}
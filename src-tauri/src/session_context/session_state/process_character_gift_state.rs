@@ -1,5 +1,6 @@
 use anyhow::{anyhow, bail};
 use log::info;
+use serde_json::json;
 
 use crate::{game_state::GameState, session_context::session_request::SessionRequest};
 
@@ -27,6 +28,30 @@ impl ProcessCharacterGiftState {
                     &to_player_item
                 );
 
+                let character_name = game_state
+                    .character_interaction
+                    .as_ref()
+                    .ok_or(anyhow!("Character interaction not set on game state."))?
+                    .character_name
+                    .clone();
+
+                let check = game_state
+                    .get_character_disposition(&character_name)
+                    .check_gift(&run_id)
+                    .and_then(|_| {
+                        game_state.check_exchange(&character_name, &[to_player_item.clone()], &[])
+                    });
+
+                if let Err(reason) = check {
+                    info!("Refused gift from {}: {}", &character_name, &reason);
+
+                    return Ok(SessionState::CharacterSubmitToolOutputsState {
+                        run_id,
+                        tool_call_id,
+                        output: json!({ "error": reason }).to_string(),
+                    });
+                }
+
                 game_state
                     .character_interaction
                     .as_mut()
@@ -0,0 +1,77 @@
+use anyhow::{anyhow, bail};
+use log::info;
+use serde_json::json;
+
+use crate::{game::Game, game_state::GameState, session_context::session_request::SessionRequest};
+
+use super::SessionState;
+
+pub struct ProcessCharacterGrantPassageState {}
+
+impl ProcessCharacterGrantPassageState {
+    pub async fn process(
+        request: SessionRequest,
+        game_state: &mut GameState,
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+        game: &Game,
+    ) -> Result<SessionState, anyhow::Error> {
+        match request {
+            SessionRequest::ContinueProcessing => {
+                let to_scene_name = arguments["scene"]
+                    .as_str()
+                    .ok_or(anyhow!(
+                        "Actor did not provide scene in grant passage request."
+                    ))?
+                    .to_string();
+
+                let character_name = game_state
+                    .character_interaction
+                    .as_ref()
+                    .ok_or(anyhow!("Character interaction not set on game state."))?
+                    .character_name
+                    .clone();
+
+                let scene_name = game_state
+                    .current_scene_name
+                    .clone()
+                    .ok_or(anyhow!("No current scene set on game state."))?;
+
+                let guarded_exit = game
+                    .scenes
+                    .iter()
+                    .find(|s| s.name == scene_name)
+                    .and_then(|s| s.find_exit(&to_scene_name))
+                    .filter(|e| e.permission_from.as_ref() == Some(&character_name));
+
+                let output = match guarded_exit {
+                    Some(exit) => {
+                        info!(
+                            "{} granted the player passage through {} to {}.",
+                            &character_name, &exit.name, &to_scene_name
+                        );
+
+                        game_state.grant_passage(&scene_name, &to_scene_name);
+
+                        json!({ "result": "granted" }).to_string()
+                    }
+                    None => json!({
+                        "error": format!("You do not guard any exit from {} to {}.", &scene_name, &to_scene_name)
+                    })
+                    .to_string(),
+                };
+
+                Ok(SessionState::CharacterSubmitToolOutputsState {
+                    run_id,
+                    tool_call_id,
+                    output,
+                })
+            }
+            _ => bail!(
+                "Unexpected request received for ProcessCharacterGrantPassageState: {:?}.",
+                request
+            ),
+        }
+    }
+}
@@ -1,87 +1,108 @@
-use anyhow::{bail, anyhow};
-use log::{info, error, trace};
-use openai_lib::{OpenAIClient, assistant::{AssistantClient, CreateAssistantRequest}, model::ChatModel, tool::Tool, thread::{ThreadClient, CreateThreadRequest}};
+use anyhow::{anyhow, bail, Context};
+use log::{error, info, trace};
+use openai_lib::{
+    assistant::{AssistantClient, CreateAssistantRequest},
+    model::ChatModel,
+    thread::{CreateThreadRequest, ThreadClient},
+    tool::Tool,
+    OpenAIClient,
+};
 use serde_json::json;
 
-use crate::{session_context::session_request::SessionRequest, game_state::{GameState, character_interaction::CharacterInteraction, character_save_data::CharacterSaveData, character_profile::CharacterProfile}, game::Game, prompt_builder::PromptBuilder};
+use crate::{
+    game::{Character, Game, SceneExit},
+    game_state::{
+        character_interaction::CharacterInteraction, character_profile::CharacterProfile,
+        character_save_data::CharacterSaveData, journal::QuestEvent, GameState,
+    },
+    prompt_builder::{PromptBuilder, PromptLibrary},
+    session_context::session_request::SessionRequest,
+};
 
 use super::SessionState;
 
+/// The most characters the player can talk to at once.
+const MAX_GROUP_SIZE: usize = 4;
+
 pub struct ProcessCharacterInteractState {}
 
 impl ProcessCharacterInteractState {
-    pub async fn process(request: SessionRequest, openai_client: &OpenAIClient, game_state: &mut GameState, run_id: String, tool_call_id: String, arguments: serde_json::Value, game: &Game) -> Result<SessionState, anyhow::Error> {
+    pub async fn process(
+        request: SessionRequest,
+        openai_client: &OpenAIClient,
+        game_state: &mut GameState,
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+        game: &Game,
+    ) -> Result<SessionState, anyhow::Error> {
         match request {
             SessionRequest::ContinueProcessing => {
-                
-                let character_name = arguments["character"].as_str().ok_or(anyhow!("Unable to interpret arguments for character_interact function."))?.to_string();
-
-                let character_name = {
-                    if character_name.contains(":") {
-                        character_name.split(":").next().ok_or(anyhow!("Narrator prefixed character name with colon."))?.to_string()
-                    } else {
-                        character_name
-                    }
+                let character_names = match (arguments["characters"].as_array(), arguments["character"].as_str()) {
+                    (Some(characters), _) => characters
+                        .iter()
+                        .filter_map(|c| c.as_str())
+                        .map(String::from)
+                        .collect::<Vec<_>>(),
+                    (None, Some(character)) => vec![character.to_string()],
+                    (None, None) => bail!("Unable to interpret arguments for character_interact function."),
                 };
 
-                let character = game
-                    .characters
-                    .iter()
-                    .find(|c| c.name == character_name)
-                    .ok_or(anyhow!("Unable to find character with provided name."))?;
+                let characters = match find_characters(game_state, game, &character_names) {
+                    Ok(characters) => characters,
+                    Err(reason) => {
+                        let output = json!({ "error": reason }).to_string();
 
-                // TODO: Make sure character is in the active scene.
+                        info!("Rejected character interaction: {}", &output);
 
-                info!(
-                    "Found character information for {}. Generating profile and context for assistant.",
-                    &character_name
-                );
+                        return Ok(SessionState::SubmitToolOutputsState {
+                            run_id,
+                            tool_call_id,
+                            output,
+                        });
+                    }
+                };
 
-                let profile = CharacterProfile::from_character(&character)?;
-                let profile = serde_json::to_string(&profile)?;
-
-                let character_save_data = game_state.character_save_data.entry(character.name.clone())
-                    .or_insert(CharacterSaveData::new(character.inventory.clone()));
-
-                let additional_context = json!({
-                    "previous_conversations": &character_save_data.previous_conversations,
-                    "character_inventory": &character_save_data.character_inventory,
-                })
-                .to_string();
-
-                let instructions = PromptBuilder::new()
-                    .add_prompt("./prompts/character_actor/main.txt")
-                    .add_plain_text(&profile)
-                    .add_plain_text(&additional_context)
-                    .build();
-
-                info!("Loaded instructions for character assistant.");
-                trace!("{}", &instructions);
-
-                info!("Creating character assistant.");
-
-                let assistant_response = openai_client
-                    .create_assistant(
-                    CreateAssistantRequest::builder()
-                        .instructions(instructions)
-                        .model(ChatModel::Gpt_35_Turbo_1106)
-                        .add_tool(Tool::function().from_file("./prompts/character_actor/give_function.json")?)
-                            .add_tool(Tool::function().from_file("./prompts/character_actor/trade_function.json")?)
-                            .build())
-                    .await
-                    .map_err(|e| anyhow!("Failed to create assistant for character: {}", e))?;
+                for character in &characters {
+                    game_state.record_quest_event(QuestEvent::CharacterMet(&character.name));
+                }
 
-                let character_assistant_id = assistant_response.id;
+                let mut builder = CharacterInteraction::builder();
+                let mut assistant_ids = Vec::new();
 
-                info!(
-                    "Created assistant with id {} for character.",
-                    &character_assistant_id
-                );
+                for character in &characters {
+                    let partners = characters
+                        .iter()
+                        .filter(|c| c.name != character.name)
+                        .map(|c| c.name.clone())
+                        .collect::<Vec<_>>();
 
-                let thread_response = openai_client.create_thread(CreateThreadRequest::builder().build()).await.map_err(|e| {
-                    error!("Failed to create thread for character assistant:\n{:?}", e);
-                    anyhow!("Failed to start thread.")
-                })?;
+                    let assistant_id =
+                        match create_assistant(openai_client, game_state, game, character, partners)
+                            .await
+                        {
+                            Ok(assistant_id) => assistant_id,
+                            Err(e) => {
+                                delete_assistants(openai_client, &assistant_ids).await;
+                                return Err(e);
+                            }
+                        };
+
+                    builder = builder.add_participant(&character.name, &assistant_id);
+                    assistant_ids.push(assistant_id);
+                }
+
+                let thread_response = match openai_client
+                    .create_thread(CreateThreadRequest::builder().build())
+                    .await
+                {
+                    Ok(thread_response) => thread_response,
+                    Err(e) => {
+                        error!("Failed to create thread for character assistant:\n{:?}", e);
+                        delete_assistants(openai_client, &assistant_ids).await;
+                        bail!("Failed to start thread.");
+                    }
+                };
 
                 let thread_id = thread_response.id;
 
@@ -90,9 +111,7 @@ impl ProcessCharacterInteractState {
                     &thread_id
                 );
 
-                let character_interaction = CharacterInteraction::builder()
-                    .character_name(&character.name)
-                    .assistant_id(&character_assistant_id)
+                let character_interaction = builder
                     .thread_id(&thread_id)
                     .initiating_run_id(&run_id)
                     .initiating_tool_call_id(&tool_call_id)
@@ -106,3 +125,165 @@ impl ProcessCharacterInteractState {
         }
     }
 }
+
+/// Finds the characters the narrator asked to talk to, returning the reason as an error if any of
+/// them can't be found or aren't in the current scene.
+fn find_characters<'a>(
+    game_state: &GameState,
+    game: &'a Game,
+    character_names: &[String],
+) -> Result<Vec<&'a Character>, String> {
+    if character_names.is_empty() {
+        return Err(String::from("No characters were provided."));
+    }
+
+    if character_names.len() > MAX_GROUP_SIZE {
+        return Err(format!(
+            "The player can talk to at most {} characters at once.",
+            MAX_GROUP_SIZE
+        ));
+    }
+
+    let mut characters: Vec<&Character> = Vec::new();
+
+    for character_name in character_names {
+        // The narrator sometimes includes the character's description after their name
+        let character_name = Character::name_from_summary(character_name);
+
+        let character = game
+            .characters
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(&character_name))
+            .ok_or(format!("There is no character named {}.", &character_name))?;
+
+        if let Some(current_scene_name) = &game_state.current_scene_name {
+            if game_state.character_scene(game, &character.name).as_ref()
+                != Some(current_scene_name)
+            {
+                let characters_present = game_state
+                    .characters_in_scene(game, current_scene_name)
+                    .iter()
+                    .map(|c| c.name.clone())
+                    .collect::<Vec<_>>();
+
+                return Err(format!(
+                    "{} is not in {}. The characters here are: [{}].",
+                    &character.name,
+                    current_scene_name,
+                    characters_present.join(", ")
+                ));
+            }
+        }
+
+        if !characters.iter().any(|c| c.name == character.name) {
+            characters.push(character);
+        }
+    }
+
+    Ok(characters)
+}
+
+/// Deletes the assistants created for a conversation that couldn't be started, so they aren't left
+/// behind. Failures are only logged, since the conversation has already failed.
+async fn delete_assistants(openai_client: &OpenAIClient, assistant_ids: &[String]) {
+    for assistant_id in assistant_ids {
+        if let Err(e) = openai_client.delete_assistant(assistant_id).await {
+            error!(
+                "Failed to delete character assistant {}:\n{:?}",
+                assistant_id, e
+            );
+        }
+    }
+}
+
+/// Creates the assistant that acts as a character. `partners` are the other characters in the
+/// conversation, if it's a group conversation.
+async fn create_assistant(
+    openai_client: &OpenAIClient,
+    game_state: &mut GameState,
+    game: &Game,
+    character: &Character,
+    partners: Vec<String>,
+) -> Result<String, anyhow::Error> {
+    info!(
+        "Found character information for {}. Generating profile and context for assistant.",
+        &character.name
+    );
+
+    let profile = CharacterProfile::from_character(character)?;
+    let profile = serde_json::to_string(&profile)?;
+
+    let guarded_exits = game_state
+        .current_scene_name
+        .as_ref()
+        .and_then(|scene_name| game.scenes.iter().find(|s| &s.name == scene_name))
+        .map(|scene| {
+            scene
+                .exits
+                .iter()
+                .filter(|e| e.permission_from.as_ref() == Some(&character.name))
+                .cloned()
+                .collect::<Vec<SceneExit>>()
+        })
+        .unwrap_or_default();
+
+    let character_save_data = game_state
+        .character_save_data
+        .entry(character.name.clone())
+        .or_insert(CharacterSaveData::new(character.inventory.clone()));
+
+    let mut additional_context = json!({
+        "previous_conversations": &character_save_data.previous_conversations,
+        "character_inventory": &character_save_data.character_inventory,
+        "guarded_exits": guarded_exits,
+        "disposition": &character_save_data.disposition,
+        "attitude": character_save_data.disposition.attitude(),
+    });
+
+    if !partners.is_empty() {
+        additional_context["conversation_partners"] = json!(partners);
+    }
+
+    let instructions = PromptBuilder::new()
+        .add_prompt("character_actor/main.txt")
+        .set_variable("character_profile", profile)
+        .set_variable("additional_context", additional_context.to_string())
+        .build()
+        .context("Failed to build instructions for character assistant.")?;
+
+    info!("Loaded instructions for character assistant.");
+    trace!("{}", &instructions);
+
+    info!("Creating character assistant.");
+
+    let assistant_response = openai_client
+        .create_assistant(
+            CreateAssistantRequest::builder()
+                .instructions(instructions)
+                .model(ChatModel::Gpt_35_Turbo_1106)
+                .add_tool(
+                    Tool::function()
+                        .from_json(&PromptLibrary::read("character_actor/give_function.json")?)?,
+                )
+                .add_tool(
+                    Tool::function()
+                        .from_json(&PromptLibrary::read("character_actor/trade_function.json")?)?,
+                )
+                .add_tool(Tool::function().from_json(&PromptLibrary::read(
+                    "character_actor/grant_passage_function.json",
+                )?)?)
+                .add_tool(Tool::function().from_json(&PromptLibrary::read(
+                    "character_actor/update_disposition_function.json",
+                )?)?)
+                .build(),
+        )
+        .await
+        .map_err(|e| anyhow!("Failed to create assistant for character: {}", e))?;
+
+    info!(
+        "Created assistant with id {} for character.",
+        &assistant_response.id
+    );
+
+    Ok(assistant_response.id)
+}
@@ -1,7 +1,11 @@
 use anyhow::{anyhow, bail};
 use log::info;
+use serde_json::json;
 
-use crate::{game_state::GameState, session_context::session_request::SessionRequest};
+use crate::{
+    game_state::{journal::QuestEvent, GameState},
+    session_context::session_request::SessionRequest,
+};
 
 use super::SessionState;
 
@@ -17,29 +21,92 @@ impl ProcessCharacterTradeState {
     ) -> Result<SessionState, anyhow::Error> {
         match request {
             SessionRequest::ContinueProcessing => {
-                let to_player_item = arguments["your_item"]
-                    .as_str()
-                    .ok_or(anyhow!(
-                        "Actor did not provide trade item in trade request."
-                    ))?
-                    .to_string();
-                let from_player_item = arguments["player_item"]
-                    .as_str()
-                    .ok_or(anyhow!(
-                        "Actor did not provide trade item in trade request."
-                    ))?
-                    .to_string();
+                let item_list = |name: &str| {
+                    arguments[name]
+                        .as_array()
+                        .map(|items| {
+                            items
+                                .iter()
+                                .filter_map(|i| i.as_str())
+                                .map(String::from)
+                                .collect::<Vec<_>>()
+                        })
+                        .ok_or(anyhow!(
+                            "Actor did not provide trade items in trade request."
+                        ))
+                };
+
+                let to_player_items = item_list("your_items")?;
+                let from_player_items = item_list("player_items")?;
 
                 info!(
-                    "Character requested to trade {} for the player's {}",
-                    to_player_item, from_player_item
+                    "Character requested to trade {:?} for the player's {:?}",
+                    to_player_items, from_player_items
                 );
 
-                game_state
+                let character_name = game_state
                     .character_interaction
-                    .as_mut()
+                    .as_ref()
                     .ok_or(anyhow!("Character interaction not set on game state."))?
-                    .propose_trade(&to_player_item, &from_player_item);
+                    .character_name
+                    .clone();
+
+                if let Err(reason) = check_trade(
+                    game_state,
+                    &character_name,
+                    &run_id,
+                    &to_player_items,
+                    &from_player_items,
+                ) {
+                    info!("Refused trade from {}: {}", &character_name, &reason);
+
+                    return Ok(SessionState::CharacterSubmitToolOutputsState {
+                        run_id,
+                        tool_call_id,
+                        output: json!({ "error": reason }).to_string(),
+                    });
+                }
+
+                let character_interaction = game_state
+                    .character_interaction
+                    .as_mut()
+                    .ok_or(anyhow!("Character interaction not set on game state."))?;
+
+                // Offering back the player's counter-offer accepts it, so it doesn't need to be
+                // confirmed again
+                if character_interaction.is_agreed(&to_player_items, &from_player_items) {
+                    character_interaction.counter_offer = None;
+
+                    info!("{} accepted the player's counter-offer.", &character_name);
+
+                    let output = match game_state.exchange_items(
+                        &character_name,
+                        &to_player_items,
+                        &from_player_items,
+                    ) {
+                        Ok(_) => {
+                            for item in &to_player_items {
+                                game_state.record_quest_event(QuestEvent::ItemObtained(item));
+                            }
+
+                            json!({
+                                "player_response": "accept",
+                                "updated_player_inventory": game_state.get_player_inventory(),
+                                "updated_character_inventory": game_state.get_character_inventory(&character_name),
+                            })
+                        }
+                        Err(reason) => json!({ "error": reason }),
+                    }
+                    .to_string();
+
+                    return Ok(SessionState::CharacterSubmitToolOutputsState {
+                        run_id,
+                        tool_call_id,
+                        output,
+                    });
+                }
+
+                character_interaction.propose_trade(to_player_items, from_player_items);
 
                 Ok(SessionState::AwaitingPlayerTradeResponseState {
                     run_id,
@@ -53,3 +120,27 @@ impl ProcessCharacterTradeState {
         }
     }
 }
+
+/// Checks that the character is willing to trade and that both sides have the items they're
+/// offering, returning the reason as an error if not.
+fn check_trade(
+    game_state: &mut GameState,
+    character_name: &str,
+    run_id: &str,
+    to_player_items: &[String],
+    from_player_items: &[String],
+) -> Result<(), String> {
+    if to_player_items.is_empty() || from_player_items.is_empty() {
+        return Err(String::from(
+            "A trade needs at least one item from each side. Use give_item to give the player something for free.",
+        ));
+    }
+
+    game_state
+        .get_character_disposition(character_name)
+        .check_trade(run_id)?;
+
+    game_state.check_exchange(character_name, to_player_items, from_player_items)?;
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use anyhow::{anyhow, bail, Context};
+use log::info;
+use serde_json::json;
+
+use crate::{
+    game_state::{disposition::DispositionChange, GameState},
+    session_context::session_request::SessionRequest,
+};
+
+use super::SessionState;
+
+pub struct ProcessCharacterUpdateDispositionState {}
+
+impl ProcessCharacterUpdateDispositionState {
+    pub async fn process(
+        request: SessionRequest,
+        game_state: &mut GameState,
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+    ) -> Result<SessionState, anyhow::Error> {
+        match request {
+            SessionRequest::ContinueProcessing => {
+                let change = serde_json::from_value::<DispositionChange>(arguments)
+                    .context("Actor provided invalid arguments to update disposition.")?;
+
+                let character_name = game_state
+                    .character_interaction
+                    .as_ref()
+                    .ok_or(anyhow!("Character interaction not set on game state."))?
+                    .character_name
+                    .clone();
+
+                let disposition = game_state.get_character_disposition(&character_name);
+
+                if let Err(reason) = disposition.apply(&change, &run_id) {
+                    info!(
+                        "Refused disposition update from {}: {}",
+                        &character_name, &reason
+                    );
+
+                    return Ok(SessionState::CharacterSubmitToolOutputsState {
+                        run_id,
+                        tool_call_id,
+                        output: json!({ "error": reason }).to_string(),
+                    });
+                }
+
+                info!(
+                    "Updated disposition of {} towards the player: {:?}",
+                    &character_name, disposition
+                );
+
+                let output = json!({
+                    "trust": disposition.trust,
+                    "affection": disposition.affection,
+                    "fear": disposition.fear,
+                    "attitude": disposition.attitude(),
+                })
+                .to_string();
+
+                Ok(SessionState::CharacterSubmitToolOutputsState {
+                    run_id,
+                    tool_call_id,
+                    output,
+                })
+            }
+            _ => bail!(
+                "Unexpected request received for ProcessCharacterUpdateDispositionState: {:?}.",
+                request
+            ),
+        }
+    }
+}
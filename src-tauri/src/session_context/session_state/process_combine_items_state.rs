@@ -0,0 +1,112 @@
+use anyhow::{anyhow, bail};
+use log::info;
+use serde_json::json;
+
+use crate::{
+    game::Game, game_state::GameState, session_context::session_request::SessionRequest,
+    utils::fuzzy_match::FuzzyMatch,
+};
+
+use super::SessionState;
+
+pub struct ProcessCombineItemsState {}
+
+impl ProcessCombineItemsState {
+    pub async fn process(
+        request: SessionRequest,
+        game_state: &mut GameState,
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+        game: &Game,
+    ) -> Result<SessionState, anyhow::Error> {
+        match request {
+            SessionRequest::ContinueProcessing => {
+                let (item, other_item) = match (
+                    arguments["item"].as_str(),
+                    arguments["other_item"].as_str(),
+                ) {
+                    (Some(item), Some(other_item)) => (item, other_item),
+                    _ => return Err(anyhow!("Invalid arguments to combine_items function.")),
+                };
+
+                match combine_items(game_state, game, item, other_item) {
+                    Ok(result) => {
+                        info!("Combined {} and {} into {}.", item, other_item, &result);
+
+                        // The result is added like any other item, so it's created if it's new
+                        Ok(SessionState::ProcessAddItemState {
+                            run_id,
+                            tool_call_id,
+                            arguments: json!({ "item": result }),
+                        })
+                    }
+                    Err(reason) => {
+                        let output = json!({ "error": reason }).to_string();
+
+                        info!("Processed combine_items function with output: {}", &output);
+
+                        Ok(SessionState::SubmitToolOutputsState {
+                            run_id,
+                            tool_call_id,
+                            output,
+                        })
+                    }
+                }
+            }
+            _ => bail!(
+                "Invalid session request for combine items processing state: {:?}. Expected ContinueProcessing.",
+                &request
+            ),
+        }
+    }
+}
+
+/// Removes two items from the player's inventory if they can be combined, returning the name of
+/// the item they make. Returns the reason as an error if they can't be combined.
+fn combine_items(
+    game_state: &mut GameState,
+    game: &Game,
+    item_name: &str,
+    other_item_name: &str,
+) -> Result<String, String> {
+    let missing_item =
+        |name: &str| format!("Player does not have any {} in their inventory.", name);
+
+    let mut inventory = game_state.get_player_inventory();
+
+    let item = FuzzyMatch::find(item_name, &inventory)
+        .cloned()
+        .ok_or(missing_item(item_name))?;
+    if let Some(index) = inventory.iter().position(|i| i == &item) {
+        inventory.remove(index);
+    }
+
+    let other_item = FuzzyMatch::find(other_item_name, &inventory)
+        .cloned()
+        .ok_or(missing_item(other_item_name))?;
+
+    let properties = |name: &str| {
+        game.items
+            .iter()
+            .find(|i| i.name == name)
+            .map(|i| i.properties.clone())
+            .unwrap_or_default()
+    };
+
+    let result = properties(&item)
+        .combination_with(&other_item)
+        .or(properties(&other_item).combination_with(&item))
+        .map(|combination| combination.result.clone())
+        .ok_or(format!(
+            "The {} and {} can't be combined.",
+            item, other_item
+        ))?;
+
+    game_state.remove_item(&item).map_err(|e| e.to_string())?;
+    game_state
+        .remove_item(&other_item)
+        .map_err(|e| e.to_string())?;
+
+    Ok(result)
+}
@@ -0,0 +1,59 @@
+use anyhow::{anyhow, bail};
+use log::info;
+use serde_json::json;
+
+use crate::{game_state::GameState, session_context::session_request::SessionRequest};
+
+use super::SessionState;
+
+pub struct ProcessCompleteObjectiveState {}
+
+impl ProcessCompleteObjectiveState {
+    pub async fn process(
+        request: SessionRequest,
+        game_state: &mut GameState,
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+    ) -> Result<SessionState, anyhow::Error> {
+        match request {
+            SessionRequest::ContinueProcessing => {
+                let objective_id = arguments["objective_id"]
+                    .as_str()
+                    .ok_or(anyhow!(
+                        "Unable to interpret arguments for complete_objective function."
+                    ))?
+                    .to_string();
+
+                let output = match game_state.journal.complete(&objective_id) {
+                    Ok(title) => {
+                        game_state.add_system_message(&format!("Objective completed: {}", title));
+
+                        json!({
+                            "success": true,
+                            "active_objectives": game_state.journal.active_titles(),
+                            "game_won": game_state.journal.is_won(),
+                        })
+                    }
+                    Err(reason) => json!({ "error": reason }),
+                }
+                .to_string();
+
+                info!(
+                    "Processed complete_objective function with output: {}",
+                    &output
+                );
+
+                Ok(SessionState::SubmitToolOutputsState {
+                    run_id,
+                    tool_call_id,
+                    output,
+                })
+            }
+            _ => bail!(
+                "Invalid session request for complete objective processing state: {:?}. Expected ContinueProcessing.",
+                &request
+            ),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use anyhow::bail;
+use log::{error, info};
+use openai_lib::OpenAIClient;
+use serde_json::json;
+
+use crate::{
+    file_manager::FileManager,
+    game::{Game, GameFactory},
+    session_context::session_request::SessionRequest,
+};
+
+use super::SessionState;
+
+pub struct ProcessCreateItemState {}
+
+impl ProcessCreateItemState {
+    pub async fn process(
+        request: SessionRequest,
+        openai_client: &OpenAIClient,
+        file_manager: &FileManager,
+        run_id: String,
+        tool_call_id: String,
+        item: String,
+        game: &mut Game,
+    ) -> Result<SessionState, anyhow::Error> {
+        match request {
+            SessionRequest::ContinueProcessing => {
+                info!("Creating new item {} requested by narrator.", &item);
+
+                let created = match GameFactory::load(&game.id, openai_client, file_manager) {
+                    Ok(game_factory) => game_factory.create_item(game, &item).await,
+                    Err(e) => Err(e),
+                };
+
+                match created {
+                    Ok(item) => Ok(SessionState::ProcessAddItemState {
+                        run_id,
+                        tool_call_id,
+                        arguments: json!({ "item": item.name }),
+                    }),
+                    Err(e) => {
+                        error!("Unable to create item {}: {:?}", &item, e);
+
+                        let output = json!({
+                            "error": format!("The player is unable to find any {}.", &item)
+                        })
+                        .to_string();
+
+                        Ok(SessionState::SubmitToolOutputsState {
+                            run_id,
+                            tool_call_id,
+                            output,
+                        })
+                    }
+                }
+            }
+            _ => bail!(
+                "Invalid session request for create item processing state: {:?}. Expected ContinueProcessing.",
+                &request
+            ),
+        }
+    }
+}
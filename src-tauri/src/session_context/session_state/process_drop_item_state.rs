@@ -0,0 +1,57 @@
+use anyhow::{anyhow, bail};
+use log::info;
+use serde_json::json;
+
+use crate::{game_state::GameState, session_context::session_request::SessionRequest};
+
+use super::SessionState;
+
+pub struct ProcessDropItemState {}
+
+impl ProcessDropItemState {
+    pub async fn process(
+        request: SessionRequest,
+        game_state: &mut GameState,
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+    ) -> Result<SessionState, anyhow::Error> {
+        match request {
+            SessionRequest::ContinueProcessing => {
+                let item = arguments["item"]
+                    .as_str()
+                    .ok_or(anyhow!("Invalid arguments to drop_item function."))?
+                    .to_string();
+
+                let output = match game_state.drop_item(&item) {
+                    Ok(_) => {
+                        let scene_items = game_state
+                            .current_scene_name
+                            .as_ref()
+                            .map(|scene_name| game_state.get_scene_inventory(scene_name))
+                            .unwrap_or_default();
+
+                        json!({
+                            "updated_player_inventory": format!("[{}]", game_state.get_player_inventory().join(", ")),
+                            "scene_items": scene_items,
+                        })
+                    }
+                    Err(reason) => json!({ "error": reason }),
+                }
+                .to_string();
+
+                info!("Processed drop_item function with output: {}", &output);
+
+                Ok(SessionState::SubmitToolOutputsState {
+                    run_id,
+                    tool_call_id,
+                    output,
+                })
+            }
+            _ => bail!(
+                "Invalid session request for drop item processing state: {:?}. Expected ContinueProcessing.",
+                &request
+            ),
+        }
+    }
+}
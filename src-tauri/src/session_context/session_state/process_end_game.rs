@@ -18,7 +18,6 @@ impl ProcessEndGameState {
     ) -> Result<SessionState, anyhow::Error> {
         match request {
             SessionRequest::ContinueProcessing => {
-
                 let reason = arguments["reason"]
                     .as_str()
                     .ok_or(anyhow!(
@@ -26,14 +25,40 @@ impl ProcessEndGameState {
                     ))?
                     .to_string();
 
-                game_state.end_game = Some(reason);
-                let output = json!({ "success": "true" }).to_string();
+                let won = arguments["won"].as_bool().unwrap_or(false);
+
+                let mut output = match won && !game_state.journal.is_won() {
+                    true => json!({
+                        "error": format!(
+                            "The player has not completed the quest yet. Remaining objectives: {}.",
+                            game_state.journal.active_titles().join(", ")
+                        )
+                    }),
+                    false => {
+                        game_state.end_game = Some(reason);
+                        json!({ "success": "true" })
+                    }
+                };
+
+                // A defeat from modify_stat answers that call, so its change is passed on too
+                if !arguments["stat_change"].is_null() {
+                    output["stat_change"] = arguments["stat_change"].clone();
+                }
+
+                let output = output.to_string();
 
                 info!("Processed end_game function with output: {}", &output);
 
-                Ok(SessionState::SubmitToolOutputsState { run_id, tool_call_id, output })
+                Ok(SessionState::SubmitToolOutputsState {
+                    run_id,
+                    tool_call_id,
+                    output,
+                })
             }
-            _ => bail!("Invalid session request for add item processing state: {:?}. Expected ContinueProcessing.", &request),
+            _ => bail!(
+                "Invalid session request for end game processing state: {:?}. Expected ContinueProcessing.",
+                &request
+            ),
         }
     }
 }
@@ -0,0 +1,45 @@
+use anyhow::{anyhow, bail};
+use log::info;
+
+use crate::{
+    game::Game,
+    game_state::{lore::Lore, GameState},
+    session_context::session_request::SessionRequest,
+};
+
+use super::SessionState;
+
+pub struct ProcessExamineCharacterState {}
+
+impl ProcessExamineCharacterState {
+    pub async fn process(
+        request: SessionRequest,
+        game: &Game,
+        game_state: &GameState,
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+    ) -> Result<SessionState, anyhow::Error> {
+        match request {
+            SessionRequest::ContinueProcessing => {
+                let character = arguments["character"].as_str().ok_or(anyhow!(
+                    "Unable to interpret arguments for examine_character function."
+                ))?;
+
+                let output = Lore::examine_character(game, game_state, character).to_string();
+
+                info!("Processed examine_character function with output: {}", &output);
+
+                Ok(SessionState::SubmitToolOutputsState {
+                    run_id,
+                    tool_call_id,
+                    output,
+                })
+            }
+            _ => bail!(
+                "Invalid session request for examine character processing state: {:?}. Expected ContinueProcessing.",
+                &request
+            ),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use anyhow::bail;
+use log::info;
+use serde_json::json;
+
+use crate::{game_state::GameState, session_context::session_request::SessionRequest};
+
+use super::SessionState;
+
+pub struct ProcessModifyStatState {}
+
+impl ProcessModifyStatState {
+    pub async fn process(
+        request: SessionRequest,
+        game_state: &mut GameState,
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+    ) -> Result<SessionState, anyhow::Error> {
+        match request {
+            SessionRequest::ContinueProcessing => {
+                let (stat, amount) =
+                    match (arguments["stat"].as_str(), arguments["amount"].as_i64()) {
+                        (Some(stat), Some(amount)) => (stat.to_string(), amount as i32),
+                        _ => bail!("Unable to interpret arguments for modify_stat function."),
+                    };
+                let reason = arguments["reason"].as_str().unwrap_or_default().to_string();
+
+                let previous_value = game_state.stats.get(&stat).map(|s| s.value);
+
+                let output = match game_state.stats.modify(&stat, amount) {
+                    Ok(stat) => {
+                        // The stat is clamped to its range, so the change can be less than asked
+                        let change = stat.value - previous_value.unwrap_or(stat.value);
+                        let output = json!({
+                            "stat": &stat.name,
+                            "change": change,
+                            "value": stat.value,
+                            "max_value": stat.max_value,
+                        });
+                        let message = format!(
+                            "{} {:+} ({}/{})",
+                            &stat.name, change, stat.value, stat.max_value
+                        );
+
+                        game_state.add_system_message(&message);
+
+                        output
+                    }
+                    Err(reason) => json!({ "error": reason }),
+                };
+
+                info!("Processed modify_stat function with output: {}", &output);
+
+                if let Some(stat) = game_state.stats.defeated_by() {
+                    info!("Player was defeated by {} reaching zero.", &stat.name);
+
+                    let reason = format!("{} The player's {} has run out.", reason, &stat.name);
+
+                    return Ok(SessionState::ProcessEndGameState {
+                        run_id,
+                        tool_call_id,
+                        arguments: json!({
+                            "reason": reason.trim(),
+                            "won": false,
+                            "stat_change": output,
+                        }),
+                    });
+                }
+
+                let output = output.to_string();
+
+                Ok(SessionState::SubmitToolOutputsState {
+                    run_id,
+                    tool_call_id,
+                    output,
+                })
+            }
+            _ => bail!(
+                "Invalid session request for modify stat processing state: {:?}. Expected ContinueProcessing.",
+                &request
+            ),
+        }
+    }
+}
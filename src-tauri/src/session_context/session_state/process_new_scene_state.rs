@@ -2,7 +2,11 @@ use anyhow::{anyhow, bail};
 use log::info;
 use serde_json::json;
 
-use crate::{game::Game, game_state::GameState, session_context::session_request::SessionRequest};
+use crate::{
+    game::{Character, Game, Scene},
+    game_state::{journal::QuestEvent, GameState},
+    session_context::session_request::SessionRequest,
+};
 
 use super::SessionState;
 
@@ -26,36 +30,70 @@ impl ProcessNewSceneState {
                     .as_str()
                     .ok_or(anyhow!("Arguments provided in invalid format."))?
                     .to_string();
-                let new_scene = game
-                    .scenes
-                    .iter()
-                    .find(|s| s.name == new_scene)
-                    .ok_or(anyhow!("Invalid scene name provided: {}.", new_scene))?;
+
+                let new_scene = match validate_move(game_state, game, &new_scene) {
+                    Ok(new_scene) => new_scene,
+                    Err(reason) => {
+                        info!("Rejected scene change: {}", &reason);
+
+                        let output = json!({ "error": reason }).to_string();
+
+                        return Ok(SessionState::SubmitToolOutputsState {
+                            run_id,
+                            tool_call_id,
+                            output,
+                        });
+                    }
+                };
 
                 info!("Updating scene to {} in game state.", &new_scene.name);
+                let previous_scene_name = game_state.current_scene_name.clone();
                 game_state.new_scene(&new_scene.name);
 
-                let characters = new_scene
-                    .characters
+                let followers = match previous_scene_name {
+                    Some(previous_scene_name) if previous_scene_name != new_scene.name => {
+                        game_state.move_followers(game, &previous_scene_name, &new_scene.name)
+                    }
+                    _ => vec![],
+                };
+
+                let objectives_completed =
+                    game_state.record_quest_event(QuestEvent::SceneEntered(&new_scene.name));
+
+                let mut characters = game_state
+                    .characters_in_scene(game, &new_scene.name)
                     .iter()
-                    .map(
-                        |ch1| match game.characters.iter().find(|ch2| ch1.eq(&ch2.name)) {
-                            Some(c) => {
-                                format!("{}: {}", c.name, c.short_description)
-                            }
-                            None => format!("{}: a stranger", ch1),
-                        },
-                    )
+                    .map(|c| format!("{}: {}", c.name, c.short_description))
                     .collect::<Vec<String>>();
 
-                let output = json!({
+                // Characters listed in the scene that were never generated
+                characters.extend(
+                    new_scene
+                        .characters
+                        .iter()
+                        .map(|c| Character::name_from_summary(c))
+                        .filter(|name| !game.characters.iter().any(|c| &c.name == name))
+                        .map(|name| format!("{}: a stranger", name)),
+                );
+
+                let mut output = json!({
                     "name": new_scene.name.clone(),
                     "narrative": new_scene.narrative.clone(),
                     "metadata": new_scene.metadata.clone(),
                     "characters": characters,
-                    "items": new_scene.items.clone() // TODO: This should be the saved scene items
-                    // from game_state
-                }).to_string();
+                    "items": game_state.get_scene_inventory(&new_scene.name),
+                    "exits": new_scene.exits.clone(),
+                });
+
+                if !followers.is_empty() {
+                    output["followed_by"] = json!(followers);
+                }
+
+                if !objectives_completed.is_empty() {
+                    output["objectives_completed"] = json!(objectives_completed);
+                }
+
+                let output = output.to_string();
 
                 Ok(SessionState::SubmitToolOutputsState { run_id, tool_call_id, output })
             }
@@ -63,3 +101,85 @@ impl ProcessNewSceneState {
         }
     }
 }
+
+/// Checks that the player can move from their current scene to the requested scene, returning the
+/// reason the move isn't allowed so that it can be passed back to the narrator.
+fn validate_move<'a>(
+    game_state: &GameState,
+    game: &'a Game,
+    new_scene_name: &str,
+) -> Result<&'a Scene, String> {
+    let new_scene = game
+        .scenes
+        .iter()
+        .find(|s| s.name == new_scene_name)
+        .ok_or(format!(
+            "There is no scene named {} in this game.",
+            new_scene_name
+        ))?;
+
+    let current_scene_name = match &game_state.current_scene_name {
+        Some(current_scene_name) => current_scene_name,
+        None => {
+            let first_scene = game
+                .scenes
+                .first()
+                .ok_or(String::from("This game has no scenes."))?;
+
+            return match first_scene.name == new_scene.name {
+                true => Ok(new_scene),
+                false => Err(format!(
+                    "The game must begin in the first scene, {}.",
+                    &first_scene.name
+                )),
+            };
+        }
+    };
+
+    if current_scene_name == &new_scene.name {
+        return Ok(new_scene);
+    }
+
+    let current_scene = game
+        .scenes
+        .iter()
+        .find(|s| &s.name == current_scene_name)
+        .ok_or(format!(
+            "The current scene {} could not be found.",
+            current_scene_name
+        ))?;
+
+    // Games generated before scenes had exits allow moving freely between scenes
+    if game.scenes.iter().all(|s| s.exits.is_empty()) {
+        return Ok(new_scene);
+    }
+
+    if current_scene.exits.is_empty() {
+        return Err(format!(
+            "{} cannot be reached from {}, no exits lead from here.",
+            &new_scene.name, current_scene_name
+        ));
+    }
+
+    let exit = current_scene.find_exit(&new_scene.name).ok_or_else(|| {
+        let exits = current_scene
+            .exits
+            .iter()
+            .map(|e| format!("{} ({})", e.scene, e.name))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        format!(
+            "{} cannot be reached from {}. The exits from here lead to: {}.",
+            &new_scene.name, current_scene_name, exits
+        )
+    })?;
+
+    exit.check_access(
+        &game_state.inventory,
+        game_state.is_exit_unlocked(current_scene_name, &new_scene.name),
+        game_state.has_passage(current_scene_name, &new_scene.name),
+    )?;
+
+    Ok(new_scene)
+}
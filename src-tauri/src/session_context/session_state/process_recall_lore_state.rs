@@ -0,0 +1,45 @@
+use anyhow::{anyhow, bail};
+use log::info;
+
+use crate::{
+    game::Game,
+    game_state::{lore::Lore, GameState},
+    session_context::session_request::SessionRequest,
+};
+
+use super::SessionState;
+
+pub struct ProcessRecallLoreState {}
+
+impl ProcessRecallLoreState {
+    pub async fn process(
+        request: SessionRequest,
+        game: &Game,
+        game_state: &GameState,
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+    ) -> Result<SessionState, anyhow::Error> {
+        match request {
+            SessionRequest::ContinueProcessing => {
+                let topic = arguments["topic"].as_str().ok_or(anyhow!(
+                    "Unable to interpret arguments for recall_lore function."
+                ))?;
+
+                let output = Lore::recall(game, game_state, topic).to_string();
+
+                info!("Processed recall_lore function with output: {}", &output);
+
+                Ok(SessionState::SubmitToolOutputsState {
+                    run_id,
+                    tool_call_id,
+                    output,
+                })
+            }
+            _ => bail!(
+                "Invalid session request for recall lore processing state: {:?}. Expected ContinueProcessing.",
+                &request
+            ),
+        }
+    }
+}
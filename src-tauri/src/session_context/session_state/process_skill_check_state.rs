@@ -0,0 +1,54 @@
+use anyhow::{anyhow, bail};
+use log::info;
+use serde_json::json;
+
+use crate::{
+    game_state::{skill_check::SkillCheck, GameState},
+    session_context::session_request::SessionRequest,
+};
+
+use super::SessionState;
+
+pub struct ProcessSkillCheckState {}
+
+impl ProcessSkillCheckState {
+    pub async fn process(
+        request: SessionRequest,
+        game_state: &mut GameState,
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+    ) -> Result<SessionState, anyhow::Error> {
+        match request {
+            SessionRequest::ContinueProcessing => {
+                let stat = arguments["stat"].as_str().ok_or(anyhow!(
+                    "Unable to interpret arguments for skill_check function."
+                ))?;
+                let difficulty = serde_json::from_value(arguments["difficulty"].clone())
+                    .map_err(|e| anyhow!("Invalid difficulty for skill_check function: {:?}", e))?;
+
+                let output = match game_state.stats.get(stat) {
+                    Ok(stat) => {
+                        let stat = stat.clone();
+                        let roll = game_state.dice.roll_d20();
+                        json!(SkillCheck::resolve(&stat, difficulty, roll))
+                    }
+                    Err(reason) => json!({ "error": reason }),
+                }
+                .to_string();
+
+                info!("Processed skill_check function with output: {}", &output);
+
+                Ok(SessionState::SubmitToolOutputsState {
+                    run_id,
+                    tool_call_id,
+                    output,
+                })
+            }
+            _ => bail!(
+                "Invalid session request for skill check processing state: {:?}. Expected ContinueProcessing.",
+                &request
+            ),
+        }
+    }
+}
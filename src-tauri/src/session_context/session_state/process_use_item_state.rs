@@ -0,0 +1,136 @@
+use anyhow::{anyhow, bail};
+use log::info;
+use serde_json::{json, Value};
+
+use crate::{
+    game::{Game, ItemProperties},
+    game_state::GameState,
+    session_context::session_request::SessionRequest,
+    utils::fuzzy_match::FuzzyMatch,
+};
+
+use super::SessionState;
+
+pub struct ProcessUseItemState {}
+
+impl ProcessUseItemState {
+    pub async fn process(
+        request: SessionRequest,
+        game_state: &mut GameState,
+        run_id: String,
+        tool_call_id: String,
+        arguments: serde_json::Value,
+        game: &Game,
+    ) -> Result<SessionState, anyhow::Error> {
+        match request {
+            SessionRequest::ContinueProcessing => {
+                let item = arguments["item"]
+                    .as_str()
+                    .ok_or(anyhow!("Invalid arguments to use_item function."))?;
+
+                if let Some(target) = arguments["target"].as_str() {
+                    info!("Using item {} on {}.", item, target);
+                }
+
+                let output = match use_item(game_state, game, item) {
+                    Ok(output) => output,
+                    Err(reason) => json!({ "error": reason }),
+                }
+                .to_string();
+
+                info!("Processed use_item function with output: {}", &output);
+
+                Ok(SessionState::SubmitToolOutputsState {
+                    run_id,
+                    tool_call_id,
+                    output,
+                })
+            }
+            _ => bail!(
+                "Invalid session request for use item processing state: {:?}. Expected ContinueProcessing.",
+                &request
+            ),
+        }
+    }
+}
+
+/// Uses an item from the player's inventory, consuming it and unlocking any exits from the current
+/// scene that it opens. Returns the reason as an error if the item can't be used.
+fn use_item(game_state: &mut GameState, game: &Game, item_name: &str) -> Result<Value, String> {
+    let item = FuzzyMatch::find(item_name, &game_state.inventory)
+        .cloned()
+        .ok_or(format!(
+            "Player does not have any {} in their inventory.",
+            item_name
+        ))?;
+
+    let properties = game
+        .items
+        .iter()
+        .find(|i| i.name == item)
+        .map(|i| i.properties.clone())
+        .unwrap_or_default();
+
+    if !properties.usable {
+        return Err(match properties.combinations.is_empty() {
+            true => format!("The {} can't be used.", item),
+            false => format!(
+                "The {} can't be used on its own, but it might be combined with something.",
+                item
+            ),
+        });
+    }
+
+    let unlocked_exits = unlock_exits(game_state, game, &item, &properties);
+
+    if properties.consumable {
+        game_state.remove_item(&item).map_err(|e| e.to_string())?;
+    }
+
+    Ok(json!({
+        "item": item,
+        "effect": properties.effect,
+        "consumed": properties.consumable,
+        "unlocked_exits": unlocked_exits,
+        "updated_player_inventory": format!("[{}]", game_state.get_player_inventory().join(", ")),
+    }))
+}
+
+/// Unlocks the item locks on exits from the current scene that require the item or lead to a scene
+/// it unlocks, returning the names of the exits that were unlocked. Any character lock on the same
+/// exit still needs the character's permission.
+fn unlock_exits(
+    game_state: &mut GameState,
+    game: &Game,
+    item: &str,
+    properties: &ItemProperties,
+) -> Vec<String> {
+    let current_scene = match game_state
+        .current_scene_name
+        .as_ref()
+        .and_then(|name| game.scenes.iter().find(|s| &s.name == name))
+    {
+        Some(scene) => scene,
+        None => return vec![],
+    };
+
+    let exits = current_scene
+        .exits
+        .iter()
+        .filter(|exit| {
+            exit.required_item.as_ref().is_some_and(|required_item| {
+                properties.unlocks_scene(&exit.scene) || required_item.eq_ignore_ascii_case(item)
+            })
+        })
+        .filter(|exit| !game_state.is_exit_unlocked(&current_scene.name, &exit.scene))
+        .collect::<Vec<_>>();
+
+    for exit in &exits {
+        game_state.unlock_exit(&current_scene.name, &exit.scene);
+    }
+
+    exits
+        .iter()
+        .map(|exit| format!("{} ({})", exit.name, exit.scene))
+        .collect()
+}
@@ -0,0 +1,358 @@
+use log::{info, warn};
+use openai_lib::{assistant::AssistantClient, run::RunClient, thread::ThreadClient, OpenAIClient};
+use serde_json::json;
+
+use crate::{game_state::GameState, session_context::run_poller::PollError};
+
+use super::SessionState;
+
+/// How the session gets back to a usable state after processing a state fails. OpenAI won't
+/// start a new run on a thread while another is waiting for tool outputs, so a failed state must
+/// never leave its run behind.
+#[derive(Debug, PartialEq)]
+pub enum Recovery {
+    /// No run is waiting on the state, so the session can go back to waiting on the player.
+    Reset { character: bool },
+    /// A tool call failed, so the run is sent the error as the tool's output and carries on.
+    SubmitError {
+        run_id: String,
+        tool_call_id: String,
+        character: bool,
+    },
+    /// The run can't carry on, so it is cancelled.
+    CancelRun { run_id: String, character: bool },
+    /// The conversation can't carry on, so it is ended and the narrator is told why.
+    AbandonInteraction,
+}
+
+impl Recovery {
+    pub fn for_state(state: &SessionState) -> Self {
+        match state {
+            SessionState::IdleState
+            | SessionState::PendingRunState
+            | SessionState::ReadMessageState => Recovery::Reset { character: false },
+            SessionState::CharacterRunRequestState
+            | SessionState::CharacterReadMessageState
+            | SessionState::CharacterIdleState => Recovery::Reset { character: true },
+            SessionState::PollingRunState { run_id }
+            | SessionState::SubmitToolOutputsState { run_id, .. } => Recovery::CancelRun {
+                run_id: run_id.clone(),
+                character: false,
+            },
+            SessionState::CharacterPollingRunState { run_id }
+            | SessionState::CharacterSubmitToolOutputsState { run_id, .. } => Recovery::CancelRun {
+                run_id: run_id.clone(),
+                character: true,
+            },
+            SessionState::RequiresActionState { run_id, tool_call } => Recovery::SubmitError {
+                run_id: run_id.clone(),
+                tool_call_id: tool_call.id.clone(),
+                character: false,
+            },
+            SessionState::CharacterRequiresActionState { run_id, tool_call } => {
+                Recovery::SubmitError {
+                    run_id: run_id.clone(),
+                    tool_call_id: tool_call.id.clone(),
+                    character: true,
+                }
+            }
+            SessionState::ProcessNewSceneState {
+                run_id,
+                tool_call_id,
+                ..
+            }
+            | SessionState::ProcessAddItemState {
+                run_id,
+                tool_call_id,
+                ..
+            }
+            | SessionState::ProcessCreateItemState {
+                run_id,
+                tool_call_id,
+                ..
+            }
+            | SessionState::ProcessRemoveItemState {
+                run_id,
+                tool_call_id,
+                ..
+            }
+            | SessionState::ProcessDropItemState {
+                run_id,
+                tool_call_id,
+                ..
+            }
+            | SessionState::ProcessUseItemState {
+                run_id,
+                tool_call_id,
+                ..
+            }
+            | SessionState::ProcessCombineItemsState {
+                run_id,
+                tool_call_id,
+                ..
+            }
+            | SessionState::ProcessCharacterInteractState {
+                run_id,
+                tool_call_id,
+                ..
+            }
+            | SessionState::ProcessEndGameState {
+                run_id,
+                tool_call_id,
+                ..
+            }
+            | SessionState::ProcessCompleteObjectiveState {
+                run_id,
+                tool_call_id,
+                ..
+            }
+            | SessionState::ProcessModifyStatState {
+                run_id,
+                tool_call_id,
+                ..
+            }
+            | SessionState::ProcessSkillCheckState {
+                run_id,
+                tool_call_id,
+                ..
+            }
+            | SessionState::ProcessExamineItemState {
+                run_id,
+                tool_call_id,
+                ..
+            }
+            | SessionState::ProcessExamineCharacterState {
+                run_id,
+                tool_call_id,
+                ..
+            }
+            | SessionState::ProcessRecallLoreState {
+                run_id,
+                tool_call_id,
+                ..
+            } => Recovery::SubmitError {
+                run_id: run_id.clone(),
+                tool_call_id: tool_call_id.clone(),
+                character: false,
+            },
+            SessionState::ProcessCharacterTradeState {
+                run_id,
+                tool_call_id,
+                ..
+            }
+            | SessionState::ProcessCharacterGiftState {
+                run_id,
+                tool_call_id,
+                ..
+            }
+            | SessionState::ProcessCharacterGrantPassageState {
+                run_id,
+                tool_call_id,
+                ..
+            }
+            | SessionState::ProcessCharacterUpdateDispositionState {
+                run_id,
+                tool_call_id,
+                ..
+            }
+            | SessionState::AwaitingPlayerTradeResponseState {
+                run_id,
+                tool_call_id,
+            }
+            | SessionState::AwaitingPlayerGiftResponseState {
+                run_id,
+                tool_call_id,
+            } => Recovery::SubmitError {
+                run_id: run_id.clone(),
+                tool_call_id: tool_call_id.clone(),
+                character: true,
+            },
+            SessionState::CharacterEndInteractionState => Recovery::AbandonInteraction,
+        }
+    }
+
+    /// Returns the state the session should continue from, cancelling the failed run if needed.
+    /// The cause of the error is passed on to the assistant if its run carries on.
+    pub async fn recover(
+        self,
+        error: &anyhow::Error,
+        openai_client: &OpenAIClient,
+        game_state: &mut GameState,
+    ) -> SessionState {
+        info!("Recovering from failed state with {:?}", &self);
+
+        let reason = error.root_cause().to_string();
+        let reason = reason.as_str();
+
+        match self {
+            Recovery::Reset { character } => {
+                Recovery::report(error, game_state);
+
+                Recovery::resume(character, reason, openai_client, game_state).await
+            }
+            Recovery::SubmitError {
+                run_id,
+                tool_call_id,
+                character,
+            } => {
+                let output = json!({ "error": reason }).to_string();
+
+                // A trade that failed part way through can no longer be responded to
+                if let Some(character_interaction) = game_state.character_interaction.as_mut() {
+                    character_interaction.trade = None;
+                }
+
+                match character {
+                    true => SessionState::CharacterSubmitToolOutputsState {
+                        run_id,
+                        tool_call_id,
+                        output,
+                    },
+                    false => SessionState::SubmitToolOutputsState {
+                        run_id,
+                        tool_call_id,
+                        output,
+                    },
+                }
+            }
+            Recovery::CancelRun { run_id, character } => {
+                let thread_id = match (character, game_state.character_interaction.as_ref()) {
+                    (true, Some(character_interaction)) => character_interaction.thread_id.clone(),
+                    _ => game_state.thread_id.clone(),
+                };
+
+                // The run may have already failed or expired, in which case it's already done
+                if let Err(e) = openai_client.cancel_run(&thread_id, &run_id).await {
+                    warn!("Unable to cancel run {}: {:?}", &run_id, e);
+                }
+
+                Recovery::report(error, game_state);
+
+                Recovery::resume(character, reason, openai_client, game_state).await
+            }
+            Recovery::AbandonInteraction => {
+                Recovery::abandon_interaction(reason, openai_client, game_state).await
+            }
+        }
+    }
+
+    /// Tells the player their request failed, and whether it's worth trying again.
+    fn report(error: &anyhow::Error, game_state: &mut GameState) {
+        let (message, can_retry) = match error.chain().find_map(|e| e.downcast_ref::<PollError>()) {
+            Some(poll_error) => (poll_error.player_message(), poll_error.is_retryable()),
+            None => ("Something went wrong.", false),
+        };
+
+        game_state.add_error_message(message, can_retry);
+
+        // The conversation window only shows the conversation, so the player is told there too
+        if let Some(character_interaction) = game_state.character_interaction.as_mut() {
+            character_interaction.add_nonverbal(message);
+        }
+    }
+
+    /// Goes back to waiting on the player, in the conversation if it's still open.
+    async fn resume(
+        character: bool,
+        reason: &str,
+        openai_client: &OpenAIClient,
+        game_state: &mut GameState,
+    ) -> SessionState {
+        match (character, game_state.character_interaction.as_ref()) {
+            (true, Some(character_interaction)) if !character_interaction.closed => {
+                SessionState::CharacterIdleState
+            }
+            (true, Some(_)) => {
+                Recovery::abandon_interaction(reason, openai_client, game_state).await
+            }
+            _ => SessionState::IdleState,
+        }
+    }
+
+    /// Ends the conversation, sending the narrator's run the error as the output of the tool call
+    /// that started it. The conversation's thread and assistants are deleted, the same as when a
+    /// conversation ends normally, but failing to delete them doesn't stop the narrator's run.
+    async fn abandon_interaction(
+        reason: &str,
+        openai_client: &OpenAIClient,
+        game_state: &mut GameState,
+    ) -> SessionState {
+        game_state.add_error_message("The conversation ended unexpectedly.", false);
+
+        let character_interaction = match game_state.character_interaction.take() {
+            Some(character_interaction) => character_interaction,
+            None => return SessionState::IdleState,
+        };
+
+        if let Err(e) = openai_client
+            .delete_thread(&character_interaction.thread_id)
+            .await
+        {
+            warn!("Unable to delete abandoned conversation thread: {:?}", e);
+        }
+
+        for assistant_id in character_interaction.assistant_ids() {
+            if let Err(e) = openai_client.delete_assistant(&assistant_id).await {
+                warn!(
+                    "Unable to delete character assistant {}: {:?}",
+                    &assistant_id, e
+                );
+            }
+        }
+
+        SessionState::SubmitToolOutputsState {
+            run_id: character_interaction.initiating_run_id,
+            tool_call_id: character_interaction.initiating_tool_call_id,
+            output: json!({
+                "error": format!("The conversation ended unexpectedly: {}", reason)
+            })
+            .to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn failed_tool_calls_keep_their_run() {
+        let state = SessionState::ProcessCharacterTradeState {
+            run_id: String::from("run"),
+            tool_call_id: String::from("call"),
+            arguments: json!({}),
+        };
+
+        assert_eq!(
+            Recovery::for_state(&state),
+            Recovery::SubmitError {
+                run_id: String::from("run"),
+                tool_call_id: String::from("call"),
+                character: true,
+            }
+        );
+    }
+
+    #[test]
+    fn failed_runs_are_cancelled() {
+        let state = SessionState::SubmitToolOutputsState {
+            run_id: String::from("run"),
+            tool_call_id: String::from("call"),
+            output: String::new(),
+        };
+
+        assert_eq!(
+            Recovery::for_state(&state),
+            Recovery::CancelRun {
+                run_id: String::from("run"),
+                character: false,
+            }
+        );
+        assert_eq!(
+            Recovery::for_state(&SessionState::PendingRunState),
+            Recovery::Reset { character: false }
+        );
+    }
+}
@@ -48,6 +48,27 @@ impl RequiresActionState {
                             arguments,
                         })
                     }
+                    "drop_item" => {
+                        return Ok(SessionState::ProcessDropItemState {
+                            run_id,
+                            tool_call_id,
+                            arguments,
+                        })
+                    }
+                    "use_item" => {
+                        return Ok(SessionState::ProcessUseItemState {
+                            run_id,
+                            tool_call_id,
+                            arguments,
+                        })
+                    }
+                    "combine_items" => {
+                        return Ok(SessionState::ProcessCombineItemsState {
+                            run_id,
+                            tool_call_id,
+                            arguments,
+                        })
+                    }
                     "character_interact" => {
                         return Ok(SessionState::ProcessCharacterInteractState {
                             run_id,
@@ -55,6 +76,42 @@ impl RequiresActionState {
                             arguments,
                         })
                     }
+                    "complete_objective" => {
+                        return Ok(SessionState::ProcessCompleteObjectiveState {
+                            run_id,
+                            tool_call_id,
+                            arguments,
+                        })
+                    }
+                    "modify_stat" => {
+                        return Ok(SessionState::ProcessModifyStatState {
+                            run_id,
+                            tool_call_id,
+                            arguments,
+                        })
+                    }
+                    "skill_check" => {
+                        return Ok(SessionState::ProcessSkillCheckState {
+                            run_id,
+                            tool_call_id,
+                            arguments,
+                        })
+                    }
+                    "examine_item" => Ok(SessionState::ProcessExamineItemState {
+                        run_id,
+                        tool_call_id,
+                        arguments,
+                    }),
+                    "examine_character" => Ok(SessionState::ProcessExamineCharacterState {
+                        run_id,
+                        tool_call_id,
+                        arguments,
+                    }),
+                    "recall_lore" => Ok(SessionState::ProcessRecallLoreState {
+                        run_id,
+                        tool_call_id,
+                        arguments,
+                    }),
                     "end_game" => {
                         return Ok(SessionState::ProcessEndGameState {
                             run_id,
@@ -0,0 +1,260 @@
+use serde::Serialize;
+
+use super::SessionState;
+
+/// Identifies a `SessionState` variant without its data, so that transitions can be declared and
+/// recorded.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StateKind {
+    IdleState,
+    PendingRunState,
+    PollingRunState,
+    RequiresActionState,
+    ReadMessageState,
+    ProcessNewSceneState,
+    ProcessAddItemState,
+    ProcessCreateItemState,
+    ProcessRemoveItemState,
+    ProcessDropItemState,
+    ProcessUseItemState,
+    ProcessCombineItemsState,
+    ProcessCharacterInteractState,
+    ProcessEndGameState,
+    ProcessCompleteObjectiveState,
+    ProcessModifyStatState,
+    ProcessSkillCheckState,
+    ProcessExamineItemState,
+    ProcessExamineCharacterState,
+    ProcessRecallLoreState,
+    SubmitToolOutputsState,
+    CharacterRunRequestState,
+    CharacterPollingRunState,
+    CharacterRequiresActionState,
+    ProcessCharacterTradeState,
+    ProcessCharacterGiftState,
+    ProcessCharacterGrantPassageState,
+    ProcessCharacterUpdateDispositionState,
+    CharacterSubmitToolOutputsState,
+    AwaitingPlayerTradeResponseState,
+    AwaitingPlayerGiftResponseState,
+    CharacterReadMessageState,
+    CharacterIdleState,
+    CharacterEndInteractionState,
+}
+
+use StateKind::*;
+
+impl StateKind {
+    pub const ALL: &'static [StateKind] = &[
+        IdleState,
+        PendingRunState,
+        PollingRunState,
+        RequiresActionState,
+        ReadMessageState,
+        ProcessNewSceneState,
+        ProcessAddItemState,
+        ProcessCreateItemState,
+        ProcessRemoveItemState,
+        ProcessDropItemState,
+        ProcessUseItemState,
+        ProcessCombineItemsState,
+        ProcessCharacterInteractState,
+        ProcessEndGameState,
+        ProcessCompleteObjectiveState,
+        ProcessModifyStatState,
+        ProcessSkillCheckState,
+        ProcessExamineItemState,
+        ProcessExamineCharacterState,
+        ProcessRecallLoreState,
+        SubmitToolOutputsState,
+        CharacterRunRequestState,
+        CharacterPollingRunState,
+        CharacterRequiresActionState,
+        ProcessCharacterTradeState,
+        ProcessCharacterGiftState,
+        ProcessCharacterGrantPassageState,
+        ProcessCharacterUpdateDispositionState,
+        CharacterSubmitToolOutputsState,
+        AwaitingPlayerTradeResponseState,
+        AwaitingPlayerGiftResponseState,
+        CharacterReadMessageState,
+        CharacterIdleState,
+        CharacterEndInteractionState,
+    ];
+
+    /// The states a successfully processed state can move to. Failed states aren't bound by this,
+    /// since `Recovery` decides where they go.
+    pub fn exits(self) -> &'static [StateKind] {
+        match self {
+            IdleState => &[PendingRunState],
+            PendingRunState => &[PollingRunState],
+            PollingRunState => &[RequiresActionState, ReadMessageState],
+            RequiresActionState => &[
+                ProcessNewSceneState,
+                ProcessAddItemState,
+                ProcessRemoveItemState,
+                ProcessDropItemState,
+                ProcessUseItemState,
+                ProcessCombineItemsState,
+                ProcessCharacterInteractState,
+                ProcessEndGameState,
+                ProcessCompleteObjectiveState,
+                ProcessModifyStatState,
+                ProcessSkillCheckState,
+                ProcessExamineItemState,
+                ProcessExamineCharacterState,
+                ProcessRecallLoreState,
+            ],
+            ReadMessageState => &[IdleState],
+            ProcessAddItemState => &[ProcessCreateItemState, SubmitToolOutputsState],
+            ProcessCreateItemState | ProcessCombineItemsState => {
+                &[ProcessAddItemState, SubmitToolOutputsState]
+            }
+            ProcessModifyStatState => &[ProcessEndGameState, SubmitToolOutputsState],
+            ProcessCharacterInteractState => &[CharacterRunRequestState, SubmitToolOutputsState],
+            ProcessNewSceneState
+            | ProcessRemoveItemState
+            | ProcessDropItemState
+            | ProcessUseItemState
+            | ProcessEndGameState
+            | ProcessCompleteObjectiveState
+            | ProcessSkillCheckState
+            | ProcessExamineItemState
+            | ProcessExamineCharacterState
+            | ProcessRecallLoreState => &[SubmitToolOutputsState],
+            SubmitToolOutputsState => &[PollingRunState],
+            CharacterRunRequestState | CharacterSubmitToolOutputsState => {
+                &[CharacterPollingRunState]
+            }
+            CharacterPollingRunState => &[CharacterRequiresActionState, CharacterReadMessageState],
+            CharacterRequiresActionState => &[
+                ProcessCharacterTradeState,
+                ProcessCharacterGiftState,
+                ProcessCharacterGrantPassageState,
+                ProcessCharacterUpdateDispositionState,
+            ],
+            ProcessCharacterTradeState => &[
+                AwaitingPlayerTradeResponseState,
+                CharacterSubmitToolOutputsState,
+            ],
+            ProcessCharacterGiftState => &[
+                AwaitingPlayerGiftResponseState,
+                CharacterSubmitToolOutputsState,
+            ],
+            ProcessCharacterGrantPassageState | ProcessCharacterUpdateDispositionState => {
+                &[CharacterSubmitToolOutputsState]
+            }
+            // An invalid counter-offer leaves the trade open for the player to respond to again
+            AwaitingPlayerTradeResponseState => {
+                &[CharacterPollingRunState, AwaitingPlayerTradeResponseState]
+            }
+            AwaitingPlayerGiftResponseState => &[CharacterPollingRunState],
+            CharacterReadMessageState => &[
+                CharacterIdleState,
+                CharacterRunRequestState,
+                CharacterEndInteractionState,
+            ],
+            CharacterIdleState => &[CharacterRunRequestState, CharacterEndInteractionState],
+            CharacterEndInteractionState => &[CharacterRunRequestState, SubmitToolOutputsState],
+        }
+    }
+
+    pub fn can_move_to(self, next: StateKind) -> bool {
+        self.exits().contains(&next)
+    }
+}
+
+impl SessionState {
+    pub fn kind(&self) -> StateKind {
+        match self {
+            SessionState::IdleState => IdleState,
+            SessionState::PendingRunState => PendingRunState,
+            SessionState::PollingRunState { .. } => PollingRunState,
+            SessionState::RequiresActionState { .. } => RequiresActionState,
+            SessionState::ReadMessageState => ReadMessageState,
+            SessionState::ProcessNewSceneState { .. } => ProcessNewSceneState,
+            SessionState::ProcessAddItemState { .. } => ProcessAddItemState,
+            SessionState::ProcessCreateItemState { .. } => ProcessCreateItemState,
+            SessionState::ProcessRemoveItemState { .. } => ProcessRemoveItemState,
+            SessionState::ProcessDropItemState { .. } => ProcessDropItemState,
+            SessionState::ProcessUseItemState { .. } => ProcessUseItemState,
+            SessionState::ProcessCombineItemsState { .. } => ProcessCombineItemsState,
+            SessionState::ProcessCharacterInteractState { .. } => ProcessCharacterInteractState,
+            SessionState::ProcessEndGameState { .. } => ProcessEndGameState,
+            SessionState::ProcessCompleteObjectiveState { .. } => ProcessCompleteObjectiveState,
+            SessionState::ProcessModifyStatState { .. } => ProcessModifyStatState,
+            SessionState::ProcessSkillCheckState { .. } => ProcessSkillCheckState,
+            SessionState::ProcessExamineItemState { .. } => ProcessExamineItemState,
+            SessionState::ProcessExamineCharacterState { .. } => ProcessExamineCharacterState,
+            SessionState::ProcessRecallLoreState { .. } => ProcessRecallLoreState,
+            SessionState::SubmitToolOutputsState { .. } => SubmitToolOutputsState,
+            SessionState::CharacterRunRequestState => CharacterRunRequestState,
+            SessionState::CharacterPollingRunState { .. } => CharacterPollingRunState,
+            SessionState::CharacterRequiresActionState { .. } => CharacterRequiresActionState,
+            SessionState::ProcessCharacterTradeState { .. } => ProcessCharacterTradeState,
+            SessionState::ProcessCharacterGiftState { .. } => ProcessCharacterGiftState,
+            SessionState::ProcessCharacterGrantPassageState { .. } => {
+                ProcessCharacterGrantPassageState
+            }
+            SessionState::ProcessCharacterUpdateDispositionState { .. } => {
+                ProcessCharacterUpdateDispositionState
+            }
+            SessionState::CharacterSubmitToolOutputsState { .. } => CharacterSubmitToolOutputsState,
+            SessionState::AwaitingPlayerTradeResponseState { .. } => {
+                AwaitingPlayerTradeResponseState
+            }
+            SessionState::AwaitingPlayerGiftResponseState { .. } => AwaitingPlayerGiftResponseState,
+            SessionState::CharacterReadMessageState => CharacterReadMessageState,
+            SessionState::CharacterIdleState => CharacterIdleState,
+            SessionState::CharacterEndInteractionState => CharacterEndInteractionState,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    /// Every state that can be reached from `start` by following the table.
+    fn reachable_from(start: StateKind) -> HashSet<StateKind> {
+        let mut reached = HashSet::from([start]);
+        let mut pending = vec![start];
+
+        while let Some(kind) = pending.pop() {
+            for &next in kind.exits() {
+                if reached.insert(next) {
+                    pending.push(next);
+                }
+            }
+        }
+
+        reached
+    }
+
+    #[test]
+    fn every_state_is_reachable() {
+        let reached = reachable_from(PendingRunState);
+
+        let unreachable = StateKind::ALL
+            .iter()
+            .filter(|kind| !reached.contains(kind))
+            .collect::<Vec<_>>();
+
+        assert!(unreachable.is_empty(), "Unreachable: {:?}", unreachable);
+        assert_eq!(reached.len(), StateKind::ALL.len());
+    }
+
+    #[test]
+    fn every_state_leads_back_to_the_player() {
+        for &kind in StateKind::ALL {
+            assert!(!kind.exits().is_empty(), "{:?} has no exits", kind);
+            assert!(
+                reachable_from(kind).contains(&IdleState),
+                "{:?} never returns to IdleState",
+                kind
+            );
+        }
+    }
+}
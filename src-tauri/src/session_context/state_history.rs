@@ -0,0 +1,126 @@
+use std::{collections::VecDeque, fmt::Write};
+
+use serde::Serialize;
+
+use super::session_state::StateKind;
+
+const MAX_TRANSITIONS: usize = 200;
+
+/// A state change the session made, kept so a session that went wrong can be inspected.
+#[derive(Serialize, Debug, Clone)]
+pub struct StateTransition {
+    pub from: StateKind,
+    pub to: StateKind,
+    /// The request being processed, which is `ContinueProcessing` for states that run on their own.
+    pub request: String,
+    /// Whether the `from` state failed, so `to` was chosen by its recovery instead of the table.
+    pub recovered: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct StateHistory {
+    transitions: VecDeque<StateTransition>,
+}
+
+impl StateHistory {
+    pub fn record(&mut self, transition: StateTransition) {
+        if self.transitions.len() == MAX_TRANSITIONS {
+            self.transitions.pop_front();
+        }
+
+        self.transitions.push_back(transition);
+    }
+
+    pub fn transitions(&self) -> Vec<StateTransition> {
+        self.transitions.iter().cloned().collect()
+    }
+
+    /// Renders every declared transition as a Graphviz graph. Transitions taken this session are
+    /// drawn bold and labelled with how often they were taken, recoveries are drawn dashed in red,
+    /// and the current state is filled in.
+    pub fn to_dot(&self, current: Option<StateKind>) -> String {
+        let mut dot = String::from("digraph SessionState {\n    node [shape=box];\n");
+
+        for &kind in StateKind::ALL {
+            let style = match Some(kind) == current {
+                true => " [style=filled, fillcolor=lightblue]",
+                false => "",
+            };
+            let _ = writeln!(dot, "    {:?}{};", kind, style);
+        }
+
+        for &from in StateKind::ALL {
+            for &to in from.exits() {
+                let style = match self.count(from, to, false) {
+                    0 => String::new(),
+                    n => format!(" [penwidth=2, label=\"{}\"]", n),
+                };
+                let _ = writeln!(dot, "    {:?} -> {:?}{};", from, to, style);
+            }
+        }
+
+        let mut recoveries = Vec::new();
+        for transition in self.transitions.iter().filter(|t| t.recovered) {
+            if !recoveries.contains(&(transition.from, transition.to)) {
+                recoveries.push((transition.from, transition.to));
+            }
+        }
+
+        for (from, to) in recoveries {
+            let _ = writeln!(
+                dot,
+                "    {:?} -> {:?} [style=dashed, color=red, label=\"{}\"];",
+                from,
+                to,
+                self.count(from, to, true)
+            );
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn count(&self, from: StateKind, to: StateKind, recovered: bool) -> usize {
+        self.transitions
+            .iter()
+            .filter(|t| t.from == from && t.to == to && t.recovered == recovered)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn transition(from: StateKind, to: StateKind, recovered: bool) -> StateTransition {
+        StateTransition {
+            from,
+            to,
+            request: String::from("ContinueProcessing"),
+            recovered,
+        }
+    }
+
+    #[test]
+    fn marks_taken_transitions_and_current_state() {
+        let mut history = StateHistory::default();
+        history.record(transition(
+            StateKind::IdleState,
+            StateKind::PendingRunState,
+            false,
+        ));
+        history.record(transition(
+            StateKind::PollingRunState,
+            StateKind::IdleState,
+            true,
+        ));
+
+        let dot = history.to_dot(Some(StateKind::IdleState));
+
+        assert!(dot.contains("IdleState [style=filled, fillcolor=lightblue];"));
+        assert!(dot.contains("IdleState -> PendingRunState [penwidth=2, label=\"1\"];"));
+        assert!(dot.contains("PendingRunState -> PollingRunState;"));
+        assert!(dot.contains("PollingRunState -> IdleState [style=dashed, color=red"));
+        assert_eq!(history.transitions().len(), 2);
+    }
+}
@@ -0,0 +1,200 @@
+const MIN_SIMILARITY: f64 = 0.75;
+const IGNORED_PREFIXES: [&str; 4] = ["the", "a", "an", "some"];
+
+pub struct FuzzyMatch {}
+
+impl FuzzyMatch {
+    /// Finds the candidate that best matches a name the model came up with. Names match if they
+    /// only differ by case, punctuation or a leading article, or if they are spelled closely
+    /// enough. Otherwise, the only candidate that contains every word of the query is used.
+    pub fn find<'a>(
+        query: &str,
+        candidates: impl IntoIterator<Item = &'a String>,
+    ) -> Option<&'a String> {
+        let query = FuzzyMatch::normalize(query);
+        if query.is_empty() {
+            return None;
+        }
+
+        let candidates = FuzzyMatch::normalize_all(candidates);
+
+        if let Some((candidate, _)) = candidates.iter().find(|(_, name)| name == &query) {
+            return Some(candidate);
+        }
+
+        if let Some(candidate) = FuzzyMatch::close_matches(&query, &candidates).first() {
+            return Some(candidate);
+        }
+
+        match FuzzyMatch::containing_matches(&query, &candidates).as_slice() {
+            [candidate] => Some(candidate),
+            _ => None,
+        }
+    }
+
+    /// Like `find`, but for names the player typed, which aren't guessed at when they fit more
+    /// than one candidate. "key" finds nothing when there's both a Rusty Key and a Silver Key.
+    pub fn find_unique<'a>(
+        query: &str,
+        candidates: impl IntoIterator<Item = &'a String>,
+    ) -> Option<&'a String> {
+        let query = FuzzyMatch::normalize(query);
+        if query.is_empty() {
+            return None;
+        }
+
+        let candidates = FuzzyMatch::normalize_all(candidates);
+
+        if let Some((candidate, _)) = candidates.iter().find(|(_, name)| name == &query) {
+            return Some(candidate);
+        }
+
+        let mut close = FuzzyMatch::close_matches(&query, &candidates);
+        close.dedup();
+
+        match close.as_slice() {
+            [candidate] => return Some(candidate),
+            [] => {}
+            _ => return None,
+        }
+
+        let mut containing = FuzzyMatch::containing_matches(&query, &candidates);
+        containing.sort();
+        containing.dedup();
+
+        match containing.as_slice() {
+            [candidate] => Some(candidate),
+            _ => None,
+        }
+    }
+
+    fn normalize_all<'a>(
+        candidates: impl IntoIterator<Item = &'a String>,
+    ) -> Vec<(&'a String, String)> {
+        candidates
+            .into_iter()
+            .map(|c| (c, FuzzyMatch::normalize(c)))
+            .collect()
+    }
+
+    /// The candidates spelled closely enough to the query, closest first.
+    fn close_matches<'a>(query: &str, candidates: &[(&'a String, String)]) -> Vec<&'a String> {
+        let mut close = candidates
+            .iter()
+            .map(|(candidate, name)| (*candidate, FuzzyMatch::similarity(query, name)))
+            .filter(|(_, similarity)| *similarity >= MIN_SIMILARITY)
+            .collect::<Vec<_>>();
+        close.sort_by(|(a_name, a), (b_name, b)| b.total_cmp(a).then(a_name.cmp(b_name)));
+
+        close.into_iter().map(|(candidate, _)| candidate).collect()
+    }
+
+    /// The candidates that contain every word of the query.
+    fn containing_matches<'a>(query: &str, candidates: &[(&'a String, String)]) -> Vec<&'a String> {
+        candidates
+            .iter()
+            .filter(|(_, name)| {
+                let words = name.split(' ').collect::<Vec<_>>();
+                query.split(' ').all(|word| words.contains(&word))
+            })
+            .map(|(candidate, _)| *candidate)
+            .collect()
+    }
+
+    fn normalize(name: &str) -> String {
+        let name = name
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+            .collect::<String>();
+
+        let mut words = name.split_whitespace().collect::<Vec<_>>();
+        if words.len() > 1 && IGNORED_PREFIXES.contains(&words[0]) {
+            words.remove(0);
+        }
+
+        words.join(" ")
+    }
+
+    fn similarity(a: &str, b: &str) -> f64 {
+        let max_len = a.chars().count().max(b.chars().count());
+        match max_len {
+            0 => 1.0,
+            _ => 1.0 - FuzzyMatch::edit_distance(a, b) as f64 / max_len as f64,
+        }
+    }
+
+    fn edit_distance(a: &str, b: &str) -> usize {
+        let b = b.chars().collect::<Vec<_>>();
+        let mut previous = (0..=b.len()).collect::<Vec<_>>();
+
+        for (i, a_char) in a.chars().enumerate() {
+            let mut current = vec![i + 1];
+            for (j, b_char) in b.iter().enumerate() {
+                let substitution = previous[j] + usize::from(a_char != *b_char);
+                current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+            }
+            previous = current;
+        }
+
+        previous[b.len()]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn names() -> Vec<String> {
+        ["Temple Map", "Echo Crystal", "Rune Stone", "Rusty Key"]
+            .iter()
+            .map(|n| n.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn matches_close_names() {
+        let names = names();
+
+        assert_eq!(
+            FuzzyMatch::find("the temple map", &names).unwrap(),
+            "Temple Map"
+        );
+        assert_eq!(
+            FuzzyMatch::find("Echo-Crystal", &names).unwrap(),
+            "Echo Crystal"
+        );
+        assert_eq!(
+            FuzzyMatch::find("Rune Stones", &names).unwrap(),
+            "Rune Stone"
+        );
+        assert_eq!(FuzzyMatch::find("crystal", &names).unwrap(), "Echo Crystal");
+    }
+
+    #[test]
+    fn does_not_match_different_names() {
+        let names = names();
+
+        assert!(FuzzyMatch::find("Silver Key", &names).is_none());
+        assert!(FuzzyMatch::find("Pebble", &names).is_none());
+        assert!(FuzzyMatch::find("", &names).is_none());
+    }
+
+    #[test]
+    fn unique_matches_leave_ambiguous_names_alone() {
+        let mut names = names();
+        names.push(String::from("Rusty Keg"));
+        names.push(String::from("Silver Key"));
+
+        assert_eq!(
+            FuzzyMatch::find_unique("rune stones", &names).unwrap(),
+            "Rune Stone"
+        );
+        assert_eq!(
+            FuzzyMatch::find_unique("Rusty Key", &names).unwrap(),
+            "Rusty Key"
+        );
+        assert!(FuzzyMatch::find_unique("rusty kex", &names).is_none());
+        assert!(FuzzyMatch::find_unique("key", &names).is_none());
+    }
+}
@@ -1,3 +1,5 @@
+pub mod fuzzy_match;
 pub mod logger;
 pub mod string_utilities;
-pub mod random;
\ No newline at end of file
+pub mod random;
+pub mod stable_hash;
@@ -1,4 +1,6 @@
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::stable_hash::StableHash;
 
 pub struct Random {}
 
@@ -11,4 +13,43 @@ impl Random {
             .collect::<String>();
         return id;
     }
+
+    pub fn generate_seed() -> u32 {
+        rand::thread_rng().gen()
+    }
+
+    /// Derives a new seed from a parent seed and a label. The same seed and label will always
+    /// produce the same result, so any randomness seeded from it can be replayed.
+    pub fn derive_seed(seed: u32, label: &str) -> u32 {
+        let mut data = seed.to_le_bytes().to_vec();
+        data.extend_from_slice(label.as_bytes());
+
+        let hash = StableHash::fnv1a(&data);
+        (hash ^ (hash >> 32)) as u32
+    }
+
+    pub fn from_seed(seed: u32) -> StdRng {
+        StdRng::seed_from_u64(seed as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn derived_seeds_are_stable() {
+        assert_eq!(
+            Random::derive_seed(42, "tmp/summary.json"),
+            Random::derive_seed(42, "tmp/summary.json")
+        );
+        assert_ne!(
+            Random::derive_seed(42, "tmp/summary.json"),
+            Random::derive_seed(42, "tmp/narrative.json")
+        );
+        assert_ne!(
+            Random::derive_seed(42, "tmp/summary.json"),
+            Random::derive_seed(43, "tmp/summary.json")
+        );
+    }
 }
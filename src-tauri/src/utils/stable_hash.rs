@@ -0,0 +1,32 @@
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Hashing that produces the same result across runs, platforms and compiler versions, unlike the
+/// standard library's `DefaultHasher`. This is not suitable for anything security related.
+pub struct StableHash {}
+
+impl StableHash {
+    /// Hashes the data with the 64-bit FNV-1a algorithm.
+    pub fn fnv1a(data: &[u8]) -> u64 {
+        data.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ *byte as u64).wrapping_mul(FNV_PRIME)
+        })
+    }
+
+    /// Hashes the text with the 64-bit FNV-1a algorithm, returning it as a hex string.
+    pub fn hex(text: &str) -> String {
+        format!("{:016x}", StableHash::fnv1a(text.as_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn matches_fnv1a_reference_values() {
+        assert_eq!(StableHash::fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(StableHash::fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(StableHash::hex("foobar"), "85944171f73967e8");
+    }
+}
@@ -6,6 +6,25 @@ import useGameState from '../../hooks/useGameState';
 import PlayerEntry from '../PlayerEntry/PlayerEntry';
 import Modal from '../Modal/Modal';
 import AlertDialog from '../AlertDialog/AlertDialog';
+import TradeOffer from '../TradeOffer/TradeOffer';
+import { Character } from '../../types/Game';
+
+interface CharacterPortraitProps {
+  character: Character;
+}
+
+const CharacterPortrait: React.FC<CharacterPortraitProps> = ({
+  character,
+}) => {
+  const { src, alt } = useProcessImage(character.image);
+
+  return (
+    <div>
+      <h3 className="font-overlock-sc text-lg">{character.name}</h3>
+      <img src={src} alt={alt} className="object-cover shadow-inner" />
+    </div>
+  );
+};
 
 interface CharacterWindowProps {
   characterInteraction: CharacterInteraction | null;
@@ -16,9 +35,12 @@ const CharacterWindow: React.FC<CharacterWindowProps> = ({
 }) => {
   const { game } = useGameContext();
   const [playerInput, setPlayerInput] = React.useState('');
+  const [counterOffering, setCounterOffering] = React.useState(false);
   const {
+    gameState,
     sendCharacterMessage,
     characterTradeResponse,
+    characterTradeCounterOffer,
     endCharacterConversation,
     loading,
   } = useGameState();
@@ -27,13 +49,21 @@ const CharacterWindow: React.FC<CharacterWindowProps> = ({
     return null;
   }
 
-  const character = game.characters.find(
-    (ch) => ch.name === characterInteraction.character_name
+  const participantNames = characterInteraction.participants.length
+    ? characterInteraction.participants.map((p) => p.character_name)
+    : [characterInteraction.character_name];
+
+  const characters = game.characters.filter((ch) =>
+    participantNames.includes(ch.name)
   );
 
-  if (!character) return null;
+  if (!characters.length) return null;
 
-  const { src, alt } = useProcessImage(character.image);
+  const trade = characterInteraction.trade;
+  const traderName =
+    trade?.character_name || characterInteraction.character_name;
+  const characterInventory =
+    gameState?.character_save_data[traderName]?.character_inventory || [];
 
   return (
     <>
@@ -47,9 +77,12 @@ const CharacterWindow: React.FC<CharacterWindowProps> = ({
         clickOut
       >
         <div className="grid w-[70vw] grid-cols-2 gap-4">
-          <div>
-            <h3 className="font-overlock-sc text-lg">{character.name}</h3>
-            <img src={src} alt={alt} className="object-cover shadow-inner" />
+          <div
+            className={`grid gap-2 ${characters.length > 1 ? 'grid-cols-2' : ''}`}
+          >
+            {characters.map((character) => (
+              <CharacterPortrait key={character.name} character={character} />
+            ))}
           </div>
           <div className="flex flex-col gap-2">
             <div className="flex h-full flex-grow">
@@ -75,7 +108,7 @@ const CharacterWindow: React.FC<CharacterWindowProps> = ({
       </Modal>
 
       <AlertDialog
-        open={!!characterInteraction.trade}
+        open={!!trade && !counterOffering}
         setOpen={(open: boolean) => {
           if (!open) {
             characterTradeResponse(false);
@@ -83,21 +116,44 @@ const CharacterWindow: React.FC<CharacterWindowProps> = ({
         }}
         title="Trade Request"
         message={
-          characterInteraction.trade?.from_player
-            ? `${character.name} wishes to trade their ${characterInteraction.trade?.to_player} for your ${characterInteraction.trade?.from_player}. Do you accept?`
-            : `${character.name} wishes to give you their ${characterInteraction.trade?.to_player}. Do you accept?`
+          trade?.from_player.length
+            ? `${traderName} wishes to trade their ${trade.to_player.join(', ')} for your ${trade.from_player.join(', ')}. Do you accept?`
+            : `${traderName} wishes to give you their ${trade?.to_player.join(', ')}. Do you accept?`
         }
         actions={[
           {
             title: 'Accept',
             onSelect: () => characterTradeResponse(true),
           },
+          ...(trade?.from_player.length
+            ? [
+                {
+                  title: 'Counter-offer',
+                  onSelect: () => setCounterOffering(true),
+                },
+              ]
+            : []),
           {
             title: 'Decline',
             onSelect: () => characterTradeResponse(false),
           },
         ]}
       />
+
+      {trade && counterOffering && (
+        <TradeOffer
+          open={counterOffering}
+          setOpen={setCounterOffering}
+          traderName={traderName}
+          trade={trade}
+          playerInventory={gameState?.inventory || []}
+          characterInventory={characterInventory}
+          onSubmit={(toPlayer, fromPlayer) => {
+            setCounterOffering(false);
+            characterTradeCounterOffer(toPlayer, fromPlayer);
+          }}
+        />
+      )}
     </>
   );
 };
@@ -53,6 +53,11 @@ const GameSummaryCard: React.FC<GameSummaryCardProps> = ({
               <div className="relative h-full w-full p-3">
                 <h2 className="font-overlock-sc text-lg">{game.name}</h2>
                 <p>{game.summary.description}</p>
+                {game.seed > 0 && (
+                  <p className="mt-1 text-[12px] opacity-75">
+                    Seed: {game.seed}
+                  </p>
+                )}
               </div>
             </div>
           </div>
@@ -0,0 +1,37 @@
+import React from 'react';
+
+interface JournalListProps {
+  journal: Journal;
+}
+
+const JournalList: React.FC<JournalListProps> = ({ journal }) => {
+  const entries = journal.entries.filter((entry) => entry.status !== 'locked');
+
+  return (
+    <div className="flex h-full flex-col items-center gap-6">
+      <h2 className="font-overlock-sc text-lg">Journal</h2>
+
+      <ul className="no-scrollbar flex h-full max-w-64 flex-1 flex-col gap-4 overflow-y-scroll">
+        {entries.map((entry) => (
+          <li
+            key={entry.id}
+            className={`rounded-md bg-yellow-950 p-2 shadow-lg ${
+              entry.status === 'completed' ? 'opacity-50' : ''
+            }`}
+          >
+            <h3
+              className={`font-overlock-sc text-sm ${
+                entry.status === 'completed' ? 'line-through' : ''
+              }`}
+            >
+              {entry.title}
+            </h3>
+            <p className="text-xs">{entry.description}</p>
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+};
+
+export default JournalList;
@@ -1,10 +1,83 @@
 import React from 'react';
+import { IoArrowUndo } from 'react-icons/io5';
+
+interface NarrativeMessageProps {
+  message: GameMessage;
+  onRewind?: (turn: number) => void;
+  onRetry?: () => void;
+}
+
+const NarrativeMessage: React.FC<NarrativeMessageProps> = ({
+  message,
+  onRewind,
+  onRetry,
+}) => {
+  switch (message.kind) {
+    case 'narration':
+      return <p>{message.text}</p>;
+    case 'player':
+      return (
+        <p className="group flex items-center gap-2 text-gray-400">
+          &gt; {message.text}
+          {onRewind && (
+            <button
+              className="invisible hover:text-gray-50 group-hover:visible"
+              title="Rewind to before this"
+              onClick={() => onRewind(message.turn - 1)}
+            >
+              <IoArrowUndo />
+            </button>
+          )}
+        </p>
+      );
+    case 'system_event':
+      return <p className="text-sm italic text-blue-300">{message.text}</p>;
+    case 'error':
+      return (
+        <p className="flex items-center gap-2 text-sm italic text-red-400">
+          {message.text}
+          {message.can_retry && onRetry && (
+            <button className="underline hover:text-gray-50" onClick={onRetry}>
+              Try again
+            </button>
+          )}
+        </p>
+      );
+    case 'scene_transition':
+      return (
+        <h3 className="mt-2 font-overlock-sc text-lg">{message.scene_name}</h3>
+      );
+    case 'item_delta':
+      return (
+        <p
+          className={`text-sm italic ${
+            message.gained ? 'text-green-300' : 'text-red-300'
+          }`}
+        >
+          {message.gained ? '+' : '-'} {message.item}
+        </p>
+      );
+    case 'meta':
+      return (
+        <div className="text-sm">
+          <p className="text-gray-400">&gt; {message.query}</p>
+          <p className="italic text-gray-300">{message.answer}</p>
+        </div>
+      );
+  }
+};
 
 interface NarrativeWindowProps {
-  messages: string[];
+  messages: GameMessage[];
+  onRewind?: (turn: number) => void;
+  onRetry?: () => void;
 }
 
-const NarrativeWindow: React.FC<NarrativeWindowProps> = ({ messages }) => {
+const NarrativeWindow: React.FC<NarrativeWindowProps> = ({
+  messages,
+  onRewind,
+  onRetry,
+}) => {
   const containerRef = React.useRef<HTMLDivElement>(null);
   React.useEffect(() => {
     containerRef.current?.scrollTo(0, containerRef.current?.scrollHeight);
@@ -15,7 +88,15 @@ const NarrativeWindow: React.FC<NarrativeWindowProps> = ({ messages }) => {
       className="flex h-full max-h-[78vh] w-full flex-grow-0 flex-col gap-2 overflow-scroll scroll-smooth rounded-md border-2 border-gray-50 p-2 text-[14px]"
     >
       {messages.map((message, id) => {
-        return <p key={id}>{message}</p>;
+        return (
+          <NarrativeMessage
+            key={id}
+            message={message}
+            onRewind={onRewind}
+            // Only the latest failure can be retried
+            onRetry={id === messages.length - 1 ? onRetry : undefined}
+          />
+        );
       })}
     </div>
   );
@@ -7,12 +7,14 @@ interface SlideoutPanelProps {
   children: React.ReactNode;
   tabContentOpen?: React.ReactNode;
   tabContentClosed?: React.ReactNode;
+  tabPosition?: string;
 }
 
 const SlideoutPanel: React.FC<SlideoutPanelProps> = ({
   children,
   tabContentOpen = <GrClose />,
   tabContentClosed = <IoIosArrowBack />,
+  tabPosition = 'top-14',
 }) => {
   const [open, setOpen] = React.useState(false);
   return (
@@ -23,7 +25,7 @@ const SlideoutPanel: React.FC<SlideoutPanelProps> = ({
     >
       <div className="relative h-full">
         <button
-          className="absolute right-0 top-14 h-16 w-8 translate-x-[100%] cursor-pointer rounded-r-md bg-yellow-600 p-2 text-blue-950"
+          className={`absolute right-0 ${tabPosition} h-16 w-8 translate-x-[100%] cursor-pointer rounded-r-md bg-yellow-600 p-2 text-blue-950`}
           onClick={() => setOpen((open) => !open)}
         >
           {open
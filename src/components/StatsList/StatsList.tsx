@@ -0,0 +1,38 @@
+import React from 'react';
+
+interface StatsListProps {
+  stats: PlayerStats;
+}
+
+const StatsList: React.FC<StatsListProps> = ({ stats }) => {
+  return (
+    <div className="flex h-full flex-col items-center gap-6">
+      <h2 className="font-overlock-sc text-lg">Stats</h2>
+
+      <ul className="no-scrollbar flex h-full w-48 flex-1 flex-col gap-4 overflow-y-scroll">
+        {stats.stats.map((stat) => (
+          <li key={stat.name} className="flex flex-col gap-1">
+            <div className="flex justify-between font-overlock-sc text-sm">
+              <span>{stat.name}</span>
+              <span>
+                {stat.value}/{stat.max_value}
+              </span>
+            </div>
+            <div className="h-2 w-full rounded-full bg-yellow-950">
+              <div
+                className={`h-full rounded-full ${
+                  stat.fatal_at_zero ? 'bg-red-700' : 'bg-yellow-600'
+                }`}
+                style={{
+                  width: `${(stat.value / Math.max(stat.max_value, 1)) * 100}%`,
+                }}
+              />
+            </div>
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+};
+
+export default StatsList;
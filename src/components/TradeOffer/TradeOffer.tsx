@@ -0,0 +1,101 @@
+import React from 'react';
+import Modal from '../Modal/Modal';
+import PromptButton from '../PromptButton/PromptButton';
+
+interface ItemSelectionProps {
+  title: string;
+  items: string[];
+  selected: string[];
+  setSelected: (selected: string[]) => void;
+}
+
+const ItemSelection: React.FC<ItemSelectionProps> = ({
+  title,
+  items,
+  selected,
+  setSelected,
+}) => {
+  const toggle = (item: string) => {
+    setSelected(
+      selected.includes(item)
+        ? selected.filter((i) => i !== item)
+        : [...selected, item]
+    );
+  };
+
+  return (
+    <div className="flex flex-col gap-2">
+      <h3 className="font-overlock-sc text-lg">{title}</h3>
+      <ul className="flex flex-col gap-1">
+        {items.map((item) => (
+          <li key={item}>
+            <label className="flex items-center gap-2">
+              <input
+                type="checkbox"
+                checked={selected.includes(item)}
+                onChange={() => toggle(item)}
+              />
+              {item}
+            </label>
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+};
+
+interface TradeOfferProps {
+  open: boolean;
+  setOpen: (open: boolean) => void;
+  traderName: string;
+  trade: CharacterTrade;
+  playerInventory: string[];
+  characterInventory: string[];
+  onSubmit: (toPlayer: string[], fromPlayer: string[]) => void;
+}
+
+const TradeOffer: React.FC<TradeOfferProps> = ({
+  open,
+  setOpen,
+  traderName,
+  trade,
+  playerInventory,
+  characterInventory,
+  onSubmit,
+}) => {
+  const [toPlayer, setToPlayer] = React.useState(trade.to_player);
+  const [fromPlayer, setFromPlayer] = React.useState(trade.from_player);
+
+  return (
+    <Modal open={open} setOpen={setOpen} clickOut>
+      <div className="min-h-72 min-w-96">
+        <h2 className="mb-6 text-xl">Counter-offer</h2>
+        <div className="mb-12 grid grid-cols-2 gap-8">
+          <ItemSelection
+            title="You give"
+            items={playerInventory}
+            selected={fromPlayer}
+            setSelected={setFromPlayer}
+          />
+          <ItemSelection
+            title={`${traderName} gives`}
+            items={characterInventory}
+            selected={toPlayer}
+            setSelected={setToPlayer}
+          />
+        </div>
+        <div className="absolute bottom-2 right-2 flex items-center justify-center gap-4">
+          <PromptButton onClick={() => setOpen(false)}>Cancel</PromptButton>
+          <PromptButton
+            disabled={!toPlayer.length || !fromPlayer.length}
+            onClick={() => onSubmit(toPlayer, fromPlayer)}
+          >
+            Offer
+          </PromptButton>
+        </div>
+      </div>
+    </Modal>
+  );
+};
+
+export default TradeOffer;
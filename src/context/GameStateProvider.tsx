@@ -3,11 +3,19 @@ import React from 'react';
 interface IGameStateContext {
   gameState: GameState | null;
   setGameState: React.Dispatch<React.SetStateAction<GameState | null>>;
+  sessionId: string | null;
+  setSessionId: React.Dispatch<React.SetStateAction<string | null>>;
+  pendingRequests: number[];
+  setPendingRequests: React.Dispatch<React.SetStateAction<number[]>>;
 }
 
 export const GameStateContext = React.createContext<IGameStateContext>({
   gameState: null,
   setGameState: () => {},
+  sessionId: null,
+  setSessionId: () => {},
+  pendingRequests: [],
+  setPendingRequests: () => {},
 });
 
 interface GameStateProviderProps {
@@ -16,8 +24,19 @@ interface GameStateProviderProps {
 
 const GameStateProvider: React.FC<GameStateProviderProps> = ({ children }) => {
   const [gameState, setGameState] = React.useState<GameState | null>(null);
+  const [sessionId, setSessionId] = React.useState<string | null>(null);
+  const [pendingRequests, setPendingRequests] = React.useState<number[]>([]);
   return (
-    <GameStateContext.Provider value={{ gameState, setGameState }}>
+    <GameStateContext.Provider
+      value={{
+        gameState,
+        setGameState,
+        sessionId,
+        setSessionId,
+        pendingRequests,
+        setPendingRequests,
+      }}
+    >
       {children}
     </GameStateContext.Provider>
   );
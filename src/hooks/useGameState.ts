@@ -6,9 +6,16 @@ import { appWindow } from '@tauri-apps/api/window';
 import { Event, UnlistenFn } from '@tauri-apps/api/event';
 
 const useGameState = () => {
-  const { gameState, setGameState } = React.useContext(GameStateContext);
+  const {
+    gameState,
+    setGameState,
+    sessionId,
+    setSessionId,
+    pendingRequests,
+    setPendingRequests,
+  } = React.useContext(GameStateContext);
   const navigate = useNavigate();
-  const [loading, setLoading] = React.useState(true);
+  const loading = pendingRequests.length > 0;
 
   React.useEffect(() => {
     console.log('Initializing event listener for game state.');
@@ -16,6 +23,9 @@ const useGameState = () => {
     const subscribe = async () => {
       unlisten = await appWindow.listen('state', (event: Event<GameState>) => {
         if (!event.payload) return;
+        // Other sessions may still be running in the background
+        const { game_id, save_slot } = event.payload;
+        if (`${game_id}/${save_slot}` !== sessionId) return;
         console.log('Setting game state through event update: ', event.payload);
         setGameState(event.payload);
       });
@@ -24,9 +34,38 @@ const useGameState = () => {
     return () => {
       unlisten();
     };
-  }, [setGameState]);
+  }, [setGameState, sessionId]);
+
+  React.useEffect(() => {
+    let unlisten: UnlistenFn;
+    const subscribe = async () => {
+      unlisten = await appWindow.listen(
+        'request',
+        (event: Event<SessionRequestUpdate>) => {
+          const { session_id, request_id, status, message } = event.payload;
+          if (session_id !== sessionId) return;
+          if (status === 'failed') {
+            console.error(`Request ${request_id} failed: `, message);
+          }
+          setPendingRequests((pending) =>
+            pending.filter((id) => id !== request_id)
+          );
+        }
+      );
+    };
+    subscribe();
+    return () => {
+      unlisten();
+    };
+  }, [setPendingRequests, sessionId]);
+
+  // Requests are processed in the background, with their results arriving as
+  // state updates, so the UI only keeps track of which are still pending
+  const track = (requestId: number) => {
+    setPendingRequests((pending) => [...pending, requestId]);
+  };
 
-  const startGame = async (gameId: string) => {
+  const startGame = async (gameId: string, saveSlot?: string) => {
     if (gameState) {
       console.warn(
         'Attempted to start a new game, despite a game already loaded.'
@@ -36,21 +75,26 @@ const useGameState = () => {
 
     console.log('Starting a new game with ID: ', gameId);
 
-    setLoading(true);
     try {
-      const { game_state } = (await invoke('start_game', {
-        request: {
-          game_id: gameId,
-        },
-      })) as { game_state: GameState };
-
-      console.log(
-        'Received game state for new game with initial narrative: ',
-        game_state.messages[0]
-      );
+      const { session_id, game_state, request_id } = (await invoke(
+        'start_game',
+        {
+          request: {
+            game_id: gameId,
+            save_slot: saveSlot ?? null,
+          },
+        }
+      )) as {
+        session_id: string;
+        game_state: GameState;
+        request_id: number | null;
+      };
 
+      console.log(`Started game session ${session_id}.`);
+
+      setSessionId(session_id);
+      setPendingRequests(request_id !== null ? [request_id] : []);
       setGameState(game_state);
-      setLoading(false);
     } catch (error) {
       console.error('Error starting game: ', error);
       navigate('/mainmenu');
@@ -63,24 +107,56 @@ const useGameState = () => {
         "Attempted to send a message to a game that doesn't exist."
       );
     }
-    setLoading(true);
 
     console.log(`Player entered narrative message "${message}"`);
 
     try {
-      const { game_state } = (await invoke('game_prompt', {
-        request: { prompt: message },
-      })) as { game_state: GameState };
+      const { request_id } = (await invoke('game_prompt', {
+        request: { session_id: sessionId, prompt: message },
+      })) as { request_id: number };
+      track(request_id);
+    } catch (error) {
+      console.error('Error occurred sending prompt: ', error);
+      navigate('/mainmenu');
+    }
+  };
 
-      console.log(
-        'Received response from narrator: ',
-        game_state.messages[game_state.messages.length - 1]
-      );
+  const rewindToTurn = async (turn: number) => {
+    if (!gameState) {
+      console.error("Attempted to rewind a game that doesn't exist.");
+    }
 
-      setGameState(game_state);
-      setLoading(false);
+    console.log(`Player rewound the game to turn ${turn}`);
+
+    try {
+      const { request_id } = (await invoke('rewind_turn', {
+        request: { session_id: sessionId, turn },
+      })) as { request_id: number };
+      track(request_id);
     } catch (error) {
-      console.error('Error occurred sending prompt: ', error);
+      // The game is left as it was, so the player can keep playing
+      console.error('Failed to rewind game: ', error);
+    }
+  };
+
+  const cancelRequest = async () => {
+    console.log('Player stopped waiting on a response.');
+
+    try {
+      await invoke('cancel_request', { request: { session_id: sessionId } });
+    } catch (error) {
+      console.error('Failed to cancel request: ', error);
+    }
+  };
+
+  const sendCharacterPrompt = async (request: object) => {
+    try {
+      const { request_id } = (await invoke('character_prompt', {
+        request: { session_id: sessionId, ...request },
+      })) as { request_id: number };
+      track(request_id);
+    } catch (error) {
+      console.error('Failed to send character prompt: ', error);
       navigate('/mainmenu');
     }
   };
@@ -91,23 +167,10 @@ const useGameState = () => {
         "Attempted to send a character message to a game session that doesn't exist."
       );
     }
-    setLoading(true);
 
     console.log(`Player entered character message "${message}"`);
 
-    try {
-      const { game_state } = (await invoke('character_prompt', {
-        request: { message },
-      })) as { game_state: GameState };
-      setGameState(game_state);
-      setLoading(false);
-    } catch (error) {
-      console.error(
-        'Failed to receive response from character prompt: ',
-        error
-      );
-      navigate('/mainmenu');
-    }
+    await sendCharacterPrompt({ message });
   };
 
   const characterTradeResponse = async (accept: boolean) => {
@@ -116,23 +179,29 @@ const useGameState = () => {
         "Attempted to send a character trade response to a game session that doesn't exist."
       );
     }
-    setLoading(true);
 
     console.log(`Player entered character trade response "${accept}"`);
 
-    try {
-      const { game_state } = (await invoke('character_prompt', {
-        request: { trade_accept: accept },
-      })) as { game_state: GameState };
-      setGameState(game_state);
-      setLoading(false);
-    } catch (error) {
+    await sendCharacterPrompt({ trade_accept: accept });
+  };
+
+  const characterTradeCounterOffer = async (
+    toPlayer: string[],
+    fromPlayer: string[]
+  ) => {
+    if (!gameState) {
       console.error(
-        'Failed to receive response from character prompt: ',
-        error
+        "Attempted to send a character trade counter-offer to a game session that doesn't exist."
       );
-      navigate('/mainmenu');
     }
+
+    console.log(
+      `Player offered "${fromPlayer.join(', ')}" for "${toPlayer.join(', ')}"`
+    );
+
+    await sendCharacterPrompt({
+      trade_counter_offer: { to_player: toPlayer, from_player: fromPlayer },
+    });
   };
 
   const endCharacterConversation = async () => {
@@ -141,22 +210,23 @@ const useGameState = () => {
         "Attempted to end a character conversation to a game session that doesn't exist."
       );
     }
-    setLoading(true);
 
     console.log('Player ended character conversation.');
 
+    // Ending the conversation stops the character mid-response if need be
+    await sendCharacterPrompt({ end_conversation: true });
+  };
+
+  const saveGame = async () => {
+    console.log('Player saved the game.');
+
     try {
-      const { game_state } = (await invoke('character_prompt', {
-        request: { end_conversation: true },
-      })) as { game_state: GameState };
-      setGameState(game_state);
-      setLoading(false);
+      const { request_id } = (await invoke('save_game', {
+        request: { session_id: sessionId },
+      })) as { request_id: number };
+      track(request_id);
     } catch (error) {
-      console.error(
-        'Failed to receive response from character prompt: ',
-        error
-      );
-      navigate('/mainmenu');
+      console.error('Failed to save game: ', error);
     }
   };
 
@@ -164,28 +234,33 @@ const useGameState = () => {
     if (!gameState) {
       console.error("Attempted to end a game session that doesn't exist.");
     }
-    setLoading(true);
 
     console.log('Player ended the game session.');
 
     try {
-      // await invoke('end_game'); TODO: Implement this is Rust
-      setGameState(null);
-      setLoading(false);
+      // The game is saved, so starting it again picks up where it was left
+      await invoke('suspend_game', { request: { session_id: sessionId } });
     } catch (error) {
-      console.error('Failed to end game: ', error);
-      navigate('/mainmenu');
+      console.error('Failed to suspend game: ', error);
     }
+
+    setGameState(null);
+    setSessionId(null);
+    setPendingRequests([]);
   };
 
   return {
     gameState,
     startGame,
     sendNarrativeMessage,
+    rewindToTurn,
+    cancelRequest,
     loading,
     sendCharacterMessage,
     characterTradeResponse,
+    characterTradeCounterOffer,
     endCharacterConversation,
+    saveGame,
     endGame,
   };
 };
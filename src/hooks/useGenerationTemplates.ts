@@ -0,0 +1,29 @@
+import { invoke } from '@tauri-apps/api';
+import React from 'react';
+import {
+  GenerationTemplate,
+  ListGenerationTemplatesResponse,
+} from '../types/CreateNewGame';
+
+const useGenerationTemplates = () => {
+  const [templates, setTemplates] = React.useState<GenerationTemplate[]>([]);
+
+  React.useEffect(() => {
+    const fetchTemplates = async () => {
+      try {
+        const response = (await invoke(
+          'list_generation_templates'
+        )) as ListGenerationTemplatesResponse;
+        setTemplates(response.templates || []);
+      } catch (error) {
+        console.error('Error fetching generation templates:', error);
+      }
+    };
+
+    fetchTemplates();
+  }, []);
+
+  return { templates };
+};
+
+export default useGenerationTemplates;
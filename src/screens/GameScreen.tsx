@@ -14,6 +14,9 @@ import PlayerEntry from '../components/PlayerEntry/PlayerEntry';
 import { useNavigate } from 'react-router-dom';
 import SlideoutPanel from '../components/SlideoutPanel/SlideoutPanel';
 import InventoryList from '../components/InventoryList/InventoryList';
+import JournalList from '../components/JournalList/JournalList';
+import StatsList from '../components/StatsList/StatsList';
+import { GiHearts, GiScrollUnfurled } from 'react-icons/gi';
 
 const GameScreen = () => {
   const navigate = useNavigate();
@@ -21,13 +24,25 @@ const GameScreen = () => {
     redirect: '/mainmenu',
   });
   const [playerInput, setPlayerInput] = React.useState<string>('');
-  const { gameState, sendNarrativeMessage, endGame } = useGameState();
+  const {
+    gameState,
+    sendNarrativeMessage,
+    rewindToTurn,
+    cancelRequest,
+    loading,
+    saveGame,
+    endGame,
+  } = useGameState();
 
   if (!gameState || !game) {
     navigate('/gamemenu');
     return null;
   }
 
+  const lastEntry = gameState.messages
+    .filter((m) => m.kind === 'player')
+    .slice(-1)[0];
+
   return (
     <BackgroundDiv fade={false}>
       <CharacterWindow
@@ -58,9 +73,7 @@ const GameScreen = () => {
               {
                 icon: <IoMdSave />,
                 tooltip: 'Save Game',
-                onClick: () => {
-                  console.log('Saved game!');
-                },
+                onClick: saveGame,
               },
               {
                 icon: <IoSettingsSharp />,
@@ -71,7 +84,23 @@ const GameScreen = () => {
               },
             ]}
           />
-          <NarrativeWindow messages={gameState.messages} />
+          <NarrativeWindow
+            messages={gameState.messages}
+            onRewind={rewindToTurn}
+            onRetry={
+              lastEntry?.kind === 'player'
+                ? () => sendNarrativeMessage(lastEntry.text)
+                : undefined
+            }
+          />
+          {loading && (
+            <button
+              className="self-end text-sm italic underline hover:text-gray-50"
+              onClick={cancelRequest}
+            >
+              Stop waiting
+            </button>
+          )}
           <PlayerEntry
             value={playerInput}
             onChange={(e) => {
@@ -91,6 +120,18 @@ const GameScreen = () => {
       <SlideoutPanel tabContentClosed={<BsBackpack />}>
         <InventoryList inventory={gameState.inventory} />
       </SlideoutPanel>
+      <SlideoutPanel
+        tabContentClosed={<GiScrollUnfurled />}
+        tabPosition="top-32"
+      >
+        <JournalList journal={gameState.journal} />
+      </SlideoutPanel>
+      <SlideoutPanel
+        tabContentClosed={<GiHearts />}
+        tabPosition="top-[12.5rem]"
+      >
+        <StatsList stats={gameState.stats} />
+      </SlideoutPanel>
     </BackgroundDiv>
   );
 };
@@ -8,6 +8,9 @@ import Select from '../components/Select/Select';
 import { useNavigate } from 'react-router-dom';
 import useIncompleteGame from '../hooks/useIncompleteGame';
 import AlertDialog from '../components/AlertDialog/AlertDialog';
+import useGenerationTemplates from '../hooks/useGenerationTemplates';
+
+const NO_TEMPLATE = 'none';
 
 interface GenerateStep {
   title: string;
@@ -28,6 +31,15 @@ const steps: GenerateStep[] = [
     entryType: 'text',
     default: '',
   },
+  {
+    title: 'Template',
+    description:
+      'Choose a template to shape the genre, tone, and size of the generated game, or none to leave it up to the prompt.',
+    setField: 'template',
+    entryType: 'select',
+    selectOptions: [NO_TEMPLATE],
+    default: NO_TEMPLATE,
+  },
   {
     title: 'Narrative Quality',
     description:
@@ -55,6 +67,14 @@ const steps: GenerateStep[] = [
     sliderRange: [0, 2],
     default: '0.8',
   },
+  {
+    title: 'Seed',
+    description:
+      'Optionally provide a number to seed generation with. Using the same seed and settings again will reproduce a similar game. Leave this blank for a random seed.',
+    setField: 'seed',
+    entryType: 'text',
+    default: '',
+  },
 ];
 
 const GenerateOptionsScreen = () => {
@@ -68,6 +88,7 @@ const GenerateOptionsScreen = () => {
   const navigate = useNavigate();
 
   const { incompleteGame, clearIncompleteGame } = useIncompleteGame();
+  const { templates } = useGenerationTemplates();
 
   React.useEffect(() => {
     setTimeout(() => {
@@ -76,7 +97,7 @@ const GenerateOptionsScreen = () => {
   }, []);
 
   React.useEffect(() => {
-    if (Object.keys(request).length === 4) {
+    if (Object.keys(request).length === steps.length) {
       console.log('Formed request: ', request);
       navigate('/generate-game', { state: { request } });
     }
@@ -88,7 +109,11 @@ const GenerateOptionsScreen = () => {
     ),
     select: () => (
       <Select
-        options={steps[stepIndex].selectOptions || []}
+        options={
+          steps[stepIndex].setField === 'template'
+            ? [NO_TEMPLATE, ...templates.map((template) => template.id)]
+            : steps[stepIndex].selectOptions || []
+        }
         value={value}
         onChange={(e) => setValue(e.target.value)}
       />
@@ -108,7 +133,10 @@ const GenerateOptionsScreen = () => {
     setRequest((request) => {
       return {
         ...request,
-        [steps[stepIndex].setField]: value,
+        [steps[stepIndex].setField]:
+          steps[stepIndex].setField === 'template' && value === NO_TEMPLATE
+            ? undefined
+            : value,
       };
     });
     setOpen(false);
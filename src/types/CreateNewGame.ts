@@ -6,6 +6,8 @@ export interface CreateNewGameRequest {
   image_content_setting?: ContentSetting;
   temperature_setting?: string;
   resume_previous?: string;
+  template?: string;
+  seed?: string;
 }
 
 export type ContentSetting = 'minimum' | 'moderate' | 'high';
@@ -16,3 +18,30 @@ export interface CreateNewGameResponse {
   error?: 'file_system_error' | 'setup_error' | 'game_generation_error';
   message?: string;
 }
+
+export interface GenerationTemplate {
+  id: string;
+  name: string;
+  description: string;
+  genre: string | null;
+  tone: string | null;
+  target_play_length: string | null;
+  age_rating: string | null;
+  art_direction: string | null;
+  scene_count: CountRange | null;
+  character_count: CountRange | null;
+  item_count: CountRange | null;
+  attributes: string[] | null;
+}
+
+export interface CountRange {
+  min: number;
+  max: number;
+}
+
+export interface ListGenerationTemplatesResponse {
+  success: boolean;
+  templates?: GenerationTemplate[];
+  error?: 'setup_error';
+  message?: string;
+}
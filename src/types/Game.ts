@@ -7,12 +7,60 @@ export interface Game {
   scenes: Scene[];
   characters: Character[];
   items: Item[];
+  quest: Quest | null;
+  attributes: PlayerAttributes;
+  seed: number;
 }
 
+export interface PlayerAttributes {
+  attributes: PlayerAttribute[];
+}
+
+export interface PlayerAttribute {
+  name: string;
+  description: string;
+  starting_value: number;
+  max_value: number;
+  fatal_at_zero: boolean;
+}
+
+export interface Quest {
+  objectives: Objective[];
+}
+
+export interface Objective {
+  id: string;
+  title: string;
+  description: string;
+  prerequisites: string[];
+  trigger: ObjectiveTrigger;
+  is_final: boolean;
+}
+
+export type ObjectiveTrigger =
+  | { type: 'obtain_item'; item: string }
+  | { type: 'reach_scene'; scene: string }
+  | { type: 'talk_to'; character: string }
+  | { type: 'narrator' };
+
 export interface Item {
   name: string;
   description: string;
   image: Image;
+  properties: ItemProperties;
+}
+
+export interface ItemProperties {
+  usable: boolean;
+  effect: string;
+  consumable: boolean;
+  combinations: ItemCombination[];
+  unlocks: string[];
+}
+
+export interface ItemCombination {
+  with: string;
+  result: string;
 }
 
 export interface Character {
@@ -23,6 +71,17 @@ export interface Character {
   thoughts: string;
   inventory: string[];
   image: Image;
+  movement: CharacterMovement;
+}
+
+export type CharacterMovement =
+  | { type: 'stationary' }
+  | { type: 'follows_player' }
+  | { type: 'schedule'; stops: ScheduleStop[] };
+
+export interface ScheduleStop {
+  after_objective: string;
+  scene: string;
 }
 
 export interface Scene {
@@ -32,6 +91,14 @@ export interface Scene {
   characters: string[];
   items: string[];
   image: Image;
+  exits: SceneExit[];
+}
+
+export interface SceneExit {
+  name: string;
+  scene: string;
+  required_item: string | null;
+  permission_from: string | null;
 }
 
 export interface Image {
@@ -56,14 +123,6 @@ export interface Narrative {
   }[];
 }
 
-// export interface PlayerAttribute {
-//   name: string;
-//   description: string;
-//   advancement: string;
-//   significance: string;
-//   startingValue: number;
-// }
-
 // export interface KeyItem {
 //   name: string;
 //   description: string;
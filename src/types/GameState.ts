@@ -1,16 +1,72 @@
 interface GameState {
+  game_id: string;
+  save_slot: string;
   current_scene_name: string | null;
-  messages: string[];
+  messages: GameMessage[];
+  turn: number;
   inventory: string[];
   character_interaction: CharacterInteraction | null;
+  character_save_data: Record<string, CharacterSaveData>;
+  journal: Journal;
+  stats: PlayerStats;
   end_game: string | null;
 }
 
+type GameMessage = {
+  turn: number;
+  timestamp: number;
+} & GameMessageKind;
+
+type GameMessageKind =
+  | { kind: 'narration'; text: string }
+  | { kind: 'player'; text: string }
+  | { kind: 'system_event'; text: string }
+  | { kind: 'error'; text: string; can_retry: boolean }
+  | { kind: 'scene_transition'; scene_name: string }
+  | { kind: 'item_delta'; item: string; gained: boolean }
+  | { kind: 'meta'; query: string; answer: string };
+
+interface SessionRequestUpdate {
+  session_id: string;
+  request_id: number;
+  status: 'completed' | 'failed' | 'cancelled';
+  message: string | null;
+}
+
+interface Journal {
+  entries: JournalEntry[];
+}
+
+interface JournalEntry {
+  id: string;
+  title: string;
+  description: string;
+  prerequisites: string[];
+  is_final: boolean;
+  status: 'locked' | 'active' | 'completed';
+}
+
+interface PlayerStats {
+  stats: PlayerStat[];
+}
+
+interface PlayerStat {
+  name: string;
+  value: number;
+  max_value: number;
+  fatal_at_zero: boolean;
+}
+
 interface CharacterInteraction {
   character_name: string;
   messages: CharacterMessage[];
   trade: CharacterTrade | null;
   closed: boolean;
+  participants: CharacterParticipant[];
+}
+
+interface CharacterParticipant {
+  character_name: string;
 }
 
 interface CharacterMessage {
@@ -19,8 +75,13 @@ interface CharacterMessage {
 }
 
 interface CharacterTrade {
-  to_player: string | null;
-  from_player: string | null;
+  character_name: string;
+  to_player: string[];
+  from_player: string[];
+}
+
+interface CharacterSaveData {
+  character_inventory: string[];
 }
 
 interface GameGenerationUpdate {
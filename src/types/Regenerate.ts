@@ -0,0 +1,21 @@
+import { Game } from './Game';
+
+export type RegenerationTarget =
+  | { type: 'cover' }
+  | { type: 'narrative_page'; target: number }
+  | { type: 'scene'; target: string }
+  | { type: 'character'; target: string }
+  | { type: 'item'; target: string };
+
+export interface RegenerateRequest {
+  game_id: string;
+  target: RegenerationTarget;
+  instruction?: string;
+}
+
+export interface RegenerateResponse {
+  success: boolean;
+  game?: Game;
+  error?: 'setup_error' | 'generation_error';
+  message?: string;
+}